@@ -500,7 +500,9 @@ impl ActiveThread {
             ThreadEvent::ShowError(error) => {
                 self.last_error = Some(error.clone());
             }
-            ThreadEvent::StreamedCompletion | ThreadEvent::SummaryChanged => {
+            ThreadEvent::StreamedCompletion
+            | ThreadEvent::SummaryChanged
+            | ThreadEvent::TemperatureChanged => {
                 self.save_thread(cx);
             }
             ThreadEvent::DoneStreaming => {
@@ -644,7 +646,7 @@ impl ActiveThread {
                     }
                 }
             }
-            ThreadEvent::CheckpointChanged => cx.notify(),
+            ThreadEvent::CheckpointChanged | ThreadEvent::StreamedToolOutput(_) => cx.notify(),
         }
     }
 
@@ -1739,33 +1741,41 @@ impl ActiveThread {
                         .child(Label::new(output).size(LabelSize::Small).buffer_font(cx)),
                 ),
                 ToolUseStatus::Running => container.child(
-                    content_container().child(
-                        h_flex()
-                            .gap_1()
-                            .pb_1()
-                            .border_t_1()
-                            .border_color(self.tool_card_border_color(cx))
-                            .child(
-                                Icon::new(IconName::ArrowCircle)
-                                    .size(IconSize::Small)
-                                    .color(Color::Accent)
-                                    .with_animation(
-                                        "arrow-circle",
-                                        Animation::new(Duration::from_secs(2)).repeat(),
-                                        |icon, delta| {
-                                            icon.transform(Transformation::rotate(percentage(
-                                                delta,
-                                            )))
-                                        },
-                                    ),
-                            )
-                            .child(
-                                Label::new("Running…")
-                                    .size(LabelSize::XSmall)
-                                    .color(Color::Muted)
+                    content_container()
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .pb_1()
+                                .border_t_1()
+                                .border_color(self.tool_card_border_color(cx))
+                                .child(
+                                    Icon::new(IconName::ArrowCircle)
+                                        .size(IconSize::Small)
+                                        .color(Color::Accent)
+                                        .with_animation(
+                                            "arrow-circle",
+                                            Animation::new(Duration::from_secs(2)).repeat(),
+                                            |icon, delta| {
+                                                icon.transform(Transformation::rotate(percentage(
+                                                    delta,
+                                                )))
+                                            },
+                                        ),
+                                )
+                                .child(
+                                    Label::new("Running…")
+                                        .size(LabelSize::XSmall)
+                                        .color(Color::Muted)
+                                        .buffer_font(cx),
+                                ),
+                        )
+                        .when(!tool_use.partial_output.is_empty(), |container| {
+                            container.child(
+                                Label::new(tool_use.partial_output.clone())
+                                    .size(LabelSize::Small)
                                     .buffer_font(cx),
-                            ),
-                    ),
+                            )
+                        }),
                 ),
                 ToolUseStatus::Error(err) => container.child(
                     content_container()
@@ -83,12 +83,23 @@ pub struct NewThread {
 pub struct ManageProfiles {
     #[serde(default)]
     pub customize_tools: Option<AgentProfileId>,
+    /// Whether to save the currently enabled tools as a new profile.
+    #[serde(default)]
+    pub save_current_tools: bool,
 }
 
 impl ManageProfiles {
     pub fn customize_tools(profile_id: AgentProfileId) -> Self {
         Self {
             customize_tools: Some(profile_id),
+            ..Self::default()
+        }
+    }
+
+    pub fn save_current_tools() -> Self {
+        Self {
+            save_current_tools: true,
+            ..Self::default()
         }
     }
 }
@@ -3,7 +3,7 @@ mod profile_modal_header;
 use std::sync::Arc;
 
 use assistant_settings::{AgentProfile, AgentProfileId, AssistantSettings};
-use assistant_tool::ToolWorkingSet;
+use assistant_tool::{ToolSource, ToolWorkingSet};
 use convert_case::{Case, Casing as _};
 use editor::Editor;
 use fs::Fs;
@@ -11,6 +11,7 @@ use gpui::{
     DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Subscription, WeakEntity,
     prelude::*,
 };
+use indexmap::IndexMap;
 use settings::{Settings as _, update_settings_file};
 use ui::{
     KeyBinding, ListItem, ListItemSpacing, ListSeparator, Navigable, NavigableEntry, prelude::*,
@@ -80,6 +81,11 @@ pub struct ViewProfileMode {
 pub struct NewProfileMode {
     name_editor: Entity<Editor>,
     base_profile_id: Option<AgentProfileId>,
+    /// Whether the profile is created from the currently enabled tools, rather than from
+    /// the base profile.
+    from_current_tools: bool,
+    /// The existing profile the user was warned would be overwritten.
+    overwrite_profile_id: Option<AgentProfileId>,
 }
 
 pub struct ManageProfilesModal {
@@ -107,6 +113,8 @@ impl ManageProfilesModal {
 
                     if let Some(profile_id) = action.customize_tools.clone() {
                         this.configure_tools(profile_id, window, cx);
+                    } else if action.save_current_tools {
+                        this.new_profile_from_current_tools(window, cx);
                     }
 
                     this
@@ -143,6 +151,20 @@ impl ManageProfilesModal {
         base_profile_id: Option<AgentProfileId>,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.show_new_profile(base_profile_id, false, window, cx);
+    }
+
+    fn new_profile_from_current_tools(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_new_profile(None, true, window, cx);
+    }
+
+    fn show_new_profile(
+        &mut self,
+        base_profile_id: Option<AgentProfileId>,
+        from_current_tools: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let name_editor = cx.new(|cx| Editor::single_line(window, cx));
         name_editor.update(cx, |editor, cx| {
@@ -152,6 +174,8 @@ impl ManageProfilesModal {
         self.mode = Mode::NewProfile(NewProfileMode {
             name_editor,
             base_profile_id,
+            from_current_tools,
+            overwrite_profile_id: None,
         });
         self.focus_handle(cx).focus(window);
     }
@@ -221,22 +245,40 @@ impl ManageProfilesModal {
                 let name = mode.name_editor.read(cx).text(cx);
                 let profile_id = AgentProfileId(name.to_case(Case::Kebab).into());
 
-                let profile = AgentProfile {
-                    name: name.into(),
-                    tools: base_profile
-                        .as_ref()
-                        .map(|profile| profile.tools.clone())
-                        .unwrap_or_default(),
-                    enable_all_context_servers: base_profile
-                        .as_ref()
-                        .map(|profile| profile.enable_all_context_servers)
-                        .unwrap_or_default(),
-                    context_servers: base_profile
-                        .map(|profile| profile.context_servers)
-                        .unwrap_or_default(),
+                // Ask before overwriting an existing profile, which confirming again does.
+                let overwrite = settings.profiles.contains_key(&profile_id);
+                if overwrite && mode.overwrite_profile_id.as_ref() != Some(&profile_id) {
+                    if let Mode::NewProfile(mode) = &mut self.mode {
+                        mode.overwrite_profile_id = Some(profile_id);
+                    }
+                    cx.notify();
+                    return;
+                }
+
+                let profile = if mode.from_current_tools {
+                    self.current_tools_profile(name.into(), cx)
+                } else {
+                    AgentProfile {
+                        name: name.into(),
+                        tools: base_profile
+                            .as_ref()
+                            .map(|profile| profile.tools.clone())
+                            .unwrap_or_default(),
+                        enable_all_context_servers: base_profile
+                            .as_ref()
+                            .map(|profile| profile.enable_all_context_servers)
+                            .unwrap_or_default(),
+                        context_servers: base_profile
+                            .as_ref()
+                            .map(|profile| profile.context_servers.clone())
+                            .unwrap_or_default(),
+                        user_scripts: base_profile
+                            .map(|profile| profile.user_scripts)
+                            .unwrap_or_default(),
+                    }
                 };
 
-                self.create_profile(profile_id.clone(), profile, cx);
+                self.save_profile(profile_id.clone(), profile, overwrite, cx);
                 self.view_profile(profile_id, window, cx);
             }
             Mode::ViewProfile(_) => {}
@@ -261,18 +303,54 @@ impl ManageProfilesModal {
         }
     }
 
-    fn create_profile(
+    fn save_profile(
         &self,
         profile_id: AgentProfileId,
         profile: AgentProfile,
+        overwrite: bool,
         cx: &mut Context<Self>,
     ) {
         update_settings_file::<AssistantSettings>(self.fs.clone(), cx, {
             move |settings, _cx| {
-                settings.create_profile(profile_id, profile).log_err();
+                if overwrite {
+                    settings.save_profile(profile_id, profile);
+                } else {
+                    settings.create_profile(profile_id, profile).log_err();
+                }
             }
         });
     }
+
+    /// Returns a profile enabling the tools that are currently enabled.
+    fn current_tools_profile(&self, name: SharedString, cx: &App) -> AgentProfile {
+        let mut profile = AgentProfile {
+            name,
+            tools: IndexMap::default(),
+            enable_all_context_servers: false,
+            context_servers: IndexMap::default(),
+            user_scripts: IndexMap::default(),
+        };
+        for tool in self.tools.enabled_tools(cx) {
+            let tools = match tool.source() {
+                ToolSource::Native => &mut profile.tools,
+                ToolSource::ContextServer { id } => {
+                    &mut profile
+                        .context_servers
+                        .entry(Arc::from(&*id))
+                        .or_default()
+                        .tools
+                }
+                ToolSource::UserScript { .. } => &mut profile.user_scripts,
+            };
+            tools.insert(tool.name().into(), true);
+        }
+        profile.tools.sort_keys();
+        profile.user_scripts.sort_keys();
+        for preset in profile.context_servers.values_mut() {
+            preset.tools.sort_keys();
+        }
+        profile
+    }
 }
 
 impl ModalView for ManageProfilesModal {}
@@ -407,18 +485,39 @@ impl ManageProfilesModal {
                 .unwrap_or_else(|| "Unknown".into())
         });
 
+        let title = if mode.from_current_tools {
+            "Save Current Tools as Profile".into()
+        } else {
+            match base_profile_name {
+                Some(base_profile) => format!("Fork {base_profile}"),
+                None => "New Profile".into(),
+            }
+        };
+        let overwritten_profile_name = mode.overwrite_profile_id.as_ref().and_then(|profile_id| {
+            settings
+                .profiles
+                .get(profile_id)
+                .map(|profile| profile.name.clone())
+        });
+
         v_flex()
             .id("new-profile")
             .track_focus(&self.focus_handle(cx))
-            .child(ProfileModalHeader::new(
-                match base_profile_name {
-                    Some(base_profile) => format!("Fork {base_profile}"),
-                    None => "New Profile".into(),
-                },
-                IconName::Plus,
-            ))
+            .child(ProfileModalHeader::new(title, IconName::Plus))
             .child(ListSeparator)
             .child(h_flex().p_2().child(mode.name_editor.clone()))
+            .when_some(overwritten_profile_name, |this, profile_name| {
+                this.child(
+                    h_flex().px_2().pb_2().child(
+                        Label::new(format!(
+                            "A profile named \"{profile_name}\" already exists. \
+                            Confirm again to overwrite it."
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Warning),
+                    ),
+                )
+            })
     }
 
     fn render_view_profile(
@@ -6,7 +6,6 @@ use assistant_settings::{
 };
 use assistant_tool::{ToolSource, ToolWorkingSet};
 use fs::Fs;
-use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
 use gpui::{App, Context, DismissEvent, Entity, EventEmitter, Focusable, Task, WeakEntity, Window};
 use picker::{Picker, PickerDelegate};
 use settings::{Settings as _, update_settings_file};
@@ -46,6 +45,18 @@ pub struct ToolEntry {
     pub source: ToolSource,
 }
 
+/// An item of the filtered list: a tool, or the header of the tools of a source.
+#[derive(Debug, Clone)]
+enum PickerItem {
+    Header(ToolSource),
+    Tool {
+        /// The index of the tool in [`ToolPickerDelegate::tools`].
+        index: usize,
+        /// The byte positions of the query in the tool's name.
+        positions: Vec<usize>,
+    },
+}
+
 pub struct ToolPickerDelegate {
     tool_picker: WeakEntity<ToolPicker>,
     thread_store: WeakEntity<ThreadStore>,
@@ -53,7 +64,7 @@ pub struct ToolPickerDelegate {
     tools: Vec<ToolEntry>,
     profile_id: AgentProfileId,
     profile: AgentProfile,
-    matches: Vec<StringMatch>,
+    filtered_items: Vec<PickerItem>,
     selected_index: usize,
 }
 
@@ -82,112 +93,65 @@ impl ToolPickerDelegate {
             tools: tool_entries,
             profile_id,
             profile,
-            matches: Vec::new(),
+            filtered_items: Vec::new(),
             selected_index: 0,
         }
     }
-}
 
-impl PickerDelegate for ToolPickerDelegate {
-    type ListItem = ListItem;
-
-    fn match_count(&self) -> usize {
-        self.matches.len()
-    }
-
-    fn selected_index(&self) -> usize {
-        self.selected_index
+    fn is_enabled(&self, tool: &ToolEntry) -> bool {
+        match &tool.source {
+            ToolSource::Native => self.profile.tools.get(&tool.name).copied().unwrap_or(false),
+            ToolSource::ContextServer { id } => self
+                .profile
+                .context_servers
+                .get(id.as_ref())
+                .and_then(|preset| preset.tools.get(&tool.name))
+                .copied()
+                .unwrap_or(false),
+            ToolSource::UserScript { .. } => self
+                .profile
+                .user_scripts
+                .get(&tool.name)
+                .copied()
+                .unwrap_or(false),
+        }
     }
 
-    fn set_selected_index(
-        &mut self,
-        ix: usize,
-        _window: &mut Window,
-        _cx: &mut Context<Picker<Self>>,
-    ) {
-        self.selected_index = ix;
+    fn user_scripts(&self) -> impl Iterator<Item = &ToolEntry> {
+        self.tools
+            .iter()
+            .filter(|tool| matches!(tool.source, ToolSource::UserScript { .. }))
     }
 
-    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Search tools…".into()
+    /// Enables all of the user's script tools, or disables them if they are all enabled.
+    fn toggle_all_user_scripts(&mut self, cx: &mut Context<Picker<Self>>) {
+        let scripts = self.user_scripts().cloned().collect::<Vec<_>>();
+        let is_enabled = !scripts.iter().all(|tool| self.is_enabled(tool));
+        self.set_tools_enabled(scripts, is_enabled, cx);
     }
 
-    fn update_matches(
+    fn set_tools_enabled(
         &mut self,
-        query: String,
-        window: &mut Window,
+        tools: Vec<ToolEntry>,
+        is_enabled: bool,
         cx: &mut Context<Picker<Self>>,
-    ) -> Task<()> {
-        let background = cx.background_executor().clone();
-        let candidates = self
-            .tools
-            .iter()
-            .enumerate()
-            .map(|(id, profile)| StringMatchCandidate::new(id, profile.name.as_ref()))
-            .collect::<Vec<_>>();
-
-        cx.spawn_in(window, async move |this, cx| {
-            let matches = if query.is_empty() {
-                candidates
-                    .into_iter()
-                    .enumerate()
-                    .map(|(index, candidate)| StringMatch {
-                        candidate_id: index,
-                        string: candidate.string,
-                        positions: Vec::new(),
-                        score: 0.,
-                    })
-                    .collect()
-            } else {
-                match_strings(
-                    &candidates,
-                    &query,
-                    false,
-                    100,
-                    &Default::default(),
-                    background,
-                )
-                .await
+    ) {
+        for tool in &tools {
+            let enabled_tools = match &tool.source {
+                ToolSource::Native => &mut self.profile.tools,
+                ToolSource::ContextServer { id } => {
+                    &mut self
+                        .profile
+                        .context_servers
+                        .entry(id.clone().into())
+                        .or_default()
+                        .tools
+                }
+                ToolSource::UserScript { .. } => &mut self.profile.user_scripts,
             };
-
-            this.update(cx, |this, _cx| {
-                this.delegate.matches = matches;
-                this.delegate.selected_index = this
-                    .delegate
-                    .selected_index
-                    .min(this.delegate.matches.len().saturating_sub(1));
-            })
-            .log_err();
-        })
-    }
-
-    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
-        if self.matches.is_empty() {
-            self.dismissed(window, cx);
-            return;
+            enabled_tools.insert(tool.name.clone(), is_enabled);
         }
 
-        let candidate_id = self.matches[self.selected_index].candidate_id;
-        let tool = &self.tools[candidate_id];
-
-        let is_enabled = match &tool.source {
-            ToolSource::Native => {
-                let is_enabled = self.profile.tools.entry(tool.name.clone()).or_default();
-                *is_enabled = !*is_enabled;
-                *is_enabled
-            }
-            ToolSource::ContextServer { id } => {
-                let preset = self
-                    .profile
-                    .context_servers
-                    .entry(id.clone().into())
-                    .or_default();
-                let is_enabled = preset.tools.entry(tool.name.clone()).or_default();
-                *is_enabled = !*is_enabled;
-                *is_enabled
-            }
-        };
-
         let active_profile_id = &AssistantSettings::get_global(cx).default_profile;
         if active_profile_id == &self.profile_id {
             self.thread_store
@@ -200,7 +164,6 @@ impl PickerDelegate for ToolPickerDelegate {
         update_settings_file::<AssistantSettings>(self.fs.clone(), cx, {
             let profile_id = self.profile_id.clone();
             let default_profile = self.profile.clone();
-            let tool = tool.clone();
             move |settings, _cx| match settings {
                 AssistantSettingsContent::Versioned(VersionedAssistantSettingsContent::V2(
                     settings,
@@ -227,18 +190,24 @@ impl PickerDelegate for ToolPickerDelegate {
                                         )
                                     })
                                     .collect(),
+                                user_scripts: default_profile.user_scripts,
                             });
 
-                    match tool.source {
-                        ToolSource::Native => {
-                            *profile.tools.entry(tool.name).or_default() = is_enabled;
-                        }
-                        ToolSource::ContextServer { id } => {
-                            let preset = profile
-                                .context_servers
-                                .entry(id.clone().into())
-                                .or_default();
-                            *preset.tools.entry(tool.name.clone()).or_default() = is_enabled;
+                    for tool in tools {
+                        match tool.source {
+                            ToolSource::Native => {
+                                *profile.tools.entry(tool.name).or_default() = is_enabled;
+                            }
+                            ToolSource::ContextServer { id } => {
+                                let preset = profile
+                                    .context_servers
+                                    .entry(id.clone().into())
+                                    .or_default();
+                                *preset.tools.entry(tool.name.clone()).or_default() = is_enabled;
+                            }
+                            ToolSource::UserScript { .. } => {
+                                *profile.user_scripts.entry(tool.name).or_default() = is_enabled;
+                            }
                         }
                     }
                 }
@@ -246,6 +215,88 @@ impl PickerDelegate for ToolPickerDelegate {
             }
         });
     }
+}
+
+impl PickerDelegate for ToolPickerDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.filtered_items.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        // Headers stay selectable, so that clicking one doesn't move the selection to a
+        // neighbouring tool that confirming would then toggle.
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search tools…".into()
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let query = query.to_lowercase();
+        let mut filtered_items = Vec::new();
+        let mut current_source = None;
+        for (index, tool) in self.tools.iter().enumerate() {
+            let lowercase_name = tool.name.to_lowercase();
+            let Some(start) = lowercase_name.find(&query) else {
+                continue;
+            };
+            if current_source != Some(&tool.source) {
+                current_source = Some(&tool.source);
+                filtered_items.push(PickerItem::Header(tool.source.clone()));
+            }
+            // Lowercasing can change the length of non-ASCII names, whose matches aren't
+            // highlighted.
+            let positions = if query.is_empty() || lowercase_name.len() != tool.name.len() {
+                Vec::new()
+            } else {
+                (start..start + query.len())
+                    .filter(|ix| tool.name.is_char_boundary(*ix))
+                    .collect()
+            };
+            filtered_items.push(PickerItem::Tool { index, positions });
+        }
+
+        self.filtered_items = filtered_items;
+        self.selected_index = self
+            .filtered_items
+            .iter()
+            .position(|item| matches!(item, PickerItem::Tool { .. }))
+            .unwrap_or(0);
+        Task::ready(())
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if self.filtered_items.is_empty() {
+            self.dismissed(window, cx);
+            return;
+        }
+
+        // Confirming a header, by clicking it or pressing enter on it, does nothing.
+        let Some(PickerItem::Tool { index, .. }) = self.filtered_items.get(self.selected_index)
+        else {
+            return;
+        };
+        let tool = self.tools[*index].clone();
+        let is_enabled = !self.is_enabled(&tool);
+        self.set_tools_enabled(vec![tool], is_enabled, cx);
+    }
 
     fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
         self.tool_picker
@@ -258,40 +309,49 @@ impl PickerDelegate for ToolPickerDelegate {
         ix: usize,
         selected: bool,
         _window: &mut Window,
-        _cx: &mut Context<Picker<Self>>,
+        cx: &mut Context<Picker<Self>>,
     ) -> Option<Self::ListItem> {
-        let tool_match = &self.matches[ix];
-        let tool = &self.tools[tool_match.candidate_id];
-
-        let is_enabled = match &tool.source {
-            ToolSource::Native => self.profile.tools.get(&tool.name).copied().unwrap_or(false),
-            ToolSource::ContextServer { id } => self
-                .profile
-                .context_servers
-                .get(id.as_ref())
-                .and_then(|preset| preset.tools.get(&tool.name))
-                .copied()
-                .unwrap_or(false),
+        let (index, positions) = match &self.filtered_items[ix] {
+            PickerItem::Header(source) => {
+                let label = match source {
+                    ToolSource::Native => "Built-in Tools".into(),
+                    ToolSource::ContextServer { id } => format!("{id} Tools"),
+                    ToolSource::UserScript { .. } => "My Scripts".into(),
+                };
+                let toggle_all = matches!(source, ToolSource::UserScript { .. }).then(|| {
+                    let all_enabled = self.user_scripts().all(|tool| self.is_enabled(tool));
+                    Button::new("toggle-all-user-scripts", "All Tools")
+                        .label_size(LabelSize::Small)
+                        .toggle_state(all_enabled)
+                        .on_click(cx.listener(|picker, _, _window, cx| {
+                            picker.delegate.toggle_all_user_scripts(cx);
+                            cx.notify();
+                        }))
+                });
+                return Some(
+                    ListItem::new(ix)
+                        .inset(true)
+                        .spacing(ListItemSpacing::Dense)
+                        .selectable(false)
+                        .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+                        .end_slot::<Button>(toggle_all),
+                );
+            }
+            PickerItem::Tool { index, positions } => (*index, positions),
         };
+        let tool = &self.tools[index];
+
+        let is_enabled = self.is_enabled(tool);
 
         Some(
             ListItem::new(ix)
                 .inset(true)
                 .spacing(ListItemSpacing::Sparse)
                 .toggle_state(selected)
-                .child(
-                    h_flex()
-                        .gap_2()
-                        .child(HighlightedLabel::new(
-                            tool_match.string.clone(),
-                            tool_match.positions.clone(),
-                        ))
-                        .map(|parent| match &tool.source {
-                            ToolSource::Native => parent,
-                            ToolSource::ContextServer { id } => parent
-                                .child(Label::new(id).size(LabelSize::XSmall).color(Color::Muted)),
-                        }),
-                )
+                .child(HighlightedLabel::new(
+                    tool.name.to_string(),
+                    positions.clone(),
+                ))
                 .end_slot::<Icon>(is_enabled.then(|| {
                     Icon::new(IconName::Check)
                         .size(IconSize::Small)
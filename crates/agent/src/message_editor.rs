@@ -16,8 +16,8 @@ use settings::Settings;
 use std::time::Duration;
 use theme::ThemeSettings;
 use ui::{
-    ButtonLike, Disclosure, KeyBinding, PlatformStyle, PopoverMenu, PopoverMenuHandle, Tooltip,
-    prelude::*,
+    ButtonLike, ContextMenu, Disclosure, KeyBinding, PlatformStyle, PopoverMenu, PopoverMenuHandle,
+    Tooltip, prelude::*,
 };
 use util::ResultExt as _;
 use vim_mode_setting::VimModeSetting;
@@ -35,6 +35,9 @@ use crate::{
     ToggleContextPicker, ToggleProfileSelector,
 };
 
+/// The temperatures offered in the temperature menu, besides the model's default.
+const TEMPERATURE_PRESETS: [f32; 5] = [0.0, 0.5, 1.0, 1.5, 2.0];
+
 pub struct MessageEditor {
     thread: Entity<Thread>,
     editor: Entity<Editor>,
@@ -120,6 +123,11 @@ impl MessageEditor {
                 Self::handle_inline_context_picker_event,
             ),
             cx.subscribe_in(&context_strip, window, Self::handle_context_strip_event),
+            cx.subscribe(&thread, |_, _, event, cx| {
+                if matches!(event, ThreadEvent::TemperatureChanged) {
+                    cx.notify();
+                }
+            }),
         ];
 
         Self {
@@ -149,6 +157,70 @@ impl MessageEditor {
         }
     }
 
+    fn render_temperature_menu(&self, cx: &App) -> impl IntoElement {
+        let label = match self.thread.read(cx).temperature() {
+            Some(temperature) => format!("Temp {temperature:.1}"),
+            None => "Temp Default".to_string(),
+        };
+        let thread = self.thread.clone();
+        PopoverMenu::new("temperature-menu")
+            .menu(move |window, cx| {
+                let thread = thread.clone();
+                Some(ContextMenu::build(
+                    window,
+                    cx,
+                    move |mut menu, _window, cx| {
+                        let current = thread.read(cx).temperature();
+                        menu = menu.header("Temperature").toggleable_entry(
+                            "Model Default",
+                            current.is_none(),
+                            IconPosition::End,
+                            None,
+                            {
+                                let thread = thread.clone();
+                                move |_window, cx| {
+                                    thread
+                                        .update(cx, |thread, cx| thread.set_temperature(None, cx));
+                                }
+                            },
+                        );
+                        for temperature in TEMPERATURE_PRESETS {
+                            menu = menu.toggleable_entry(
+                                format!("{temperature:.1}"),
+                                current == Some(temperature),
+                                IconPosition::End,
+                                None,
+                                {
+                                    let thread = thread.clone();
+                                    move |_window, cx| {
+                                        thread.update(cx, |thread, cx| {
+                                            thread.set_temperature(Some(temperature), cx)
+                                        });
+                                    }
+                                },
+                            );
+                        }
+                        menu
+                    },
+                ))
+            })
+            .trigger(
+                ButtonLike::new("temperature-menu-button")
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+                            .child(
+                                Icon::new(IconName::ChevronDown)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .tooltip(Tooltip::text("Temperature used for this thread")),
+            )
+            .anchor(gpui::Corner::BottomRight)
+    }
+
     fn toggle_chat_mode(&mut self, _: &ChatMode, _window: &mut Window, cx: &mut Context<Self>) {
         cx.notify();
     }
@@ -335,6 +407,7 @@ impl Render for MessageEditor {
 
         let focus_handle = self.editor.focus_handle(cx);
         let inline_context_picker = self.inline_context_picker.clone();
+        let temperature_menu = self.render_temperature_menu(cx);
 
         let thread = self.thread.read(cx);
         let is_generating = thread.is_generating();
@@ -730,7 +803,11 @@ impl Render for MessageEditor {
                                     .justify_between()
                                     .child(h_flex().gap_2().child(self.profile_selector.clone()))
                                     .child(
-                                        h_flex().gap_1().child(self.model_selector.clone()).child(
+                                        h_flex()
+                                            .gap_1()
+                                            .child(temperature_menu)
+                                            .child(self.model_selector.clone())
+                                            .child(
                                             ButtonLike::new("submit-message")
                                                 .width(button_width.into())
                                                 .style(ButtonStyle::Filled)
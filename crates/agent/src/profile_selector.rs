@@ -20,6 +20,8 @@ pub struct ProfileSelector {
     thread_store: WeakEntity<ThreadStore>,
     focus_handle: FocusHandle,
     menu_handle: PopoverMenuHandle<ContextMenu>,
+    /// Which tools changed the last time a profile was applied from this selector.
+    last_tool_changes: Option<SharedString>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -40,6 +42,7 @@ impl ProfileSelector {
             thread_store,
             focus_handle,
             menu_handle: PopoverMenuHandle::default(),
+            last_tool_changes: None,
             _subscriptions: vec![settings_subscription],
         };
         this.refresh_profiles(cx);
@@ -62,6 +65,7 @@ impl ProfileSelector {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Entity<ContextMenu> {
+        let this = cx.weak_entity();
         ContextMenu::build(window, cx, |mut menu, _window, cx| {
             let settings = AssistantSettings::get_global(cx);
             let icon_position = IconPosition::End;
@@ -76,6 +80,7 @@ impl ProfileSelector {
                     {
                         let fs = self.fs.clone();
                         let thread_store = self.thread_store.clone();
+                        let this = this.clone();
                         move |_window, cx| {
                             update_settings_file::<AssistantSettings>(fs.clone(), cx, {
                                 let profile_id = profile_id.clone();
@@ -84,11 +89,19 @@ impl ProfileSelector {
                                 }
                             });
 
-                            thread_store
-                                .update(cx, |this, cx| {
-                                    this.load_profile_by_id(&profile_id, cx);
+                            let diff = thread_store
+                                .update(cx, |this, cx| this.load_profile_by_id(&profile_id, cx))
+                                .log_err()
+                                .flatten();
+                            if let Some(diff) = diff {
+                                this.update(cx, |this, cx| {
+                                    this.last_tool_changes = Some(
+                                        format!("{}: {}", profile.name, diff.summary()).into(),
+                                    );
+                                    cx.notify();
                                 })
-                                .log_err();
+                                .ok();
+                            }
                         }
                     },
                 );
@@ -106,6 +119,17 @@ impl ProfileSelector {
                 }
             }));
 
+            menu = menu.item(
+                ContextMenuEntry::new("Save Current Tools as Profile…").handler(
+                    move |window, cx| {
+                        window.dispatch_action(
+                            ManageProfiles::save_current_tools().boxed_clone(),
+                            cx,
+                        );
+                    },
+                ),
+            );
+
             menu = menu.separator();
             menu = menu.item(ContextMenuEntry::new("Configure Profiles…").handler(
                 move |window, cx| {
@@ -139,6 +163,7 @@ impl Render for ProfileSelector {
             _ => IconName::UserRoundPen,
         };
 
+        let last_tool_changes = self.last_tool_changes.clone();
         let this = cx.entity().clone();
         let focus_handle = self.focus_handle.clone();
         PopoverMenu::new("profile-selector")
@@ -146,31 +171,35 @@ impl Render for ProfileSelector {
                 Some(this.update(cx, |this, cx| this.build_context_menu(window, cx)))
             })
             .trigger(if supports_tools {
-                ButtonLike::new("profile-selector-button").child(
-                    h_flex()
-                        .gap_1()
-                        .child(Icon::new(icon).size(IconSize::XSmall).color(Color::Muted))
-                        .child(
-                            Label::new(selected_profile)
-                                .size(LabelSize::Small)
-                                .color(Color::Muted),
-                        )
-                        .child(
-                            Icon::new(IconName::ChevronDown)
-                                .size(IconSize::XSmall)
-                                .color(Color::Muted),
-                        )
-                        .child(div().opacity(0.5).children({
-                            let focus_handle = focus_handle.clone();
-                            KeyBinding::for_action_in(
-                                &ToggleProfileSelector,
-                                &focus_handle,
-                                window,
-                                cx,
+                ButtonLike::new("profile-selector-button")
+                    .when_some(last_tool_changes, |this, changes| {
+                        this.tooltip(Tooltip::text(changes))
+                    })
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Icon::new(icon).size(IconSize::XSmall).color(Color::Muted))
+                            .child(
+                                Label::new(selected_profile)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
                             )
-                            .map(|kb| kb.size(rems_from_px(10.)))
-                        })),
-                )
+                            .child(
+                                Icon::new(IconName::ChevronDown)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                            .child(div().opacity(0.5).children({
+                                let focus_handle = focus_handle.clone();
+                                KeyBinding::for_action_in(
+                                    &ToggleProfileSelector,
+                                    &focus_handle,
+                                    window,
+                                    cx,
+                                )
+                                .map(|kb| kb.size(rems_from_px(10.)))
+                            })),
+                    )
             } else {
                 ButtonLike::new("tools-not-supported-button")
                     .disabled(true)
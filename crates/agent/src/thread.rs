@@ -1,11 +1,14 @@
 use std::fmt::Write as _;
 use std::io::Write;
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 use std::sync::Arc;
 
 use anyhow::{Context as _, Result, anyhow};
 use assistant_settings::AssistantSettings;
-use assistant_tool::{ActionLog, Tool, ToolWorkingSet};
+use assistant_tool::{
+    ActionLog, CancellationToken, StreamingToolOutput, Tool, ToolWorkingSet,
+    run_streaming_with_timeout,
+};
 use chrono::{DateTime, Utc};
 use collections::{BTreeMap, HashMap, HashSet};
 use fs::Fs;
@@ -223,6 +226,8 @@ pub struct Thread {
     initial_project_snapshot: Shared<Task<Option<Arc<ProjectSnapshot>>>>,
     cumulative_token_usage: TokenUsage,
     feedback: Option<ThreadFeedback>,
+    /// The temperature used for every request in this thread, or `None` for the model's default.
+    temperature: Option<f32>,
 }
 
 impl Thread {
@@ -261,6 +266,7 @@ impl Thread {
             },
             cumulative_token_usage: TokenUsage::default(),
             feedback: None,
+            temperature: None,
         }
     }
 
@@ -281,6 +287,11 @@ impl Thread {
         );
         let tool_use =
             ToolUseState::from_serialized_messages(tools.clone(), &serialized.messages, |_| true);
+        let action_log = cx.new(|_| {
+            let mut action_log = ActionLog::new();
+            action_log.restore(serialized.action_log, &project);
+            action_log
+        });
 
         Self {
             id,
@@ -319,10 +330,11 @@ impl Thread {
             prompt_builder,
             tools,
             tool_use,
-            action_log: cx.new(|_| ActionLog::new()),
+            action_log,
             initial_project_snapshot: Task::ready(serialized.initial_project_snapshot).shared(),
             cumulative_token_usage: serialized.cumulative_token_usage,
             feedback: None,
+            temperature: serialized.temperature.map(clamp_temperature),
         }
     }
 
@@ -356,6 +368,16 @@ impl Thread {
         cx.emit(ThreadEvent::SummaryChanged);
     }
 
+    pub fn temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    /// Pins the temperature of every request in this thread, clamping it to [`TEMPERATURE_RANGE`].
+    pub fn set_temperature(&mut self, temperature: Option<f32>, cx: &mut Context<Self>) {
+        self.temperature = temperature.map(clamp_temperature);
+        cx.emit(ThreadEvent::TemperatureChanged);
+    }
+
     pub fn latest_detailed_summary_or_text(&self) -> SharedString {
         self.latest_detailed_summary()
             .unwrap_or_else(|| self.text().into())
@@ -700,6 +722,8 @@ impl Thread {
                 initial_project_snapshot,
                 cumulative_token_usage: this.cumulative_token_usage.clone(),
                 detailed_summary_state: this.detailed_summary_state.clone(),
+                action_log: this.action_log.read(cx).serialize(cx),
+                temperature: this.temperature,
             })
         })
     }
@@ -821,6 +845,21 @@ impl Thread {
         request_kind: RequestKind,
         cx: &mut Context<Self>,
     ) {
+        // The buffers tracked in a previous session are opened the first time the thread
+        // talks to the model again, which needs to know whether they changed in the meantime.
+        if let Some(resolve) = self
+            .action_log
+            .update(cx, |action_log, cx| action_log.resolve_restored_buffers(cx))
+        {
+            cx.spawn(async move |this, cx| {
+                resolve.await;
+                this.update(cx, |this, cx| this.send_to_model(model, request_kind, cx))
+                    .ok();
+            })
+            .detach();
+            return;
+        }
+
         let mut request = self.to_completion_request(request_kind, cx);
         if model.supports_tools() {
             request.tools = {
@@ -861,7 +900,7 @@ impl Thread {
             messages: vec![],
             tools: Vec::new(),
             stop: Vec::new(),
-            temperature: None,
+            temperature: self.temperature,
         };
 
         if let Some(system_prompt_context) = self.system_prompt_context.as_ref() {
@@ -1353,7 +1392,7 @@ impl Thread {
 
         for tool_use in pending_tool_uses.iter() {
             if let Some(tool) = self.tools.tool(&tool_use.name, cx) {
-                if tool.needs_confirmation()
+                if tool.needs_confirmation(&tool_use.input)
                     && !AssistantSettings::get_global(cx).always_allow_tool_actions
                 {
                     self.tool_use.confirm_tool_use(
@@ -1389,9 +1428,17 @@ impl Thread {
         tool: Arc<dyn Tool>,
         cx: &mut Context<Thread>,
     ) {
-        let task = self.spawn_tool_use(tool_use_id.clone(), messages, input, tool, cx);
+        let cancellation = CancellationToken::new();
+        let task = self.spawn_tool_use(
+            tool_use_id.clone(),
+            messages,
+            input,
+            tool,
+            cancellation.clone(),
+            cx,
+        );
         self.tool_use
-            .run_pending_tool(tool_use_id, ui_text.into(), task);
+            .run_pending_tool(tool_use_id, ui_text.into(), task, cancellation);
     }
 
     fn spawn_tool_use(
@@ -1400,25 +1447,45 @@ impl Thread {
         messages: &[LanguageModelRequestMessage],
         input: serde_json::Value,
         tool: Arc<dyn Tool>,
+        cancellation: CancellationToken,
         cx: &mut Context<Thread>,
     ) -> Task<()> {
         let tool_name: Arc<str> = tool.name().into();
 
         let run_tool = if self.tools.is_disabled(&tool.source(), &tool_name) {
-            Task::ready(Err(anyhow!("tool is disabled: {tool_name}")))
+            StreamingToolOutput::from_result(Task::ready(Err(anyhow!(
+                "tool is disabled: {tool_name}"
+            ))))
         } else {
-            tool.run(
+            run_streaming_with_timeout(
+                tool,
                 input,
                 messages,
                 self.project.clone(),
                 self.action_log.clone(),
+                cancellation,
                 cx,
             )
         };
 
         cx.spawn({
             async move |thread: WeakEntity<Thread>, cx| {
-                let output = run_tool.await;
+                let mut chunks = run_tool.chunks.fuse();
+                let mut result = run_tool.result.fuse();
+                let output = loop {
+                    futures::select_biased! {
+                        chunk = chunks.next() => {
+                            if let Some(chunk) = chunk {
+                                thread
+                                    .update(cx, |thread, cx| {
+                                        thread.append_partial_tool_output(&tool_use_id, &chunk, cx)
+                                    })
+                                    .ok();
+                            }
+                        }
+                        output = result => break output,
+                    }
+                };
 
                 thread
                     .update(cx, |thread, cx| {
@@ -1439,6 +1506,17 @@ impl Thread {
         })
     }
 
+    fn append_partial_tool_output(
+        &mut self,
+        tool_use_id: &LanguageModelToolUseId,
+        chunk: &str,
+        cx: &mut Context<Self>,
+    ) {
+        if self.tool_use.append_partial_output(tool_use_id, chunk) {
+            cx.emit(ThreadEvent::StreamedToolOutput(tool_use_id.clone()));
+        }
+    }
+
     pub fn attach_tool_results(
         &mut self,
         updated_context: Vec<AssistantContext>,
@@ -1798,11 +1876,25 @@ pub enum ThreadEvent {
         canceled: bool,
     },
     CheckpointChanged,
+    /// A running tool streamed more of its output.
+    StreamedToolOutput(LanguageModelToolUseId),
     ToolConfirmationNeeded,
+    TemperatureChanged,
 }
 
 impl EventEmitter<ThreadEvent> for Thread {}
 
+/// The temperatures a thread can be pinned to.
+pub const TEMPERATURE_RANGE: RangeInclusive<f32> = 0.0..=2.0;
+
+fn clamp_temperature(temperature: f32) -> f32 {
+    let clamped = temperature.clamp(*TEMPERATURE_RANGE.start(), *TEMPERATURE_RANGE.end());
+    if clamped != temperature {
+        log::warn!("temperature {temperature} is out of range, using {clamped} instead");
+    }
+    clamped
+}
+
 struct PendingCompletion {
     id: usize,
     _task: Task<()>,
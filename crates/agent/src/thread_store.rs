@@ -4,11 +4,11 @@ use std::sync::Arc;
 
 use anyhow::{Result, anyhow};
 use assistant_settings::{AgentProfile, AgentProfileId, AssistantSettings};
-use assistant_tool::{ToolId, ToolSource, ToolWorkingSet};
+use assistant_tool::{SerializedActionLog, ToolId, ToolSource, ToolWorkingSet, ToolWorkingSetDiff};
 use chrono::{DateTime, Utc};
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use context_server::manager::ContextServerManager;
-use context_server::{ContextServerFactoryRegistry, ContextServerTool};
+use context_server::{ContextServerFactoryRegistry, ContextServerSettings, ContextServerTool};
 use futures::FutureExt as _;
 use futures::future::{self, BoxFuture, Shared};
 use gpui::{
@@ -202,23 +202,24 @@ impl ThreadStore {
         self.load_profile_by_id(&assistant_settings.default_profile, cx);
     }
 
-    pub fn load_profile_by_id(&self, profile_id: &AgentProfileId, cx: &Context<Self>) {
+    pub fn load_profile_by_id(
+        &self,
+        profile_id: &AgentProfileId,
+        cx: &Context<Self>,
+    ) -> Option<ToolWorkingSetDiff> {
         let assistant_settings = AssistantSettings::get_global(cx);
 
-        if let Some(profile) = assistant_settings.profiles.get(profile_id) {
-            self.load_profile(profile, cx);
-        }
+        let profile = assistant_settings.profiles.get(profile_id)?;
+        Some(self.load_profile(profile, cx))
     }
 
-    pub fn load_profile(&self, profile: &AgentProfile, cx: &Context<Self>) {
+    /// Enables the tools of the given profile, returning which tools changed.
+    pub fn load_profile(&self, profile: &AgentProfile, cx: &Context<Self>) -> ToolWorkingSetDiff {
+        let previous_tools = self.tools.snapshot();
         self.tools.disable_all_tools();
         self.tools.enable(
             ToolSource::Native,
-            &profile
-                .tools
-                .iter()
-                .filter_map(|(tool, enabled)| enabled.then(|| tool.clone()))
-                .collect::<Vec<_>>(),
+            &self.enabled_profile_tools(profile, &ToolSource::Native, &profile.tools, cx),
         );
 
         if profile.enable_all_context_servers {
@@ -232,18 +233,61 @@ impl ThreadStore {
             }
         } else {
             for (context_server_id, preset) in &profile.context_servers {
-                self.tools.enable(
-                    ToolSource::ContextServer {
-                        id: context_server_id.clone().into(),
-                    },
-                    &preset
-                        .tools
-                        .iter()
-                        .filter_map(|(tool, enabled)| enabled.then(|| tool.clone()))
-                        .collect::<Vec<_>>(),
-                )
+                let source = ToolSource::ContextServer {
+                    id: context_server_id.clone().into(),
+                };
+                let tools = self.enabled_profile_tools(profile, &source, &preset.tools, cx);
+                self.tools.enable(source, &tools)
             }
         }
+
+        for tool in self.tools.tools(cx) {
+            let source = tool.source();
+            let name = tool.name();
+            if matches!(source, ToolSource::UserScript { .. })
+                && profile.user_scripts.get(name.as_str()).copied() == Some(true)
+            {
+                self.tools.enable(source, &[name.into()]);
+            }
+        }
+
+        let diff = previous_tools.diff(&self.tools.snapshot());
+        log::debug!("loaded profile {:?}: {}", profile.name, diff.summary());
+        diff
+    }
+
+    /// Returns the tools a profile enables for the given source, skipping the ones the source
+    /// doesn't provide. Context servers whose tools haven't been loaded yet are trusted.
+    fn enabled_profile_tools<'a>(
+        &self,
+        profile: &AgentProfile,
+        source: &ToolSource,
+        tools: impl IntoIterator<Item = (&'a Arc<str>, &'a bool)>,
+        cx: &App,
+    ) -> Vec<Arc<str>> {
+        let available_tools = self
+            .tools
+            .tools(cx)
+            .into_iter()
+            .filter(|tool| tool.source() == *source)
+            .map(|tool| tool.name())
+            .collect::<HashSet<_>>();
+        tools
+            .into_iter()
+            .filter(|(_, enabled)| **enabled)
+            .filter(|(tool, _)| {
+                let known = (available_tools.is_empty() && *source != ToolSource::Native)
+                    || available_tools.contains::<str>(tool);
+                if !known {
+                    log::debug!(
+                        "profile {:?} enables unknown tool {tool:?}, skipping it",
+                        profile.name
+                    );
+                }
+                known
+            })
+            .map(|(tool, _)| tool.clone())
+            .collect()
     }
 
     fn register_context_server_handlers(&self, cx: &mut Context<Self>) {
@@ -265,6 +309,7 @@ impl ThreadStore {
             context_server::manager::Event::ServerStarted { server_id } => {
                 if let Some(server) = context_server_manager.read(cx).get_server(server_id) {
                     let context_server_manager = context_server_manager.clone();
+                    let tool_timeout = ContextServerSettings::get_global(cx).tool_timeout();
                     cx.spawn({
                         let server = server.clone();
                         let server_id = server_id.clone();
@@ -288,6 +333,7 @@ impl ThreadStore {
                                                     context_server_manager.clone(),
                                                     server.id(),
                                                     tool,
+                                                    tool_timeout,
                                                 ),
                                             ))
                                         })
@@ -334,6 +380,10 @@ pub struct SerializedThread {
     pub cumulative_token_usage: TokenUsage,
     #[serde(default)]
     pub detailed_summary_state: DetailedSummaryState,
+    #[serde(default)]
+    pub action_log: SerializedActionLog,
+    #[serde(default)]
+    pub temperature: Option<f32>,
 }
 
 impl SerializedThread {
@@ -418,6 +468,8 @@ impl LegacySerializedThread {
             initial_project_snapshot: self.initial_project_snapshot,
             cumulative_token_usage: TokenUsage::default(),
             detailed_summary_state: DetailedSummaryState::default(),
+            action_log: SerializedActionLog::default(),
+            temperature: None,
         }
     }
 }
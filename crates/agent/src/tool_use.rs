@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use assistant_tool::{Tool, ToolWorkingSet};
+use assistant_tool::{CancellationToken, Tool, ToolWorkingSet};
 use collections::HashMap;
 use futures::FutureExt as _;
 use futures::future::Shared;
@@ -24,6 +24,8 @@ pub struct ToolUse {
     pub input: serde_json::Value,
     pub icon: ui::IconName,
     pub needs_confirmation: bool,
+    /// The output the tool has streamed so far, while it's running.
+    pub partial_output: SharedString,
 }
 
 #[derive(Debug, Clone)]
@@ -131,6 +133,9 @@ impl ToolUseState {
     pub fn cancel_pending(&mut self) -> Vec<PendingToolUse> {
         let mut pending_tools = Vec::new();
         for (tool_use_id, tool_use) in self.pending_tool_uses_by_id.drain() {
+            if let PendingToolUseStatus::Running { cancellation, .. } = &tool_use.status {
+                cancellation.cancel();
+            }
             self.tool_results.insert(
                 tool_use_id.clone(),
                 LanguageModelToolResult {
@@ -184,9 +189,15 @@ impl ToolUseState {
                 }
             })();
 
+            let partial_output = self
+                .pending_tool_uses_by_id
+                .get(&tool_use.id)
+                .map(|pending_tool_use| SharedString::from(pending_tool_use.partial_output.clone()))
+                .unwrap_or_default();
+
             let (icon, needs_confirmation) = if let Some(tool) = self.tools.tool(&tool_use.name, cx)
             {
-                (tool.icon(), tool.needs_confirmation())
+                (tool.icon(), tool.needs_confirmation(&tool_use.input))
             } else {
                 (IconName::Cog, false)
             };
@@ -199,6 +210,7 @@ impl ToolUseState {
                 status,
                 icon,
                 needs_confirmation,
+                partial_output,
             })
         }
 
@@ -272,6 +284,7 @@ impl ToolUseState {
                     .into(),
                 input: tool_use.input,
                 status: PendingToolUseStatus::Idle,
+                partial_output: String::new(),
             },
         );
     }
@@ -281,15 +294,32 @@ impl ToolUseState {
         tool_use_id: LanguageModelToolUseId,
         ui_text: SharedString,
         task: Task<()>,
+        cancellation: CancellationToken,
     ) {
         if let Some(tool_use) = self.pending_tool_uses_by_id.get_mut(&tool_use_id) {
             tool_use.ui_text = ui_text.into();
             tool_use.status = PendingToolUseStatus::Running {
                 _task: task.shared(),
+                cancellation,
             };
         }
     }
 
+    /// Appends a chunk of output streamed by a running tool, returning whether the tool use
+    /// is still pending.
+    pub fn append_partial_output(
+        &mut self,
+        tool_use_id: &LanguageModelToolUseId,
+        chunk: &str,
+    ) -> bool {
+        if let Some(tool_use) = self.pending_tool_uses_by_id.get_mut(tool_use_id) {
+            tool_use.partial_output.push_str(chunk);
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn confirm_tool_use(
         &mut self,
         tool_use_id: LanguageModelToolUseId,
@@ -411,6 +441,8 @@ pub struct PendingToolUse {
     pub ui_text: Arc<str>,
     pub input: serde_json::Value,
     pub status: PendingToolUseStatus,
+    /// The output the tool has streamed so far.
+    pub partial_output: String,
 }
 
 #[derive(Debug, Clone)]
@@ -426,7 +458,10 @@ pub struct Confirmation {
 pub enum PendingToolUseStatus {
     Idle,
     NeedsConfirmation(Arc<Confirmation>),
-    Running { _task: Shared<Task<()>> },
+    Running {
+        _task: Shared<Task<()>>,
+        cancellation: CancellationToken,
+    },
     Error(#[allow(unused)] Arc<str>),
 }
 
@@ -1,12 +1,12 @@
 mod supported_countries;
 
-use std::{pin::Pin, str::FromStr};
+use std::{pin::Pin, str::FromStr, time::Duration};
 
 use anyhow::{Context as _, Result, anyhow};
 use chrono::{DateTime, Utc};
 use futures::{AsyncBufReadExt, AsyncReadExt, Stream, StreamExt, io::BufReader, stream::BoxStream};
 use http_client::http::{HeaderMap, HeaderValue};
-use http_client::{AsyncBody, HttpClient, Method, Request as HttpRequest};
+use http_client::{AsyncBody, HttpClient, Method, Request as HttpRequest, StatusCode};
 use serde::{Deserialize, Serialize};
 use strum::{EnumIter, EnumString};
 use thiserror::Error;
@@ -286,6 +286,10 @@ pub async fn complete(
         let response_message: Response =
             serde_json::from_slice(&body).context("failed to deserialize response body")?;
         Ok(response_message)
+    } else if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        Err(AnthropicError::RateLimit {
+            retry_after: retry_after_from_headers(response.headers()),
+        })
     } else {
         let mut body = Vec::new();
         response
@@ -412,6 +416,10 @@ pub async fn stream_completion_with_rate_limit_info(
             })
             .boxed();
         Ok((stream, rate_limits.log_err()))
+    } else if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        Err(AnthropicError::RateLimit {
+            retry_after: retry_after_from_headers(response.headers()),
+        })
     } else {
         let mut body = Vec::new();
         response
@@ -437,6 +445,12 @@ pub async fn stream_completion_with_rate_limit_info(
     }
 }
 
+/// Reads the delay from a `Retry-After` header expressed in seconds.
+pub fn retry_after_from_headers(headers: &HeaderMap) -> Option<Duration> {
+    let retry_after = get_header("retry-after", headers).ok()?;
+    retry_after.trim().parse().ok().map(Duration::from_secs)
+}
+
 pub async fn extract_tool_args_from_events(
     tool_name: String,
     mut events: Pin<Box<dyn Send + Stream<Item = Result<Event>>>>,
@@ -703,6 +717,12 @@ pub struct MessageDelta {
 pub enum AnthropicError {
     #[error("an error occurred while interacting with the Anthropic API: {error_type}: {message}", error_type = .0.error_type, message = .0.message)]
     ApiError(ApiError),
+    /// The request was rejected with a 429 because the rate limit was exceeded.
+    #[error("the Anthropic API rate limit was exceeded")]
+    RateLimit {
+        /// How long the API asked to wait before retrying, if it said so.
+        retry_after: Option<Duration>,
+    },
     #[error("{0}")]
     Other(#[from] anyhow::Error),
 }
@@ -3,6 +3,7 @@ mod context_tests;
 
 use crate::patch::{AssistantEdit, AssistantPatch, AssistantPatchStatus};
 use anyhow::{Context as _, Result, anyhow};
+use assistant_settings::AssistantSettings;
 use assistant_slash_command::{
     SlashCommandContent, SlashCommandEvent, SlashCommandLine, SlashCommandOutputSection,
     SlashCommandResult, SlashCommandWorkingSet,
@@ -22,13 +23,14 @@ use language_model::{
     LanguageModel, LanguageModelCacheConfiguration, LanguageModelCompletionEvent,
     LanguageModelImage, LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage,
     LanguageModelToolUseId, MaxMonthlySpendReachedError, MessageContent, PaymentRequiredError,
-    Role, StopReason, report_assistant_event,
+    RateLimitExceededError, Role, StopReason, report_assistant_event,
 };
 use open_ai::Model as OpenAiModel;
 use paths::contexts_dir;
 use project::Project;
 use prompt_store::PromptBuilder;
 use serde::{Deserialize, Serialize};
+use settings::Settings as _;
 use smallvec::SmallVec;
 use std::{
     cmp::{Ordering, max},
@@ -460,6 +462,7 @@ pub enum ContextEvent {
     MessagesEdited,
     SummaryChanged,
     StreamedCompletion,
+    CompletionRetryChanged,
     StartedThoughtProcess(Range<language::Anchor>),
     EndedThoughtProcess(language::Anchor),
     PatchesUpdated {
@@ -597,9 +600,20 @@ impl Content {
 struct PendingCompletion {
     id: usize,
     assistant_message_id: MessageId,
+    retry: Option<CompletionRetry>,
     _task: Task<()>,
 }
 
+/// A completion that was rejected because of a rate limit and is waiting to be retried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompletionRetry {
+    /// The retry that is about to be made, starting at 1.
+    pub attempt: u32,
+    pub max_attempts: u32,
+}
+
+const RATE_LIMIT_INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct InvokedSlashCommandId(clock::Lamport);
 
@@ -2330,12 +2344,46 @@ impl AssistantContext {
 
         let task = cx.spawn({
             async move |this, cx| {
-                let stream = model.stream_completion(request, &cx);
                 let assistant_message_id = assistant_message.id;
                 let mut response_latency = None;
                 let stream_completion = async {
                     let request_start = Instant::now();
-                    let mut events = stream.await?;
+                    let max_retries =
+                        cx.update(|cx| AssistantSettings::get_global(cx).max_rate_limit_retries)?;
+                    let mut retry_delay = RATE_LIMIT_INITIAL_RETRY_DELAY;
+                    let mut attempt = 0;
+                    let mut events = loop {
+                        let error = match model.stream_completion(request.clone(), &cx).await {
+                            Ok(events) => break events,
+                            Err(error) => error,
+                        };
+                        // Only rate limits are worth retrying, everything else fails right away.
+                        let Some(rate_limit) = error.downcast_ref::<RateLimitExceededError>()
+                        else {
+                            return Err(error);
+                        };
+                        if attempt >= max_retries {
+                            return Err(error);
+                        }
+
+                        attempt += 1;
+                        let delay = rate_limit.retry_after.unwrap_or(retry_delay);
+                        retry_delay *= 2;
+                        this.update(cx, |this, cx| {
+                            this.set_completion_retry(
+                                pending_completion_id,
+                                Some(CompletionRetry {
+                                    attempt,
+                                    max_attempts: max_retries,
+                                }),
+                                cx,
+                            )
+                        })?;
+                        cx.background_executor().timer(delay).await;
+                    };
+                    this.update(cx, |this, cx| {
+                        this.set_completion_retry(pending_completion_id, None, cx)
+                    })?;
                     let mut stop_reason = StopReason::EndTurn;
                     let mut thought_process_stack = Vec::new();
 
@@ -2526,6 +2574,7 @@ impl AssistantContext {
         self.pending_completions.push(PendingCompletion {
             id: pending_completion_id,
             assistant_message_id: assistant_message.id,
+            retry: None,
             _task: task,
         });
 
@@ -3018,6 +3067,68 @@ impl AssistantContext {
         }
     }
 
+    /// Returns the rate limit retry the completion for the given assistant message is waiting on.
+    pub fn completion_retry(&self, message_id: MessageId) -> Option<CompletionRetry> {
+        self.pending_completions
+            .iter()
+            .find(|completion| completion.assistant_message_id == message_id)
+            .and_then(|completion| completion.retry)
+    }
+
+    fn set_completion_retry(
+        &mut self,
+        pending_completion_id: usize,
+        retry: Option<CompletionRetry>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(completion) = self
+            .pending_completions
+            .iter_mut()
+            .find(|completion| completion.id == pending_completion_id)
+        else {
+            return;
+        };
+        if completion.retry != retry {
+            completion.retry = retry;
+            cx.emit(ContextEvent::CompletionRetryChanged);
+        }
+    }
+
+    /// Renders the conversation as Markdown, with a `## <Role>` header above each message.
+    ///
+    /// Messages keep their order in the context and their text is copied verbatim, so fenced
+    /// code blocks survive. Empty messages are skipped, as are system messages unless
+    /// `include_system_messages` is set.
+    pub fn to_markdown(&self, include_system_messages: bool, cx: &App) -> String {
+        let buffer = self.buffer.read(cx);
+        let mut markdown = String::new();
+        for message in self.messages(cx) {
+            if message.role == Role::System && !include_system_messages {
+                continue;
+            }
+
+            let text = buffer
+                .text_for_range(message.offset_range)
+                .collect::<String>();
+            let text = text.trim_matches('\n');
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            if !markdown.is_empty() {
+                markdown.push('\n');
+            }
+            let role = message.role.to_string();
+            let mut role_chars = role.chars();
+            let header = role_chars
+                .next()
+                .map(|first| first.to_uppercase().chain(role_chars).collect::<String>())
+                .unwrap_or_default();
+            markdown.push_str(&format!("## {header}\n\n{text}\n"));
+        }
+        markdown
+    }
+
     fn message_for_offset(&self, offset: usize, cx: &App) -> Option<Message> {
         self.messages_for_offsets([offset], cx).pop()
     }
@@ -1,8 +1,10 @@
 use crate::{
-    AssistantContext, AssistantEdit, AssistantEditKind, CacheStatus, ContextEvent, ContextId,
-    ContextOperation, InvokedSlashCommandId, MessageCacheMetadata, MessageId, MessageStatus,
+    AssistantContext, AssistantEdit, AssistantEditKind, CacheStatus, CompletionRetry, ContextEvent,
+    ContextId, ContextOperation, InvokedSlashCommandId, MessageCacheMetadata, MessageId,
+    MessageStatus, RequestType,
 };
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use assistant_settings::AssistantSettings;
 use assistant_slash_command::{
     ArgumentCompletion, SlashCommand, SlashCommandContent, SlashCommandEvent, SlashCommandOutput,
     SlashCommandOutputSection, SlashCommandRegistry, SlashCommandResult, SlashCommandWorkingSet,
@@ -16,14 +18,16 @@ use futures::{
 };
 use gpui::{App, Entity, SharedString, Task, TestAppContext, WeakEntity, prelude::*};
 use language::{Buffer, BufferSnapshot, LanguageRegistry, LspAdapterDelegate};
-use language_model::{LanguageModelCacheConfiguration, LanguageModelRegistry, Role};
+use language_model::{
+    LanguageModelCacheConfiguration, LanguageModelRegistry, RateLimitExceededError, Role,
+};
 use parking_lot::Mutex;
 use pretty_assertions::assert_eq;
 use project::Project;
 use prompt_store::PromptBuilder;
 use rand::prelude::*;
 use serde_json::json;
-use settings::SettingsStore;
+use settings::{Settings as _, SettingsStore};
 use std::{
     cell::RefCell,
     env,
@@ -31,6 +35,7 @@ use std::{
     path::Path,
     rc::Rc,
     sync::{Arc, atomic::AtomicBool},
+    time::Duration,
 };
 use text::{OffsetRangeExt as _, ReplicaId, ToOffset, network::Network};
 use ui::{IconName, Window};
@@ -1587,6 +1592,155 @@ fn test_mark_cache_anchors(cx: &mut App) {
     );
 }
 
+#[gpui::test]
+async fn test_rate_limited_completion_is_retried(cx: &mut TestAppContext) {
+    let settings_store = cx.update(SettingsStore::test);
+    cx.set_global(settings_store);
+    cx.update(AssistantSettings::register);
+    cx.update(LanguageModelRegistry::test);
+    let model = cx.read(|cx| {
+        LanguageModelRegistry::read_global(cx)
+            .active_model()
+            .unwrap()
+    });
+    let fake_model = model.as_fake();
+    let registry = Arc::new(LanguageRegistry::test(cx.executor()));
+    let prompt_builder = Arc::new(PromptBuilder::new(None).unwrap());
+    let context = cx.new(|cx| {
+        AssistantContext::local(
+            registry,
+            None,
+            None,
+            prompt_builder.clone(),
+            Arc::new(SlashCommandWorkingSet::default()),
+            cx,
+        )
+    });
+
+    // Errors other than rate limits fail the completion right away.
+    fake_model.fail_next_completions([anyhow!("internal server error")]);
+    context.update(cx, |context, cx| context.assist(RequestType::Chat, cx));
+    let failed_message_id =
+        context.read_with(cx, |context, cx| context.messages(cx).nth(1).unwrap().id);
+    cx.run_until_parked();
+    assert_eq!(
+        message_status(&context, failed_message_id, cx),
+        MessageStatus::Error("internal server error".into())
+    );
+
+    fake_model.fail_next_completions([
+        anyhow!(RateLimitExceededError { retry_after: None }),
+        anyhow!(RateLimitExceededError {
+            retry_after: Some(Duration::from_secs(5))
+        }),
+    ]);
+    context.update(cx, |context, cx| context.assist(RequestType::Chat, cx));
+    let message_id = context.read_with(cx, |context, cx| context.messages(cx).nth(3).unwrap().id);
+    cx.run_until_parked();
+    assert_eq!(
+        context.read_with(cx, |context, _| context.completion_retry(message_id)),
+        Some(CompletionRetry {
+            attempt: 1,
+            max_attempts: 3
+        })
+    );
+
+    // Without a `Retry-After`, the first retry waits for the initial delay.
+    cx.executor().advance_clock(Duration::from_secs(1));
+    cx.run_until_parked();
+    assert_eq!(
+        context.read_with(cx, |context, _| context.completion_retry(message_id)),
+        Some(CompletionRetry {
+            attempt: 2,
+            max_attempts: 3
+        })
+    );
+    assert_eq!(fake_model.completion_count(), 0);
+
+    // The second one waits for as long as the provider asked.
+    cx.executor().advance_clock(Duration::from_secs(5));
+    cx.run_until_parked();
+    assert_eq!(
+        context.read_with(cx, |context, _| context.completion_retry(message_id)),
+        None
+    );
+    assert_eq!(fake_model.completion_count(), 1);
+
+    fake_model.stream_last_completion_response("Hello".into());
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+    assert_eq!(
+        message_status(&context, message_id, cx),
+        MessageStatus::Done
+    );
+}
+
+fn message_status(
+    context: &Entity<AssistantContext>,
+    message_id: MessageId,
+    cx: &TestAppContext,
+) -> MessageStatus {
+    context.read_with(cx, |context, cx| {
+        context
+            .messages(cx)
+            .find(|message| message.id == message_id)
+            .unwrap()
+            .status
+    })
+}
+
+#[gpui::test]
+fn test_to_markdown(cx: &mut App) {
+    let settings_store = SettingsStore::test(cx);
+    LanguageModelRegistry::test(cx);
+    cx.set_global(settings_store);
+    let registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+    let prompt_builder = Arc::new(PromptBuilder::new(None).unwrap());
+    let context = cx.new(|cx| {
+        AssistantContext::local(
+            registry,
+            None,
+            None,
+            prompt_builder.clone(),
+            Arc::new(SlashCommandWorkingSet::default()),
+            cx,
+        )
+    });
+    let buffer = context.read(cx).buffer.clone();
+
+    let message_1 = context.read(cx).message_anchors[0].clone();
+    let message_2 = context.update(cx, |context, cx| {
+        context
+            .insert_message_after(message_1.id, Role::Assistant, MessageStatus::Done, cx)
+            .unwrap()
+    });
+    context.update(cx, |context, cx| {
+        context
+            .insert_message_after(message_2.id, Role::System, MessageStatus::Done, cx)
+            .unwrap()
+    });
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit(
+            [
+                (0..0, "What does this print?"),
+                (1..1, "It prints:\n```\n    hello\n```"),
+                (2..2, "Be concise."),
+            ],
+            None,
+            cx,
+        )
+    });
+
+    assert_eq!(
+        context.read(cx).to_markdown(false, cx),
+        "## User\n\nWhat does this print?\n\n## Assistant\n\nIt prints:\n```\n    hello\n```\n"
+    );
+    assert_eq!(
+        context.read(cx).to_markdown(true, cx),
+        "## User\n\nWhat does this print?\n\n## Assistant\n\nIt prints:\n```\n    hello\n```\n\n## System\n\nBe concise.\n"
+    );
+}
+
 fn messages(context: &Entity<AssistantContext>, cx: &App) -> Vec<(MessageId, Role, Range<usize>)> {
     context
         .read(cx)
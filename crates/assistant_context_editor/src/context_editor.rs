@@ -25,8 +25,8 @@ use gpui::{
     ClipboardItem, CursorStyle, Empty, Entity, EventEmitter, FocusHandle, Focusable, FontWeight,
     Global, InteractiveElement, IntoElement, ParentElement, Pixels, Render, RenderImage,
     SharedString, Size, StatefulInteractiveElement, Styled, Subscription, Task, Transformation,
-    WeakEntity, actions, div, img, impl_internal_actions, percentage, point, prelude::*,
-    pulsating_between, size,
+    WeakEntity, actions, div, img, impl_actions, impl_internal_actions, percentage, point,
+    prelude::*, pulsating_between, size,
 };
 use indexed_docs::IndexedDocsStore;
 use language::{
@@ -45,6 +45,7 @@ use picker::Picker;
 use project::lsp_store::LocalLspAdapterDelegate;
 use project::{Project, Worktree};
 use rope::Point;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore, update_settings_file};
 use std::{any::TypeId, borrow::Cow, cmp, ops::Range, path::PathBuf, sync::Arc, time::Duration};
@@ -59,7 +60,7 @@ use workspace::{
     Save, ShowConfiguration, Toast, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
     Workspace,
     item::{self, FollowableItem, Item, ItemHandle},
-    notifications::NotificationId,
+    notifications::{NotificationId, NotifyTaskExt as _},
     pane::{self, SaveIntent},
     searchable::{SearchEvent, SearchableItem},
 };
@@ -88,6 +89,24 @@ actions!(
     ]
 );
 
+/// Copies the conversation to the clipboard as Markdown.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema)]
+pub struct CopyAsMarkdown {
+    /// Whether to include system messages in the export.
+    #[serde(default)]
+    pub include_system_messages: bool,
+}
+
+/// Prompts for a path and saves the conversation there as a Markdown file.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema)]
+pub struct SaveAsMarkdown {
+    /// Whether to include system messages in the export.
+    #[serde(default)]
+    pub include_system_messages: bool,
+}
+
+impl_actions!(assistant, [CopyAsMarkdown, SaveAsMarkdown]);
+
 #[derive(PartialEq, Clone)]
 pub enum InsertDraggedFiles {
     ProjectPaths(Vec<PathBuf>),
@@ -670,6 +689,10 @@ impl ContextEditor {
                     }
                 });
             }
+            ContextEvent::CompletionRetryChanged => {
+                // Message headers read the retry state while rendering.
+                self.editor.update(cx, |_, cx| cx.notify());
+            }
             ContextEvent::PatchesUpdated { removed, updated } => {
                 self.patches_updated(removed, updated, window, cx);
             }
@@ -1423,7 +1446,17 @@ impl ContextEditor {
                                             )
                                             .into_any_element(),
                                     );
-                                    note = Some(Self::esc_kbd(cx).into_any_element());
+                                    note =
+                                        Some(match context.read(cx).completion_retry(message_id) {
+                                            Some(retry) => Label::new(format!(
+                                                "Rate limited, retrying ({}/{})…",
+                                                retry.attempt, retry.max_attempts
+                                            ))
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted)
+                                            .into_any_element(),
+                                            None => Self::esc_kbd(cx).into_any_element(),
+                                        });
                                 }
                                 (animated_label, spinner, note)
                             }
@@ -2159,6 +2192,65 @@ impl ContextEditor {
         });
     }
 
+    fn copy_as_markdown(
+        &mut self,
+        action: &CopyAsMarkdown,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let markdown = self
+            .context
+            .read(cx)
+            .to_markdown(action.include_system_messages, cx);
+        cx.write_to_clipboard(ClipboardItem::new_string(markdown));
+
+        struct CopyAsMarkdownToast;
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<CopyAsMarkdownToast>(),
+                        "Conversation copied to clipboard as Markdown.",
+                    )
+                    .autohide(),
+                    cx,
+                );
+            })
+            .ok();
+    }
+
+    fn save_as_markdown(
+        &mut self,
+        action: &SaveAsMarkdown,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let markdown = self
+            .context
+            .read(cx)
+            .to_markdown(action.include_system_messages, cx);
+        let fs = self.fs.clone();
+        let directory = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .next()
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+            .unwrap_or_else(|| util::paths::home_dir().clone());
+        let path = cx.prompt_for_new_path(&directory);
+
+        cx.spawn_in(window, async move |_, _| {
+            let Some(mut path) = path.await?? else {
+                return Ok(());
+            };
+            if path.extension().is_none() {
+                path.set_extension("md");
+            }
+            fs.atomic_write(path, markdown).await
+        })
+        .detach_and_notify_err(window, cx);
+    }
+
     pub fn title(&self, cx: &App) -> Cow<str> {
         self.context
             .read(cx)
@@ -3042,6 +3134,8 @@ impl Render for ContextEditor {
             .on_action(cx.listener(ContextEditor::edit))
             .on_action(cx.listener(ContextEditor::assist))
             .on_action(cx.listener(ContextEditor::split))
+            .on_action(cx.listener(ContextEditor::copy_as_markdown))
+            .on_action(cx.listener(ContextEditor::save_as_markdown))
             .on_action(move |_: &ToggleModelSelector, window, cx| {
                 language_model_selector.toggle(window, cx);
             })
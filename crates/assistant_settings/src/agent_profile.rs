@@ -34,6 +34,8 @@ pub struct AgentProfile {
     pub tools: IndexMap<Arc<str>, bool>,
     pub enable_all_context_servers: bool,
     pub context_servers: IndexMap<Arc<str>, ContextServerPreset>,
+    /// Whether each of the user's script tools is enabled, by tool name.
+    pub user_scripts: IndexMap<Arc<str>, bool>,
 }
 
 #[derive(Debug, Clone, Default)]
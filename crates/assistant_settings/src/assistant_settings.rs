@@ -85,6 +85,7 @@ pub struct AssistantSettings {
     pub profiles: IndexMap<AgentProfileId, AgentProfile>,
     pub always_allow_tool_actions: bool,
     pub notify_when_agent_waiting: NotifyWhenAgentWaiting,
+    pub max_rate_limit_retries: u32,
 }
 
 impl AssistantSettings {
@@ -193,6 +194,7 @@ impl AssistantSettingsContent {
                     profiles: None,
                     always_allow_tool_actions: None,
                     notify_when_agent_waiting: None,
+                    max_rate_limit_retries: None,
                 },
                 VersionedAssistantSettingsContent::V2(settings) => settings.clone(),
             },
@@ -218,6 +220,7 @@ impl AssistantSettingsContent {
                 profiles: None,
                 always_allow_tool_actions: None,
                 notify_when_agent_waiting: None,
+                max_rate_limit_retries: None,
             },
         }
     }
@@ -355,12 +358,27 @@ impl AssistantSettingsContent {
             return Ok(());
         };
 
-        let profiles = settings.profiles.get_or_insert_default();
-        if profiles.contains_key(&profile_id) {
+        if settings
+            .profiles
+            .as_ref()
+            .is_some_and(|profiles| profiles.contains_key(&profile_id))
+        {
             bail!("profile with ID '{profile_id}' already exists");
         }
 
-        profiles.insert(
+        self.save_profile(profile_id, profile);
+        Ok(())
+    }
+
+    /// Saves the profile, replacing any existing profile with the same ID.
+    pub fn save_profile(&mut self, profile_id: AgentProfileId, profile: AgentProfile) {
+        let AssistantSettingsContent::Versioned(VersionedAssistantSettingsContent::V2(settings)) =
+            self
+        else {
+            return;
+        };
+
+        settings.profiles.get_or_insert_default().insert(
             profile_id,
             AgentProfileContent {
                 name: profile.name.into(),
@@ -378,10 +396,9 @@ impl AssistantSettingsContent {
                         )
                     })
                     .collect(),
+                user_scripts: profile.user_scripts,
             },
         );
-
-        Ok(())
     }
 }
 
@@ -410,6 +427,7 @@ impl Default for VersionedAssistantSettingsContent {
             profiles: None,
             always_allow_tool_actions: None,
             notify_when_agent_waiting: None,
+            max_rate_limit_retries: None,
         })
     }
 }
@@ -461,6 +479,11 @@ pub struct AssistantSettingsContentV2 {
     ///
     /// Default: "primary_screen"
     notify_when_agent_waiting: Option<NotifyWhenAgentWaiting>,
+    /// How many times to retry a completion request that was rejected
+    /// because the provider's rate limit was exceeded.
+    ///
+    /// Default: 3
+    max_rate_limit_retries: Option<u32>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
@@ -506,6 +529,9 @@ pub struct AgentProfileContent {
     pub enable_all_context_servers: Option<bool>,
     #[serde(default)]
     pub context_servers: IndexMap<Arc<str>, ContextServerPresetContent>,
+    /// Whether each of the user's script tools is enabled, by tool name.
+    #[serde(default)]
+    pub user_scripts: IndexMap<Arc<str>, bool>,
 }
 
 #[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -615,6 +641,10 @@ impl Settings for AssistantSettings {
                 &mut settings.notify_when_agent_waiting,
                 value.notify_when_agent_waiting,
             );
+            merge(
+                &mut settings.max_rate_limit_retries,
+                value.max_rate_limit_retries,
+            );
             merge(&mut settings.default_profile, value.default_profile);
 
             if let Some(profiles) = value.profiles {
@@ -641,6 +671,7 @@ impl Settings for AssistantSettings {
                                         )
                                     })
                                     .collect(),
+                                user_scripts: profile.user_scripts,
                             },
                         )
                     }));
@@ -713,6 +744,7 @@ mod tests {
                             profiles: None,
                             always_allow_tool_actions: None,
                             notify_when_agent_waiting: None,
+                            max_rate_limit_retries: None,
                         }),
                     )
                 },
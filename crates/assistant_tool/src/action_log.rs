@@ -4,9 +4,11 @@ use collections::{BTreeMap, HashSet};
 use futures::{StreamExt, channel::mpsc};
 use gpui::{App, AppContext, AsyncApp, Context, Entity, Subscription, Task, WeakEntity};
 use language::{Anchor, Buffer, BufferEvent, DiskState, Point};
-use std::{cmp, ops::Range, sync::Arc};
+use project::Project;
+use serde::{Deserialize, Serialize};
+use std::{cmp, ops::Range, path::PathBuf, sync::Arc};
 use text::{Edit, Patch, Rope};
-use util::RangeExt;
+use util::{RangeExt, ResultExt as _};
 
 /// Tracks actions performed by tools in a thread
 pub struct ActionLog {
@@ -17,6 +19,8 @@ pub struct ActionLog {
     tracked_buffers: BTreeMap<Entity<Buffer>, TrackedBuffer>,
     /// Has the model edited a file since it last checked diagnostics?
     edited_since_project_diagnostics_check: bool,
+    /// Buffers restored from a [`SerializedActionLog`] that haven't been opened yet.
+    restored_buffers: Option<(WeakEntity<Project>, Vec<SerializedTrackedBuffer>)>,
 }
 
 impl ActionLog {
@@ -26,6 +30,7 @@ impl ActionLog {
             stale_buffers_in_context: HashSet::default(),
             tracked_buffers: BTreeMap::default(),
             edited_since_project_diagnostics_check: false,
+            restored_buffers: None,
         }
     }
 
@@ -74,6 +79,7 @@ impl ActionLog {
                     snapshot: text_snapshot.clone(),
                     status,
                     version: buffer.read(cx).version(),
+                    changed_while_untracked: false,
                     diff,
                     diff_update: diff_update_tx,
                     _maintain_diff: cx.spawn({
@@ -88,6 +94,7 @@ impl ActionLog {
                 }
             });
         tracked_buffer.version = buffer.read(cx).version();
+        tracked_buffer.changed_while_untracked = false;
         tracked_buffer
     }
 
@@ -384,7 +391,7 @@ impl ActionLog {
             .filter(|(buffer, tracked)| {
                 let buffer = buffer.read(cx);
 
-                tracked.version != buffer.version
+                (tracked.version != buffer.version || tracked.changed_while_untracked)
                     && buffer
                         .file()
                         .map_or(false, |file| file.disk_state() != DiskState::Deleted)
@@ -396,6 +403,111 @@ impl ActionLog {
     pub fn take_stale_buffers_in_context(&mut self) -> HashSet<Entity<Buffer>> {
         std::mem::take(&mut self.stale_buffers_in_context)
     }
+
+    /// Serializes the buffers the model has seen, so that they can be restored with
+    /// [`Self::restore`] when the thread is reloaded. Buffers deleted by the model, and
+    /// buffers without a file, are skipped.
+    pub fn serialize(&self, cx: &App) -> SerializedActionLog {
+        let mut buffers: Vec<SerializedTrackedBuffer> = self
+            .tracked_buffers
+            .iter()
+            .filter(|(_, tracked)| tracked.status != TrackedBufferStatus::Deleted)
+            .filter_map(|(buffer, tracked)| {
+                let buffer = buffer.read(cx);
+                let file = buffer.file()?;
+                Some(SerializedTrackedBuffer {
+                    path: file.full_path(cx),
+                    version: tracked.version.iter().collect(),
+                    mtime: file.disk_state().mtime(),
+                    dirty: buffer.is_dirty(),
+                })
+            })
+            .collect();
+        // Buffers restored but not opened yet are kept as they were.
+        if let Some((_, restored_buffers)) = &self.restored_buffers {
+            buffers.extend(restored_buffers.iter().cloned());
+        }
+        SerializedActionLog { buffers }
+    }
+
+    /// Tracks the buffers of a [`SerializedActionLog`] again. The buffers are only opened
+    /// in the project by [`Self::resolve_restored_buffers`].
+    pub fn restore(&mut self, serialized: SerializedActionLog, project: &Entity<Project>) {
+        if !serialized.buffers.is_empty() {
+            self.restored_buffers = Some((project.downgrade(), serialized.buffers));
+        }
+    }
+
+    /// Opens the buffers [restored](Self::restore) since the last call, if any, and tracks
+    /// them again.
+    ///
+    /// A restored buffer is considered stale if its file was modified since the log was
+    /// serialized, or if it was edited, which is only known for sure for buffers that were
+    /// saved both then and now.
+    pub fn resolve_restored_buffers(&mut self, cx: &mut Context<Self>) -> Option<Task<()>> {
+        let (project, serialized_buffers) = self.restored_buffers.take()?;
+        let project = project.upgrade()?;
+        let buffers = serialized_buffers
+            .into_iter()
+            .filter_map(|serialized_buffer| {
+                let project_path = project
+                    .read(cx)
+                    .find_project_path(&serialized_buffer.path, cx)?;
+                let open_buffer =
+                    project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+                Some((serialized_buffer, open_buffer))
+            })
+            .collect::<Vec<_>>();
+
+        Some(cx.spawn(async move |this, cx| {
+            for (serialized_buffer, open_buffer) in buffers {
+                let Some(buffer) = open_buffer.await.log_err() else {
+                    continue;
+                };
+                this.update(cx, |this, cx| {
+                    if this.tracked_buffers.contains_key(&buffer) {
+                        return;
+                    }
+                    let mut version = clock::Global::new();
+                    for timestamp in serialized_buffer.version {
+                        version.observe(timestamp);
+                    }
+                    // Versions aren't comparable across sessions, in which case the file
+                    // must not have changed on disk.
+                    let unchanged = {
+                        let buffer = buffer.read(cx);
+                        let mtime = buffer.file().and_then(|file| file.disk_state().mtime());
+                        mtime == serialized_buffer.mtime
+                            && (buffer.version() == version
+                                || (!serialized_buffer.dirty && !buffer.is_dirty()))
+                    };
+                    let tracked_buffer = this.track_buffer(buffer, false, cx);
+                    tracked_buffer.changed_while_untracked = !unchanged;
+                    cx.notify();
+                })
+                .ok();
+            }
+        }))
+    }
+}
+
+/// The buffers tracked by an [`ActionLog`], in a form that can be persisted along with the
+/// thread using it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedActionLog {
+    pub buffers: Vec<SerializedTrackedBuffer>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedTrackedBuffer {
+    /// The path of the buffer's file, starting with the name of its worktree.
+    pub path: PathBuf,
+    /// The version of the buffer when the model last saw it.
+    pub version: Vec<clock::Lamport>,
+    /// The modification time of the buffer's file when the log was serialized.
+    pub mtime: Option<fs::MTime>,
+    /// Whether the buffer had unsaved changes when the log was serialized.
+    pub dirty: bool,
 }
 
 fn apply_non_conflicting_edits(
@@ -532,6 +644,9 @@ struct TrackedBuffer {
     unreviewed_changes: Patch<u32>,
     status: TrackedBufferStatus,
     version: clock::Global,
+    /// Whether the buffer changed while the log wasn't tracking it, e.g. before the log was
+    /// restored.
+    changed_while_untracked: bool,
     diff: Entity<BufferDiff>,
     snapshot: text::BufferSnapshot,
     diff_update: mpsc::UnboundedSender<(ChangeAuthor, text::BufferSnapshot)>,
@@ -1058,6 +1173,95 @@ mod tests {
         old_text: String,
     }
 
+    #[gpui::test]
+    async fn test_serialize_and_restore(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({"file1": "lorem\n", "file2": "ipsum\n"}),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+        let action_log = cx.new(|_| ActionLog::new());
+        for path in ["dir/file1", "dir/file2"] {
+            let project_path = project
+                .read_with(cx, |project, cx| project.find_project_path(path, cx))
+                .unwrap();
+            let buffer = project
+                .update(cx, |project, cx| project.open_buffer(project_path, cx))
+                .await
+                .unwrap();
+            action_log.update(cx, |log, cx| log.buffer_read(buffer, cx));
+        }
+        let serialized = cx.read(|cx| action_log.read(cx).serialize(cx));
+        let serialized: SerializedActionLog =
+            serde_json::from_str(&serde_json::to_string(&serialized).unwrap()).unwrap();
+        assert_eq!(serialized.buffers.len(), 2);
+
+        // Restoring in the same session finds the buffers unchanged.
+        let restored_log = cx.new(|_| ActionLog::new());
+        let resolve = restored_log.update(cx, |log, cx| {
+            log.restore(serialized.clone(), &project);
+            assert!(log.tracked_buffers.is_empty(), "buffers are opened lazily");
+            log.resolve_restored_buffers(cx)
+        });
+        resolve.unwrap().await;
+        assert!(
+            restored_log
+                .update(cx, |log, cx| log.resolve_restored_buffers(cx))
+                .is_none()
+        );
+        assert_eq!(stale_paths(&restored_log, cx), Vec::<String>::new());
+
+        // Edits made after restoring make the buffer stale.
+        let buffer1 = cx.read(|cx| {
+            restored_log
+                .read(cx)
+                .tracked_buffers
+                .keys()
+                .find(|buffer| buffer.read(cx).text() == "lorem\n")
+                .unwrap()
+                .clone()
+        });
+        buffer1.update(cx, |buffer, cx| buffer.set_text("LOREM\n", cx));
+        assert_eq!(stale_paths(&restored_log, cx), vec!["dir/file1"]);
+
+        // In a new session, only the files changed on disk in the meantime are stale.
+        fs.insert_file(path!("/dir/file2"), "IPSUM\n".as_bytes().to_vec())
+            .await;
+        let new_project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let restored_log = cx.new(|_| ActionLog::new());
+        let resolve = restored_log.update(cx, |log, cx| {
+            log.restore(serialized, &new_project);
+            log.resolve_restored_buffers(cx)
+        });
+        resolve.unwrap().await;
+        assert_eq!(stale_paths(&restored_log, cx), vec!["dir/file2"]);
+    }
+
+    fn stale_paths(action_log: &Entity<ActionLog>, cx: &TestAppContext) -> Vec<String> {
+        cx.read(|cx| {
+            let mut paths = action_log
+                .read(cx)
+                .stale_buffers(cx)
+                .map(|buffer| {
+                    let file = buffer.read(cx).file().unwrap();
+                    file.full_path(cx).to_string_lossy().into_owned()
+                })
+                .collect::<Vec<_>>();
+            paths.sort();
+            paths
+        })
+    }
+
     fn unreviewed_hunks(
         action_log: &Entity<ActionLog>,
         cx: &TestAppContext,
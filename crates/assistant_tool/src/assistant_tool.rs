@@ -1,20 +1,28 @@
 mod action_log;
+mod tool_cancellation;
+mod tool_input_validation;
 mod tool_registry;
 mod tool_working_set;
 
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::Result;
-use gpui::{App, Entity, SharedString, Task};
+use anyhow::{Result, anyhow};
+use futures::stream::BoxStream;
+use futures::{FutureExt as _, StreamExt as _};
+use gpui::{App, AppContext as _, Entity, SharedString, Task};
 use icons::IconName;
 use language_model::LanguageModelRequestMessage;
 use language_model::LanguageModelToolSchemaFormat;
 use project::Project;
 
 pub use crate::action_log::*;
+pub use crate::tool_cancellation::*;
+pub use crate::tool_input_validation::*;
 pub use crate::tool_registry::*;
 pub use crate::tool_working_set::*;
 
@@ -28,6 +36,26 @@ pub enum ToolSource {
     Native,
     /// A tool provided by a context server.
     ContextServer { id: SharedString },
+    /// A tool backed by one of the user's scripts, at `path`.
+    UserScript { path: Arc<Path> },
+}
+
+/// The output of [`Tool::run_streaming`].
+pub struct StreamingToolOutput {
+    /// Chunks of output, sent as the tool produces them so the UI can show its progress.
+    pub chunks: BoxStream<'static, String>,
+    /// The final result of the tool, which is what the model receives.
+    pub result: Task<Result<String>>,
+}
+
+impl StreamingToolOutput {
+    /// Output that only consists of the final result.
+    pub fn from_result(result: Task<Result<String>>) -> Self {
+        Self {
+            chunks: futures::stream::empty().boxed(),
+            result,
+        }
+    }
 }
 
 /// A tool that can be used by a language model.
@@ -47,10 +75,16 @@ pub trait Tool: 'static + Send + Sync {
     }
 
     /// Returns true iff the tool needs the users's confirmation
-    /// before having permission to run.
-    fn needs_confirmation(&self) -> bool;
+    /// before having permission to run with the given input.
+    ///
+    /// The input comes from the model and may not match the tool's input
+    /// schema, so tools should require confirmation when they can't parse it.
+    fn needs_confirmation(&self, input: &serde_json::Value) -> bool;
 
     /// Returns the JSON schema that describes the tool's input.
+    ///
+    /// Input is checked against this schema before the tool runs, so `run` only sees input
+    /// that matches it. Tools that return an empty schema accept any input.
     fn input_schema(&self, _: LanguageModelToolSchemaFormat) -> serde_json::Value {
         serde_json::Value::Object(serde_json::Map::default())
     }
@@ -58,15 +92,162 @@ pub trait Tool: 'static + Send + Sync {
     /// Returns markdown to be displayed in the UI for this tool.
     fn ui_text(&self, input: &serde_json::Value) -> String;
 
+    /// Returns how long the tool may run before it is canceled, if it is limited.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// Runs the tool with the provided input.
+    ///
+    /// Long-running tools should check `cancellation` periodically. Once it's canceled, they
+    /// should stop and fail with [`ToolCanceled`] rather than return a partial result.
     fn run(
         self: Arc<Self>,
         input: serde_json::Value,
         messages: &[LanguageModelRequestMessage],
         project: Entity<Project>,
         action_log: Entity<ActionLog>,
+        cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>>;
+
+    /// Runs the tool with the provided input, streaming its output as it's produced.
+    ///
+    /// Dropping the returned output cancels the tool. Tools that don't override this only
+    /// report their final result.
+    fn run_streaming(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        messages: &[LanguageModelRequestMessage],
+        project: Entity<Project>,
+        action_log: Entity<ActionLog>,
+        cancellation: CancellationToken,
+        cx: &mut App,
+    ) -> StreamingToolOutput {
+        StreamingToolOutput::from_result(self.run(
+            input,
+            messages,
+            project,
+            action_log,
+            cancellation,
+            cx,
+        ))
+    }
+}
+
+/// Runs the tool with the provided input, canceling it and failing if it runs for longer
+/// than its [`Tool::timeout`].
+///
+/// Fails with a [`ToolInputError`] without running the tool if the input doesn't match the
+/// tool's [`Tool::input_schema`].
+pub fn run_with_timeout(
+    tool: Arc<dyn Tool>,
+    input: serde_json::Value,
+    messages: &[LanguageModelRequestMessage],
+    project: Entity<Project>,
+    action_log: Entity<ActionLog>,
+    cancellation: CancellationToken,
+    cx: &mut App,
+) -> Task<Result<String>> {
+    if let Err(error) = validate_input(tool.as_ref(), &input) {
+        return Task::ready(Err(error.into()));
+    }
+
+    let timeout = tool.timeout();
+    let task = tool.clone().run(
+        input,
+        messages,
+        project,
+        action_log,
+        cancellation.clone(),
+        cx,
+    );
+    let task = with_timeout(task, timeout, cancellation, cx);
+    report_run(&tool, task, cx)
+}
+
+/// Like [`run_with_timeout`], but streams the tool's output with [`Tool::run_streaming`].
+pub fn run_streaming_with_timeout(
+    tool: Arc<dyn Tool>,
+    input: serde_json::Value,
+    messages: &[LanguageModelRequestMessage],
+    project: Entity<Project>,
+    action_log: Entity<ActionLog>,
+    cancellation: CancellationToken,
+    cx: &mut App,
+) -> StreamingToolOutput {
+    if let Err(error) = validate_input(tool.as_ref(), &input) {
+        return StreamingToolOutput::from_result(Task::ready(Err(error.into())));
+    }
+
+    let timeout = tool.timeout();
+    let output = tool.clone().run_streaming(
+        input,
+        messages,
+        project,
+        action_log,
+        cancellation.clone(),
+        cx,
+    );
+    StreamingToolOutput {
+        chunks: output.chunks,
+        result: report_run(
+            &tool,
+            with_timeout(output.result, timeout, cancellation, cx),
+            cx,
+        ),
+    }
+}
+
+fn validate_input(tool: &dyn Tool, input: &serde_json::Value) -> Result<(), ToolInputError> {
+    let schema = tool.input_schema(LanguageModelToolSchemaFormat::JsonSchema);
+    validate_tool_input(&schema, input)
+}
+
+/// Reports the run to the [`ToolRegistry`] once the tool finishes.
+fn report_run(
+    tool: &Arc<dyn Tool>,
+    task: Task<Result<String>>,
+    cx: &mut App,
+) -> Task<Result<String>> {
+    let registry = ToolRegistry::default_global(cx);
+    let tool_name = tool.name();
+    let source = tool.source();
+    let executor = cx.background_executor().clone();
+    let started_at = executor.now();
+    cx.background_spawn(async move {
+        let result = task.await;
+        registry.report_tool_run(ToolRunEvent {
+            tool_name,
+            source,
+            duration: executor.now() - started_at,
+            success: result.is_ok(),
+        });
+        result
+    })
+}
+
+fn with_timeout(
+    task: Task<Result<String>>,
+    timeout: Option<Duration>,
+    cancellation: CancellationToken,
+    cx: &mut App,
+) -> Task<Result<String>> {
+    let Some(timeout) = timeout else {
+        return task;
+    };
+
+    let timer = cx.background_executor().timer(timeout);
+    cx.background_spawn(async move {
+        futures::select_biased! {
+            output = task.fuse() => output,
+            // Dropping the tool's task cancels it, but it may have work in flight elsewhere.
+            _ = timer.fuse() => {
+                cancellation.cancel();
+                Err(anyhow!("tool timed out"))
+            }
+        }
+    })
 }
 
 impl Debug for dyn Tool {
@@ -74,3 +255,444 @@ impl Debug for dyn Tool {
         f.debug_struct("Tool").field("name", &self.name()).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use super::*;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use settings::SettingsStore;
+
+    /// A tool that takes ten seconds to finish.
+    struct SlowTool {
+        timeout: Option<Duration>,
+        finished: Arc<AtomicBool>,
+        source: ToolSource,
+    }
+
+    impl Tool for SlowTool {
+        fn name(&self) -> String {
+            "slow".into()
+        }
+
+        fn source(&self) -> ToolSource {
+            self.source.clone()
+        }
+
+        fn description(&self) -> String {
+            "Takes a while".into()
+        }
+
+        fn icon(&self) -> IconName {
+            IconName::Cog
+        }
+
+        fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
+            false
+        }
+
+        fn timeout(&self) -> Option<Duration> {
+            self.timeout
+        }
+
+        fn ui_text(&self, _: &serde_json::Value) -> String {
+            "Run slow tool".into()
+        }
+
+        fn run(
+            self: Arc<Self>,
+            _: serde_json::Value,
+            _: &[LanguageModelRequestMessage],
+            _: Entity<Project>,
+            _: Entity<ActionLog>,
+            _: CancellationToken,
+            cx: &mut App,
+        ) -> Task<Result<String>> {
+            let timer = cx.background_executor().timer(Duration::from_secs(10));
+            cx.background_spawn(async move {
+                timer.await;
+                self.finished.store(true, Ordering::SeqCst);
+                Ok("done".into())
+            })
+        }
+    }
+
+    #[gpui::test]
+    async fn test_run_with_timeout(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let action_log = cx.new(|_| ActionLog::new());
+
+        let run = |timeout, cx: &mut TestAppContext| {
+            let finished = Arc::new(AtomicBool::new(false));
+            let tool = Arc::new(SlowTool {
+                timeout,
+                finished: finished.clone(),
+                source: ToolSource::Native,
+            });
+            let task = cx.update(|cx| {
+                run_with_timeout(
+                    tool,
+                    serde_json::Value::Null,
+                    &[],
+                    project.clone(),
+                    action_log.clone(),
+                    CancellationToken::new(),
+                    cx,
+                )
+            });
+            (task, finished)
+        };
+
+        let (task, finished) = run(Some(Duration::from_secs(1)), cx);
+        cx.executor().advance_clock(Duration::from_secs(2));
+        let error = task.await.unwrap_err();
+        assert_eq!(error.to_string(), "tool timed out");
+        cx.executor().advance_clock(Duration::from_secs(10));
+        cx.run_until_parked();
+        assert!(
+            !finished.load(Ordering::SeqCst),
+            "the tool should be canceled"
+        );
+
+        let (task, finished) = run(None, cx);
+        cx.executor().advance_clock(Duration::from_secs(10));
+        assert_eq!(task.await.unwrap(), "done");
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[gpui::test]
+    async fn test_run_reports_telemetry(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let action_log = cx.new(|_| ActionLog::new());
+        let registry = cx.update(ToolRegistry::default_global);
+        registry.capture_tool_runs();
+
+        let run = |timeout, source, cx: &mut TestAppContext| {
+            let tool = Arc::new(SlowTool {
+                timeout,
+                finished: Arc::new(AtomicBool::new(false)),
+                source,
+            });
+            cx.update(|cx| {
+                run_with_timeout(
+                    tool,
+                    serde_json::Value::Null,
+                    &[],
+                    project.clone(),
+                    action_log.clone(),
+                    CancellationToken::new(),
+                    cx,
+                )
+            })
+        };
+
+        let task = run(None, ToolSource::Native, cx);
+        cx.executor().advance_clock(Duration::from_secs(10));
+        task.await.unwrap();
+        assert_eq!(
+            registry.take_captured_tool_runs(),
+            [ToolRunEvent {
+                tool_name: "slow".into(),
+                source: ToolSource::Native,
+                duration: Duration::from_secs(10),
+                success: true,
+            }]
+        );
+
+        let source = ToolSource::ContextServer { id: "test".into() };
+        let task = run(Some(Duration::from_secs(1)), source.clone(), cx);
+        cx.executor().advance_clock(Duration::from_secs(1));
+        task.await.unwrap_err();
+        assert_eq!(
+            registry.take_captured_tool_runs(),
+            [ToolRunEvent {
+                tool_name: "slow".into(),
+                source,
+                duration: Duration::from_secs(1),
+                success: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reload_user_script_tools() {
+        let registry = ToolRegistry::new();
+        let script = |path: &str| -> Arc<dyn Tool> {
+            Arc::new(SlowTool {
+                timeout: None,
+                finished: Arc::new(AtomicBool::new(false)),
+                source: ToolSource::UserScript {
+                    path: Path::new(path).into(),
+                },
+            })
+        };
+
+        registry.reload_user_script_tools([script("/scripts/slow.sh")]);
+        assert_eq!(
+            registry.tool("slow").unwrap().source(),
+            ToolSource::UserScript {
+                path: Path::new("/scripts/slow.sh").into()
+            }
+        );
+
+        registry.reload_user_script_tools([script("/scripts/slower.sh")]);
+        assert_eq!(
+            registry.tool("slow").unwrap().source(),
+            ToolSource::UserScript {
+                path: Path::new("/scripts/slower.sh").into()
+            }
+        );
+
+        registry.reload_user_script_tools(Vec::<Arc<dyn Tool>>::new());
+        assert!(registry.tool("slow").is_none());
+
+        registry.register_tool(SlowTool {
+            timeout: None,
+            finished: Arc::new(AtomicBool::new(false)),
+            source: ToolSource::Native,
+        });
+        registry.reload_user_script_tools([script("/scripts/slow.sh")]);
+        assert_eq!(registry.tool("slow").unwrap().source(), ToolSource::Native);
+    }
+
+    /// A tool that works in one-second steps, checking for cancellation between them.
+    struct CancelableTool {
+        steps: usize,
+        steps_done: Arc<AtomicUsize>,
+    }
+
+    impl Tool for CancelableTool {
+        fn name(&self) -> String {
+            "cancelable".into()
+        }
+
+        fn description(&self) -> String {
+            "Can be canceled".into()
+        }
+
+        fn icon(&self) -> IconName {
+            IconName::Cog
+        }
+
+        fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
+            false
+        }
+
+        fn ui_text(&self, _: &serde_json::Value) -> String {
+            "Run cancelable tool".into()
+        }
+
+        fn run(
+            self: Arc<Self>,
+            _: serde_json::Value,
+            _: &[LanguageModelRequestMessage],
+            _: Entity<Project>,
+            _: Entity<ActionLog>,
+            cancellation: CancellationToken,
+            cx: &mut App,
+        ) -> Task<Result<String>> {
+            let executor = cx.background_executor().clone();
+            cx.background_spawn(async move {
+                for _ in 0..self.steps {
+                    cancellation.check()?;
+                    executor.timer(Duration::from_secs(1)).await;
+                    self.steps_done.fetch_add(1, Ordering::SeqCst);
+                }
+                Ok("done".into())
+            })
+        }
+    }
+
+    #[gpui::test]
+    async fn test_run_canceled(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let action_log = cx.new(|_| ActionLog::new());
+
+        let steps_done = Arc::new(AtomicUsize::new(0));
+        let cancellation = CancellationToken::new();
+        let task = cx.update(|cx| {
+            run_with_timeout(
+                Arc::new(CancelableTool {
+                    steps: 10,
+                    steps_done: steps_done.clone(),
+                }),
+                serde_json::Value::Null,
+                &[],
+                project.clone(),
+                action_log.clone(),
+                cancellation.clone(),
+                cx,
+            )
+        });
+        cx.executor().advance_clock(Duration::from_millis(2500));
+        cancellation.cancel();
+        cx.executor().advance_clock(Duration::from_secs(10));
+
+        let error = task.await.unwrap_err();
+        assert!(error.is::<ToolCanceled>(), "unexpected error: {error}");
+        assert_eq!(steps_done.load(Ordering::SeqCst), 3);
+    }
+
+    /// A tool that outputs a line every second.
+    struct LinesTool {
+        line_count: usize,
+        lines_sent: Arc<AtomicUsize>,
+    }
+
+    impl Tool for LinesTool {
+        fn name(&self) -> String {
+            "lines".into()
+        }
+
+        fn description(&self) -> String {
+            "Outputs lines".into()
+        }
+
+        fn icon(&self) -> IconName {
+            IconName::Cog
+        }
+
+        fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
+            false
+        }
+
+        fn ui_text(&self, _: &serde_json::Value) -> String {
+            "Output lines".into()
+        }
+
+        fn run(
+            self: Arc<Self>,
+            input: serde_json::Value,
+            messages: &[LanguageModelRequestMessage],
+            project: Entity<Project>,
+            action_log: Entity<ActionLog>,
+            cancellation: CancellationToken,
+            cx: &mut App,
+        ) -> Task<Result<String>> {
+            self.run_streaming(input, messages, project, action_log, cancellation, cx)
+                .result
+        }
+
+        fn run_streaming(
+            self: Arc<Self>,
+            _: serde_json::Value,
+            _: &[LanguageModelRequestMessage],
+            _: Entity<Project>,
+            _: Entity<ActionLog>,
+            _: CancellationToken,
+            cx: &mut App,
+        ) -> StreamingToolOutput {
+            let (chunks_tx, chunks_rx) = futures::channel::mpsc::unbounded();
+            let executor = cx.background_executor().clone();
+            let result = cx.background_spawn(async move {
+                let mut output = String::new();
+                for ix in 0..self.line_count {
+                    executor.timer(Duration::from_secs(1)).await;
+                    let line = format!("line {ix}\n");
+                    output.push_str(&line);
+                    chunks_tx.unbounded_send(line).ok();
+                    self.lines_sent.fetch_add(1, Ordering::SeqCst);
+                }
+                Ok(output)
+            });
+            StreamingToolOutput {
+                chunks: chunks_rx.boxed(),
+                result,
+            }
+        }
+    }
+
+    #[gpui::test]
+    async fn test_run_streaming(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let action_log = cx.new(|_| ActionLog::new());
+
+        let run = |tool: Arc<dyn Tool>, cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                run_streaming_with_timeout(
+                    tool,
+                    serde_json::Value::Null,
+                    &[],
+                    project.clone(),
+                    action_log.clone(),
+                    CancellationToken::new(),
+                    cx,
+                )
+            })
+        };
+
+        // Chunks arrive as the tool produces them.
+        let lines_sent = Arc::new(AtomicUsize::new(0));
+        let mut output = run(
+            Arc::new(LinesTool {
+                line_count: 3,
+                lines_sent: lines_sent.clone(),
+            }),
+            cx,
+        );
+        cx.executor().advance_clock(Duration::from_secs(1));
+        assert_eq!(output.chunks.next().await.as_deref(), Some("line 0\n"));
+        cx.executor().advance_clock(Duration::from_secs(2));
+        assert_eq!(output.result.await.unwrap(), "line 0\nline 1\nline 2\n");
+        assert_eq!(
+            output.chunks.collect::<Vec<_>>().await,
+            ["line 1\n", "line 2\n"]
+        );
+
+        // Dropping the output cancels the tool.
+        let lines_sent = Arc::new(AtomicUsize::new(0));
+        let output = run(
+            Arc::new(LinesTool {
+                line_count: 3,
+                lines_sent: lines_sent.clone(),
+            }),
+            cx,
+        );
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        drop(output);
+        cx.executor().advance_clock(Duration::from_secs(5));
+        cx.run_until_parked();
+        assert_eq!(lines_sent.load(Ordering::SeqCst), 1);
+
+        // Tools that only implement `run` report their result without chunks.
+        let finished = Arc::new(AtomicBool::new(false));
+        let output = run(
+            Arc::new(SlowTool {
+                timeout: None,
+                finished: finished.clone(),
+                source: ToolSource::Native,
+            }),
+            cx,
+        );
+        cx.executor().advance_clock(Duration::from_secs(10));
+        assert_eq!(output.result.await.unwrap(), "done");
+        assert!(output.chunks.collect::<Vec<_>>().await.is_empty());
+    }
+}
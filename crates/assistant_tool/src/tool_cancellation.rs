@@ -0,0 +1,49 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+
+/// Lets the caller of [`Tool::run`](crate::Tool::run) ask the tool to stop early.
+///
+/// Clones share the same state, so canceling any clone cancels them all.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks the tool holding this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Returns a [`ToolCanceled`] error if the token was canceled, so tools can bail with `?`.
+    pub fn check(&self) -> Result<()> {
+        if self.is_canceled() {
+            Err(ToolCanceled.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The error returned by a tool that stopped because its [`CancellationToken`] was canceled.
+///
+/// Use `error.is::<ToolCanceled>()` to tell a cancellation apart from a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolCanceled;
+
+impl fmt::Display for ToolCanceled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("tool canceled")
+    }
+}
+
+impl std::error::Error for ToolCanceled {}
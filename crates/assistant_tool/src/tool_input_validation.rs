@@ -0,0 +1,329 @@
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+/// An error returned instead of running a tool when its input doesn't match its
+/// [`Tool::input_schema`](crate::Tool::input_schema).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolInputError {
+    pub fields: Vec<ToolInputFieldError>,
+}
+
+/// A problem with one field of a tool's input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolInputFieldError {
+    /// The path to the field, like `invocations[0].name`, or an empty string for the input itself.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ToolInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid tool input:")?;
+        for field in &self.fields {
+            if field.path.is_empty() {
+                write!(f, "\n- {}", field.message)?;
+            } else {
+                write!(f, "\n- `{}`: {}", field.path, field.message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ToolInputError {}
+
+/// Checks `input` against a tool's JSON schema.
+///
+/// Only the keywords that schemas generated for tool inputs use are checked: `type`, `enum`,
+/// `const`, `required`, `properties`, `additionalProperties`, `items`, `anyOf`, `oneOf`,
+/// `allOf`, and local `$ref`s. An empty schema accepts any input.
+pub fn validate_tool_input(schema: &Value, input: &Value) -> Result<(), ToolInputError> {
+    let fields = validate_subschema(schema, schema, input);
+    if fields.is_empty() {
+        Ok(())
+    } else {
+        Err(ToolInputError { fields })
+    }
+}
+
+/// Validates `value` against a subschema of `root`, returning the problems found.
+fn validate_subschema(root: &Value, schema: &Value, value: &Value) -> Vec<ToolInputFieldError> {
+    let mut validator = Validator {
+        root,
+        fields: Vec::new(),
+    };
+    validator.validate(schema, value, String::new());
+    validator.fields
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    fields: Vec<ToolInputFieldError>,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(&mut self, schema: &'a Value, value: &Value, path: String) {
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => return self.error(path, "is not allowed"),
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        // References we can't resolve are a problem with the schema, not the input.
+        if let Some(resolved) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| self.resolve(reference))
+        {
+            self.validate(resolved, value, path.clone());
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let matches = match expected {
+                Value::String(expected) => type_matches(expected, value),
+                Value::Array(expected) => expected
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|expected| type_matches(expected, value)),
+                _ => true,
+            };
+            if !matches {
+                let expected = match expected {
+                    Value::Array(expected) => expected
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" or "),
+                    expected => expected.as_str().unwrap_or_default().to_string(),
+                };
+                let message = format!("expected {expected}, found {}", type_name(value));
+                return self.error(path, message);
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                let allowed = allowed
+                    .iter()
+                    .map(Value::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return self.error(path, format!("expected one of {allowed}, found {value}"));
+            }
+        }
+
+        if let Some(constant) = schema.get("const") {
+            if constant != value {
+                return self.error(path, format!("expected {constant}, found {value}"));
+            }
+        }
+
+        for key in ["anyOf", "oneOf"] {
+            if let Some(alternatives) = schema.get(key).and_then(Value::as_array) {
+                let matches_any = alternatives.iter().any(|alternative| {
+                    validate_subschema(self.root, alternative, value).is_empty()
+                });
+                if !matches_any {
+                    self.error(path.clone(), "doesn't match any of the allowed schemas");
+                }
+            }
+        }
+
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            for subschema in all_of {
+                self.validate(subschema, value, path.clone());
+            }
+        }
+
+        match value {
+            Value::Object(object) => self.validate_object(schema, object, &path),
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (ix, item) in items.iter().enumerate() {
+                        self.validate(item_schema, item, format!("{path}[{ix}]"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn validate_object(
+        &mut self,
+        schema: &'a Map<String, Value>,
+        object: &Map<String, Value>,
+        path: &str,
+    ) {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(field) {
+                    self.error(field_path(path, field), "missing required field");
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let additional_properties = schema.get("additionalProperties");
+        for (field, value) in object {
+            match properties.and_then(|properties| properties.get(field)) {
+                Some(property_schema) => {
+                    self.validate(property_schema, value, field_path(path, field))
+                }
+                None => {
+                    if let Some(additional_properties) = additional_properties {
+                        if additional_properties == &Value::Bool(false) {
+                            self.error(field_path(path, field), "unknown field");
+                        } else {
+                            self.validate(additional_properties, value, field_path(path, field));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        self.root.pointer(pointer)
+    }
+
+    fn error(&mut self, path: String, message: impl Into<String>) {
+        self.fields.push(ToolInputFieldError {
+            path,
+            message: message.into(),
+        });
+    }
+}
+
+fn field_path(parent: &str, field: &str) -> String {
+    if parent.is_empty() {
+        field.to_string()
+    } else {
+        format!("{parent}.{field}")
+    }
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        expected => expected == type_name(value),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["path", "ranges"],
+            "properties": {
+                "path": { "type": "string" },
+                "offset": { "type": ["integer", "null"] },
+                "ranges": {
+                    "type": "array",
+                    "items": { "$ref": "#/definitions/Range" }
+                }
+            },
+            "definitions": {
+                "Range": {
+                    "type": "object",
+                    "required": ["start"],
+                    "properties": {
+                        "start": { "type": "integer" },
+                        "kind": { "enum": ["line", "column"] }
+                    }
+                }
+            }
+        })
+    }
+
+    fn errors(input: Value) -> Vec<(String, String)> {
+        match validate_tool_input(&schema(), &input) {
+            Ok(()) => Vec::new(),
+            Err(error) => error
+                .fields
+                .into_iter()
+                .map(|field| (field.path, field.message))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_valid_input() {
+        assert_eq!(
+            errors(json!({
+                "path": "src/main.rs",
+                "offset": null,
+                "ranges": [{ "start": 1, "kind": "line" }]
+            })),
+            []
+        );
+        assert!(validate_tool_input(&json!({}), &json!("anything")).is_ok());
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        assert_eq!(
+            errors(json!({ "ranges": [{ "kind": "line" }] })),
+            [
+                ("path".to_string(), "missing required field".to_string()),
+                (
+                    "ranges[0].start".to_string(),
+                    "missing required field".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrong_type() {
+        assert_eq!(
+            errors(json!({
+                "path": 1,
+                "offset": "2",
+                "ranges": [{ "start": 1.5, "kind": "word" }]
+            })),
+            [
+                (
+                    "path".to_string(),
+                    "expected string, found number".to_string()
+                ),
+                (
+                    "offset".to_string(),
+                    "expected integer or null, found string".to_string()
+                ),
+                (
+                    "ranges[0].start".to_string(),
+                    "expected integer, found number".to_string()
+                ),
+                (
+                    "ranges[0].kind".to_string(),
+                    r#"expected one of "line", "column", found "word""#.to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            validate_tool_input(&schema(), &json!("src/main.rs"))
+                .unwrap_err()
+                .to_string(),
+            "invalid tool input:\n- expected object, found string"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use collections::HashMap;
 use derive_more::{Deref, DerefMut};
@@ -6,7 +7,7 @@ use gpui::Global;
 use gpui::{App, ReadGlobal};
 use parking_lot::RwLock;
 
-use crate::Tool;
+use crate::{Tool, ToolSource};
 
 #[derive(Default, Deref, DerefMut)]
 struct GlobalToolRegistry(Arc<ToolRegistry>);
@@ -16,6 +17,19 @@ impl Global for GlobalToolRegistry {}
 #[derive(Default)]
 struct ToolRegistryState {
     tools: HashMap<Arc<str>, Arc<dyn Tool>>,
+    #[cfg(any(test, feature = "test-support"))]
+    captured_runs: Option<Vec<ToolRunEvent>>,
+}
+
+/// A finished run of a tool, reported to telemetry as a "Tool Run" event.
+///
+/// The tool's input and output are never recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolRunEvent {
+    pub tool_name: String,
+    pub source: ToolSource,
+    pub duration: Duration,
+    pub success: bool,
 }
 
 #[derive(Default)]
@@ -38,9 +52,7 @@ impl ToolRegistry {
 
     pub fn new() -> Arc<Self> {
         Arc::new(Self {
-            state: RwLock::new(ToolRegistryState {
-                tools: HashMap::default(),
-            }),
+            state: RwLock::new(ToolRegistryState::default()),
         })
     }
 
@@ -62,6 +74,30 @@ impl ToolRegistry {
         state.tools.remove(tool_name);
     }
 
+    /// Replaces the tools backed by user scripts with `tools`.
+    ///
+    /// The registry doesn't watch the scripts itself: whatever loads them calls this again
+    /// after they change on disk.
+    ///
+    /// Scripts named like one of the other tools are skipped, rather than replacing it.
+    pub fn reload_user_script_tools(&self, tools: impl IntoIterator<Item = Arc<dyn Tool>>) {
+        let mut state = self.state.write();
+        state
+            .tools
+            .retain(|_, tool| !matches!(tool.source(), ToolSource::UserScript { .. }));
+        for tool in tools {
+            let name: Arc<str> = tool.name().into();
+            if state.tools.contains_key(&name) {
+                log::warn!(
+                    "skipping the user script tool {name:?} at {:?}: a tool with that name already exists",
+                    tool.source()
+                );
+                continue;
+            }
+            state.tools.insert(name, tool);
+        }
+    }
+
     /// Returns the list of tools in the registry.
     pub fn tools(&self) -> Vec<Arc<dyn Tool>> {
         self.state.read().tools.values().cloned().collect()
@@ -71,4 +107,46 @@ impl ToolRegistry {
     pub fn tool(&self, name: &str) -> Option<Arc<dyn Tool>> {
         self.state.read().tools.get(name).cloned()
     }
+
+    /// Reports a finished tool run to telemetry.
+    ///
+    /// The event is dropped by the telemetry client unless metrics are enabled in the
+    /// `telemetry` settings.
+    pub fn report_tool_run(&self, event: ToolRunEvent) {
+        #[cfg(any(test, feature = "test-support"))]
+        if let Some(captured_runs) = self.state.write().captured_runs.as_mut() {
+            captured_runs.push(event.clone());
+        }
+
+        let source = match event.source {
+            ToolSource::Native => "native",
+            ToolSource::ContextServer { .. } => "context_server",
+            ToolSource::UserScript { .. } => "user_script",
+        };
+        telemetry::event!(
+            "Tool Run",
+            tool_name = event.tool_name,
+            source,
+            duration_ms = event.duration.as_millis(),
+            success = event.success,
+        );
+    }
+
+    /// Starts capturing the tool runs reported to this registry, for tests to inspect with
+    /// [`Self::take_captured_tool_runs`].
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn capture_tool_runs(&self) {
+        self.state.write().captured_runs.get_or_insert_default();
+    }
+
+    /// Returns the tool runs captured since the last call.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn take_captured_tool_runs(&self) -> Vec<ToolRunEvent> {
+        self.state
+            .write()
+            .captured_runs
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use collections::{HashMap, HashSet, IndexMap};
+use collections::{BTreeSet, HashMap, HashSet, IndexMap};
 use gpui::App;
 use parking_lot::Mutex;
 
@@ -24,6 +24,71 @@ struct WorkingSetState {
     next_tool_id: ToolId,
 }
 
+/// The tools enabled in a [`ToolWorkingSet`] at a point in time.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ToolWorkingSetSnapshot {
+    enabled_tools: BTreeSet<(ToolSource, Arc<str>)>,
+}
+
+impl ToolWorkingSetSnapshot {
+    pub fn is_enabled(&self, source: &ToolSource, name: &Arc<str>) -> bool {
+        self.enabled_tools.contains(&(source.clone(), name.clone()))
+    }
+
+    /// Returns the tools that were enabled or disabled going from this snapshot to `newer`.
+    pub fn diff(&self, newer: &ToolWorkingSetSnapshot) -> ToolWorkingSetDiff {
+        ToolWorkingSetDiff {
+            enabled: newer
+                .enabled_tools
+                .difference(&self.enabled_tools)
+                .cloned()
+                .collect(),
+            disabled: self
+                .enabled_tools
+                .difference(&newer.enabled_tools)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// The changes between two [`ToolWorkingSetSnapshot`]s, sorted by source and tool name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ToolWorkingSetDiff {
+    pub enabled: Vec<(ToolSource, Arc<str>)>,
+    pub disabled: Vec<(ToolSource, Arc<str>)>,
+}
+
+impl ToolWorkingSetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.enabled.is_empty() && self.disabled.is_empty()
+    }
+
+    /// A one-line summary of the diff, like `enabled: X, Y; disabled: Z`.
+    pub fn summary(&self) -> String {
+        fn names(tools: &[(ToolSource, Arc<str>)]) -> String {
+            tools
+                .iter()
+                .map(|(_, name)| name.as_ref())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+
+        let mut parts = Vec::new();
+        if !self.enabled.is_empty() {
+            parts.push(format!("enabled: {}", names(&self.enabled)));
+        }
+        if !self.disabled.is_empty() {
+            parts.push(format!("disabled: {}", names(&self.disabled)));
+        }
+        if parts.is_empty() {
+            "no tools changed".to_string()
+        } else {
+            parts.join("; ")
+        }
+    }
+}
+
 impl ToolWorkingSet {
     pub fn tool(&self, name: &str, cx: &App) -> Option<Arc<dyn Tool>> {
         self.state
@@ -46,6 +111,11 @@ impl ToolWorkingSet {
         self.state.lock().enabled_tools(cx)
     }
 
+    /// Captures which tools are currently enabled, to later [diff](ToolWorkingSetSnapshot::diff) against.
+    pub fn snapshot(&self) -> ToolWorkingSetSnapshot {
+        self.state.lock().snapshot()
+    }
+
     pub fn disable_all_tools(&self) {
         let mut state = self.state.lock();
         state.disable_all_tools();
@@ -120,8 +190,19 @@ impl WorkingSetState {
         let mut tools_by_source = IndexMap::default();
 
         for tool in self.tools(cx) {
+            let mut source = tool.source();
+            // All of the user's scripts are grouped together, whichever file they come from,
+            // under the source of the first of them.
+            if matches!(source, ToolSource::UserScript { .. }) {
+                if let Some(user_scripts) = tools_by_source
+                    .keys()
+                    .find(|source| matches!(source, ToolSource::UserScript { .. }))
+                {
+                    source = user_scripts.clone();
+                }
+            }
             tools_by_source
-                .entry(tool.source())
+                .entry(source)
                 .or_insert_with(Vec::new)
                 .push(tool);
         }
@@ -144,6 +225,16 @@ impl WorkingSetState {
             .collect()
     }
 
+    fn snapshot(&self) -> ToolWorkingSetSnapshot {
+        ToolWorkingSetSnapshot {
+            enabled_tools: self
+                .enabled_tools_by_source
+                .iter()
+                .flat_map(|(source, names)| names.iter().map(|name| (source.clone(), name.clone())))
+                .collect(),
+        }
+    }
+
     fn is_enabled(&self, source: &ToolSource, name: &Arc<str>) -> bool {
         self.enabled_tools_by_source
             .get(source)
@@ -192,3 +283,58 @@ impl WorkingSetState {
         self.enabled_tools_by_source.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_diff() {
+        let working_set = ToolWorkingSet::default();
+        let context_server = ToolSource::ContextServer {
+            id: "server".into(),
+        };
+        working_set.enable(ToolSource::Native, &["read_file".into(), "grep".into()]);
+        working_set.enable(context_server.clone(), &["fetch".into()]);
+        let before = working_set.snapshot();
+
+        assert!(before.is_enabled(&ToolSource::Native, &"grep".into()));
+        assert!(before.diff(&working_set.snapshot()).is_empty());
+        assert_eq!(
+            before.diff(&working_set.snapshot()).summary(),
+            "no tools changed"
+        );
+
+        working_set.disable(ToolSource::Native, &["grep".into()]);
+        working_set.disable_source(&context_server);
+        working_set.enable(
+            ToolSource::Native,
+            &["edit_file".into(), "delete_path".into()],
+        );
+        let after = working_set.snapshot();
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.enabled,
+            vec![
+                (ToolSource::Native, "delete_path".into()),
+                (ToolSource::Native, "edit_file".into()),
+            ]
+        );
+        assert_eq!(
+            diff.disabled,
+            vec![
+                (ToolSource::Native, "grep".into()),
+                (context_server, "fetch".into()),
+            ]
+        );
+        assert_eq!(
+            diff.summary(),
+            "enabled: delete_path, edit_file; disabled: grep, fetch"
+        );
+        assert_eq!(
+            after.diff(&before).summary(),
+            "enabled: grep, fetch; disabled: delete_path, edit_file"
+        );
+    }
+}
@@ -19,6 +19,7 @@ mod read_file_tool;
 mod regex_search_tool;
 mod replace;
 mod schema;
+mod summarize_file_tool;
 mod symbol_info_tool;
 mod thinking_tool;
 
@@ -46,6 +47,7 @@ use crate::open_tool::OpenTool;
 use crate::path_search_tool::PathSearchTool;
 use crate::read_file_tool::ReadFileTool;
 use crate::regex_search_tool::RegexSearchTool;
+use crate::summarize_file_tool::SummarizeFileTool;
 use crate::symbol_info_tool::SymbolInfoTool;
 use crate::thinking_tool::ThinkingTool;
 
@@ -72,6 +74,7 @@ pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
     registry.register_tool(PathSearchTool);
     registry.register_tool(ReadFileTool);
     registry.register_tool(RegexSearchTool);
+    registry.register_tool(SummarizeFileTool);
     registry.register_tool(ThinkingTool);
     registry.register_tool(FetchTool::new(http_client));
 }
@@ -1,12 +1,14 @@
 use crate::schema::json_schema_for;
 use anyhow::{Context as _, Result, anyhow};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{ActionLog, CancellationToken, StreamingToolOutput, Tool};
+use futures::{AsyncBufReadExt as _, StreamExt as _, io::BufReader};
 use gpui::{App, Entity, Task};
 use language_model::{LanguageModelRequestMessage, LanguageModelToolSchemaFormat};
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::process::Stdio;
 use std::sync::Arc;
 use ui::IconName;
 use util::command::new_smol_command;
@@ -27,7 +29,7 @@ impl Tool for BashTool {
         "bash".to_string()
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         true
     }
 
@@ -68,74 +70,81 @@ impl Tool for BashTool {
     }
 
     fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        messages: &[LanguageModelRequestMessage],
+        project: Entity<Project>,
+        action_log: Entity<ActionLog>,
+        cancellation: CancellationToken,
+        cx: &mut App,
+    ) -> Task<Result<String>> {
+        self.run_streaming(input, messages, project, action_log, cancellation, cx)
+            .result
+    }
+
+    fn run_streaming(
         self: Arc<Self>,
         input: serde_json::Value,
         _messages: &[LanguageModelRequestMessage],
         project: Entity<Project>,
         _action_log: Entity<ActionLog>,
+        _cancellation: CancellationToken,
         cx: &mut App,
-    ) -> Task<Result<String>> {
+    ) -> StreamingToolOutput {
         let input: BashToolInput = match serde_json::from_value(input) {
             Ok(input) => input,
-            Err(err) => return Task::ready(Err(anyhow!(err))),
+            Err(err) => return StreamingToolOutput::from_result(Task::ready(Err(anyhow!(err)))),
         };
-
-        let project = project.read(cx);
-        let input_path = Path::new(&input.cd);
-        let working_dir = if input.cd == "." {
-            // Accept "." as meaning "the one worktree" if we only have one worktree.
-            let mut worktrees = project.worktrees(cx);
-
-            let only_worktree = match worktrees.next() {
-                Some(worktree) => worktree,
-                None => return Task::ready(Err(anyhow!("No worktrees found in the project"))),
-            };
-
-            if worktrees.next().is_some() {
-                return Task::ready(Err(anyhow!(
-                    "'.' is ambiguous in multi-root workspaces. Please specify a root directory explicitly."
-                )));
-            }
-
-            only_worktree.read(cx).abs_path()
-        } else if input_path.is_absolute() {
-            // Absolute paths are allowed, but only if they're in one of the project's worktrees.
-            if !project
-                .worktrees(cx)
-                .any(|worktree| input_path.starts_with(&worktree.read(cx).abs_path()))
-            {
-                return Task::ready(Err(anyhow!(
-                    "The absolute path must be within one of the project's worktrees"
-                )));
-            }
-
-            input_path.into()
-        } else {
-            let Some(worktree) = project.worktree_for_root_name(&input.cd, cx) else {
-                return Task::ready(Err(anyhow!(
-                    "`cd` directory {} not found in the project",
-                    &input.cd
-                )));
-            };
-
-            worktree.read(cx).abs_path()
+        let working_dir = match working_dir(&input, project.read(cx), cx) {
+            Ok(working_dir) => working_dir,
+            Err(err) => return StreamingToolOutput::from_result(Task::ready(Err(err))),
         };
 
-        cx.spawn(async move |_| {
+        let (chunks_tx, chunks_rx) = futures::channel::mpsc::unbounded();
+        let result = cx.spawn(async move |_| {
             // Add 2>&1 to merge stderr into stdout for proper interleaving.
             let command = format!("({}) 2>&1", input.command);
 
-            let output = new_smol_command("bash")
+            // Kill the command if the tool is canceled, which drops this task.
+            let mut child = new_smol_command("bash")
                 .arg("-c")
                 .arg(&command)
                 .current_dir(working_dir)
-                .output()
-                .await
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
                 .context("Failed to execute bash command")?;
 
-            let output_string = String::from_utf8_lossy(&output.stdout).to_string();
+            let mut output_string = String::new();
+            if let Some(stdout) = child.stdout.take() {
+                let mut stdout = BufReader::new(stdout);
+                let mut line_bytes = Vec::new();
+                loop {
+                    line_bytes.clear();
+                    let bytes_read = stdout
+                        .read_until(b'\n', &mut line_bytes)
+                        .await
+                        .context("Failed to read bash command output")?;
+                    if bytes_read == 0 {
+                        break;
+                    }
+                    // Commands may print bytes that aren't valid UTF-8, which shouldn't fail
+                    // the whole run.
+                    let mut line = String::from_utf8_lossy(&line_bytes).into_owned();
+                    if !line.ends_with('\n') {
+                        line.push('\n');
+                    }
+                    output_string.push_str(&line);
+                    chunks_tx.unbounded_send(line).ok();
+                }
+            }
+            let status = child
+                .status()
+                .await
+                .context("Failed to execute bash command")?;
 
-            if output.status.success() {
+            if status.success() {
                 if output_string.is_empty() {
                     Ok("Command executed successfully.".to_string())
                 } else {
@@ -144,10 +153,55 @@ impl Tool for BashTool {
             } else {
                 Ok(format!(
                     "Command failed with exit code {}\n{}",
-                    output.status.code().unwrap_or(-1),
+                    status.code().unwrap_or(-1),
                     &output_string
                 ))
             }
-        })
+        });
+
+        StreamingToolOutput {
+            chunks: chunks_rx.boxed(),
+            result,
+        }
+    }
+}
+
+/// Resolves the directory the command runs in, which must be within one of the project's
+/// worktrees.
+fn working_dir(input: &BashToolInput, project: &Project, cx: &App) -> Result<Arc<Path>> {
+    let input_path = Path::new(&input.cd);
+    if input.cd == "." {
+        // Accept "." as meaning "the one worktree" if we only have one worktree.
+        let mut worktrees = project.worktrees(cx);
+
+        let only_worktree = worktrees
+            .next()
+            .ok_or_else(|| anyhow!("No worktrees found in the project"))?;
+
+        if worktrees.next().is_some() {
+            return Err(anyhow!(
+                "'.' is ambiguous in multi-root workspaces. Please specify a root directory explicitly."
+            ));
+        }
+
+        Ok(only_worktree.read(cx).abs_path())
+    } else if input_path.is_absolute() {
+        // Absolute paths are allowed, but only if they're in one of the project's worktrees.
+        if !project
+            .worktrees(cx)
+            .any(|worktree| input_path.starts_with(&worktree.read(cx).abs_path()))
+        {
+            return Err(anyhow!(
+                "The absolute path must be within one of the project's worktrees"
+            ));
+        }
+
+        Ok(input_path.into())
+    } else {
+        let worktree = project
+            .worktree_for_root_name(&input.cd, cx)
+            .ok_or_else(|| anyhow!("`cd` directory {} not found in the project", &input.cd))?;
+
+        Ok(worktree.read(cx).abs_path())
     }
 }
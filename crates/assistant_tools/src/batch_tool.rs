@@ -1,6 +1,6 @@
 use crate::schema::json_schema_for;
 use anyhow::{Result, anyhow};
-use assistant_tool::{ActionLog, Tool, ToolWorkingSet};
+use assistant_tool::{ActionLog, CancellationToken, Tool, ToolWorkingSet, run_with_timeout};
 use futures::future::join_all;
 use gpui::{App, AppContext, Entity, Task};
 use language_model::{LanguageModelRequestMessage, LanguageModelToolSchemaFormat};
@@ -151,7 +151,7 @@ impl Tool for BatchTool {
         "batch_tool".into()
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         true
     }
 
@@ -209,6 +209,7 @@ impl Tool for BatchTool {
         messages: &[LanguageModelRequestMessage],
         project: Entity<Project>,
         action_log: Entity<ActionLog>,
+        cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>> {
         let input = match serde_json::from_value::<BatchToolInput>(input) {
@@ -248,8 +249,19 @@ impl Tool for BatchTool {
                     let project = project.clone();
                     let action_log = action_log.clone();
                     let messages = messages.clone();
+                    let cancellation = cancellation.clone();
                     let task = cx
-                        .update(|cx| tool.run(invocation.input, &messages, project, action_log, cx))
+                        .update(|cx| {
+                            run_with_timeout(
+                                tool,
+                                invocation.input,
+                                &messages,
+                                project,
+                                action_log,
+                                cancellation,
+                                cx,
+                            )
+                        })
                         .map_err(|err| anyhow!("Failed to start tool '{}': {}", tool_name, err))?;
 
                     tasks.push(task);
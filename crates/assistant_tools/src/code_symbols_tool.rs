@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{Result, anyhow};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{ActionLog, CancellationToken, Tool};
 use collections::IndexMap;
 use gpui::{App, AsyncApp, Entity, Task};
 use language::{CodeLabel, Language, LanguageRegistry};
@@ -82,7 +82,7 @@ impl Tool for CodeSymbolsTool {
         "code_symbols".into()
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         false
     }
 
@@ -131,6 +131,7 @@ impl Tool for CodeSymbolsTool {
         _messages: &[LanguageModelRequestMessage],
         project: Entity<Project>,
         action_log: Entity<ActionLog>,
+        _cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>> {
         let input = match serde_json::from_value::<CodeSymbolsInput>(input) {
@@ -4,7 +4,7 @@ pub mod log;
 use crate::replace::{replace_exact, replace_with_flexible_indent};
 use crate::schema::json_schema_for;
 use anyhow::{Context, Result, anyhow};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{ActionLog, CancellationToken, Tool};
 use collections::HashSet;
 use edit_action::{EditAction, EditActionParser, edit_model_prompt};
 use futures::{SinkExt, StreamExt, channel::mpsc};
@@ -81,7 +81,7 @@ impl Tool for EditFilesTool {
         "edit_files".into()
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         false
     }
 
@@ -110,6 +110,7 @@ impl Tool for EditFilesTool {
         messages: &[LanguageModelRequestMessage],
         project: Entity<Project>,
         action_log: Entity<ActionLog>,
+        _cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>> {
         let input = match serde_json::from_value::<EditFilesToolInput>(input) {
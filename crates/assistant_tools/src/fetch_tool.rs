@@ -4,8 +4,10 @@ use std::sync::Arc;
 
 use crate::schema::json_schema_for;
 use anyhow::{Context as _, Result, anyhow, bail};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{ActionLog, CancellationToken, Tool};
+use async_compression::futures::bufread::{BrotliDecoder, GzipDecoder, ZlibDecoder};
 use futures::AsyncReadExt as _;
+use futures::io::BufReader;
 use gpui::{App, AppContext as _, Entity, Task};
 use html_to_markdown::{TagHandler, convert_html_to_markdown, markdown};
 use http_client::{AsyncBody, HttpClientWithUrl};
@@ -23,6 +25,51 @@ enum ContentType {
     Json,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ContentEncoding {
+    Gzip,
+    Deflate,
+    Brotli,
+}
+
+impl ContentEncoding {
+    /// Parses a `Content-Encoding` header value into the encodings to undo, in the order they need
+    /// to be undone (the reverse of the order they were applied in).
+    fn parse_header(header: &str) -> Result<Vec<Self>> {
+        let mut encodings = Vec::new();
+        for encoding in header.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match encoding.to_ascii_lowercase().as_str() {
+                "identity" => {}
+                "gzip" | "x-gzip" => encodings.push(Self::Gzip),
+                "deflate" => encodings.push(Self::Deflate),
+                "br" => encodings.push(Self::Brotli),
+                unsupported => bail!("unsupported content encoding {unsupported:?}"),
+            }
+        }
+        encodings.reverse();
+        Ok(encodings)
+    }
+
+    async fn decode(self, body: &[u8]) -> Result<Vec<u8>> {
+        let mut decoded = Vec::new();
+        let reader = BufReader::new(body);
+        match self {
+            Self::Gzip => GzipDecoder::new(reader).read_to_end(&mut decoded).await,
+            Self::Deflate => ZlibDecoder::new(reader).read_to_end(&mut decoded).await,
+            Self::Brotli => BrotliDecoder::new(reader).read_to_end(&mut decoded).await,
+        }
+        .with_context(|| format!("failed to decode {self:?} content"))?;
+        Ok(decoded)
+    }
+}
+
+async fn decode_body(content_encoding: &str, mut body: Vec<u8>) -> Result<Vec<u8>> {
+    for encoding in ContentEncoding::parse_header(content_encoding)? {
+        body = encoding.decode(&body).await?;
+    }
+    Ok(body)
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct FetchToolInput {
     /// The URL to fetch.
@@ -61,6 +108,23 @@ impl FetchTool {
             );
         }
 
+        if let Some(content_encoding) = response.headers().get("content-encoding") {
+            let content_encoding = content_encoding
+                .to_str()
+                .context("invalid Content-Encoding header")?;
+            match decode_body(content_encoding, body.clone()).await {
+                Ok(decoded) => body = decoded,
+                Err(error) => {
+                    log::warn!("failed to decompress the response of {url}: {error:#}");
+                    return Ok(format!(
+                        "Note: the response could not be decompressed ({error:#}), \
+                        so its raw content is shown instead.\n\n{}",
+                        String::from_utf8_lossy(&body)
+                    ));
+                }
+            }
+        }
+
         let Some(content_type) = response.headers().get("content-type") else {
             bail!("missing Content-Type header");
         };
@@ -116,7 +180,7 @@ impl Tool for FetchTool {
         "fetch".to_string()
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         true
     }
 
@@ -145,6 +209,7 @@ impl Tool for FetchTool {
         _messages: &[LanguageModelRequestMessage],
         _project: Entity<Project>,
         _action_log: Entity<ActionLog>,
+        _cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>> {
         let input = match serde_json::from_value::<FetchToolInput>(input) {
@@ -168,3 +233,85 @@ impl Tool for FetchTool {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_compression::futures::bufread::GzipEncoder;
+    use gpui::TestAppContext;
+    use http_client::{FakeHttpClient, Response};
+
+    async fn gzip(content: &str) -> Vec<u8> {
+        let mut compressed = Vec::new();
+        GzipEncoder::new(BufReader::new(content.as_bytes()))
+            .read_to_end(&mut compressed)
+            .await
+            .unwrap();
+        compressed
+    }
+
+    fn client_responding_with(
+        status: u16,
+        content_type: &'static str,
+        content_encoding: &'static str,
+        body: Vec<u8>,
+    ) -> Arc<HttpClientWithUrl> {
+        FakeHttpClient::create(move |_| {
+            let body = body.clone();
+            async move {
+                Ok(Response::builder()
+                    .status(status)
+                    .header("content-type", content_type)
+                    .header("content-encoding", content_encoding)
+                    .body(body.into())
+                    .unwrap())
+            }
+        })
+    }
+
+    #[gpui::test]
+    async fn test_fetch_gzipped_response(_cx: &mut TestAppContext) {
+        let body = gzip("Hello from a compressed page").await;
+        let http_client = client_responding_with(200, "text/plain", "gzip", body);
+
+        let text = FetchTool::build_message(http_client, "https://example.com")
+            .await
+            .unwrap();
+        assert_eq!(text, "Hello from a compressed page");
+    }
+
+    #[gpui::test]
+    async fn test_fetch_undecodable_response(_cx: &mut TestAppContext) {
+        let http_client =
+            client_responding_with(200, "text/plain", "gzip", b"not actually gzipped".to_vec());
+
+        let text = FetchTool::build_message(http_client, "https://example.com")
+            .await
+            .unwrap();
+        assert!(text.starts_with("Note: the response could not be decompressed"));
+        assert!(text.ends_with("not actually gzipped"));
+    }
+
+    #[gpui::test]
+    async fn test_fetch_client_error_is_not_decoded(_cx: &mut TestAppContext) {
+        let http_client = client_responding_with(404, "text/plain", "gzip", b"not found".to_vec());
+
+        let error = FetchTool::build_message(http_client, "https://example.com")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().starts_with("status error 404"));
+    }
+
+    #[test]
+    fn test_parse_content_encoding() {
+        assert_eq!(
+            ContentEncoding::parse_header("gzip, br").unwrap(),
+            vec![ContentEncoding::Brotli, ContentEncoding::Gzip]
+        );
+        assert_eq!(
+            ContentEncoding::parse_header("identity").unwrap(),
+            Vec::new()
+        );
+        assert!(ContentEncoding::parse_header("zstd").is_err());
+    }
+}
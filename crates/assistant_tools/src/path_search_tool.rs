@@ -1,6 +1,6 @@
 use crate::schema::json_schema_for;
 use anyhow::{Result, anyhow};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{ActionLog, CancellationToken, Tool};
 use gpui::{App, AppContext, Entity, Task};
 use language_model::{LanguageModelRequestMessage, LanguageModelToolSchemaFormat};
 use project::Project;
@@ -34,6 +34,9 @@ pub struct PathSearchToolInput {
 
 const RESULTS_PER_PAGE: usize = 50;
 
+/// How many worktree entries to match between checks for cancellation.
+const CANCELLATION_CHECK_INTERVAL: usize = 1024;
+
 pub struct PathSearchTool;
 
 impl Tool for PathSearchTool {
@@ -41,7 +44,7 @@ impl Tool for PathSearchTool {
         "path_search".into()
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         false
     }
 
@@ -70,6 +73,7 @@ impl Tool for PathSearchTool {
         _messages: &[LanguageModelRequestMessage],
         project: Entity<Project>,
         _action_log: Entity<ActionLog>,
+        cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>> {
         let (offset, glob) = match serde_json::from_value::<PathSearchToolInput>(input) {
@@ -97,7 +101,10 @@ impl Tool for PathSearchTool {
                 let root_name = worktree.root_name();
 
                 // Don't consider ignored entries.
-                for entry in worktree.entries(false, 0) {
+                for (ix, entry) in worktree.entries(false, 0).enumerate() {
+                    if ix % CANCELLATION_CHECK_INTERVAL == 0 {
+                        cancellation.check()?;
+                    }
                     if path_matcher.is_match(&entry.path) {
                         matches.push(
                             PathBuf::from(root_name)
@@ -3,9 +3,8 @@ use std::sync::Arc;
 
 use crate::schema::json_schema_for;
 use anyhow::{Result, anyhow};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{ActionLog, CancellationToken, Tool};
 use gpui::{App, Entity, Task};
-use itertools::Itertools;
 use language_model::{LanguageModelRequestMessage, LanguageModelToolSchemaFormat};
 use project::Project;
 use schemars::JsonSchema;
@@ -47,7 +46,7 @@ impl Tool for ReadFileTool {
         "read_file".into()
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         false
     }
 
@@ -79,6 +78,7 @@ impl Tool for ReadFileTool {
         _messages: &[LanguageModelRequestMessage],
         project: Entity<Project>,
         action_log: Entity<ActionLog>,
+        _cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>> {
         let input = match serde_json::from_value::<ReadFileToolInput>(input) {
@@ -101,19 +101,7 @@ impl Tool for ReadFileTool {
                 .await?;
 
             let result = buffer.read_with(cx, |buffer, _cx| {
-                let text = buffer.text();
-                if input.start_line.is_some() || input.end_line.is_some() {
-                    let start = input.start_line.unwrap_or(1);
-                    let lines = text.split('\n').skip(start - 1);
-                    if let Some(end) = input.end_line {
-                        let count = end.saturating_sub(start);
-                        Itertools::intersperse(lines.take(count), "\n").collect()
-                    } else {
-                        Itertools::intersperse(lines, "\n").collect()
-                    }
-                } else {
-                    text
-                }
+                read_lines(&buffer.text(), input.start_line, input.end_line)
             })?;
 
             action_log.update(cx, |log, cx| {
@@ -124,3 +112,136 @@ impl Tool for ReadFileTool {
         })
     }
 }
+
+/// The most bytes of the file returned to the model at once.
+const MAX_OUTPUT_BYTES: usize = 64 * 1024;
+
+/// Returns the given 1-based, inclusive range of lines of the text, prefixed with their line
+/// numbers, or all of it as is when no range is given. A range reaching past the end of the
+/// text is clamped to its last line, with a note saying so, and a start after the end reads
+/// the start line only.
+fn read_lines(text: &str, start_line: Option<usize>, end_line: Option<usize>) -> String {
+    if start_line.is_none() && end_line.is_none() {
+        return truncate_output(text.to_string());
+    }
+
+    let lines = text.lines().collect::<Vec<_>>();
+    let requested_start = start_line.unwrap_or(1).max(1);
+    let requested_end = end_line.unwrap_or(lines.len()).max(requested_start);
+    if requested_end <= lines.len() {
+        return truncate_output(number_lines(
+            &lines[requested_start - 1..requested_end],
+            requested_start,
+        ));
+    }
+
+    let requested = if requested_start == requested_end {
+        format!("line {requested_start}")
+    } else {
+        format!("lines {requested_start}-{requested_end}")
+    };
+    if lines.is_empty() {
+        return format!("(requested {requested}, file is empty)");
+    }
+    let start = requested_start.min(lines.len());
+    let content = truncate_output(number_lines(&lines[start - 1..], start));
+    let line_count = match lines.len() {
+        1 => "1 line".to_string(),
+        line_count => format!("{line_count} lines"),
+    };
+    format!("{content}\n\n(requested {requested}, file has {line_count})")
+}
+
+fn number_lines(lines: &[&str], first_line_number: usize) -> String {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(ix, line)| format!("{}: {line}", first_line_number + ix))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Cuts the output at the last line that fits in [`MAX_OUTPUT_BYTES`], saying so.
+fn truncate_output(mut output: String) -> String {
+    if output.len() <= MAX_OUTPUT_BYTES {
+        return output;
+    }
+    let total_len = output.len();
+    let mut end = MAX_OUTPUT_BYTES;
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    if let Some(newline_ix) = output[..end].rfind('\n') {
+        end = newline_ix;
+    }
+    output.truncate(end);
+    output.push_str(&format!(
+        "\n\n(output truncated after {end} of {total_len} bytes, \
+        read a smaller range of lines to see the rest)"
+    ));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "one\ntwo\nthree\nfour";
+
+    #[test]
+    fn test_read_lines_in_range() {
+        assert_eq!(read_lines(TEXT, None, None), TEXT);
+        assert_eq!(read_lines(TEXT, Some(2), Some(3)), "2: two\n3: three");
+        assert_eq!(read_lines(TEXT, Some(3), None), "3: three\n4: four");
+        assert_eq!(read_lines(TEXT, None, Some(1)), "1: one");
+        assert_eq!(read_lines(TEXT, Some(0), Some(1)), "1: one");
+        assert_eq!(read_lines(TEXT, Some(3), Some(2)), "3: three");
+    }
+
+    #[test]
+    fn test_read_lines_past_end() {
+        assert_eq!(
+            read_lines(TEXT, Some(3), Some(500)),
+            "3: three\n4: four\n\n(requested lines 3-500, file has 4 lines)"
+        );
+        assert_eq!(
+            read_lines(TEXT, Some(10), Some(20)),
+            "4: four\n\n(requested lines 10-20, file has 4 lines)"
+        );
+        assert_eq!(
+            read_lines(TEXT, Some(7), None),
+            "4: four\n\n(requested line 7, file has 4 lines)"
+        );
+        assert_eq!(
+            read_lines("only", Some(1), Some(2)),
+            "1: only\n\n(requested lines 1-2, file has 1 line)"
+        );
+    }
+
+    #[test]
+    fn test_read_lines_empty_file() {
+        assert_eq!(read_lines("", None, None), "");
+        assert_eq!(
+            read_lines("", Some(1), Some(500)),
+            "(requested lines 1-500, file is empty)"
+        );
+        assert_eq!(
+            read_lines("", Some(1), None),
+            "(requested line 1, file is empty)"
+        );
+    }
+
+    #[test]
+    fn test_read_lines_truncates_large_output() {
+        let text = (1..=20_000)
+            .map(|ix| format!("line {ix}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let output = read_lines(&text, Some(1), None);
+        let (content, note) = output.split_once("\n\n").unwrap();
+        assert!(content.len() <= MAX_OUTPUT_BYTES);
+        assert!(content.starts_with("1: line 1\n2: line 2\n"));
+        assert!(content.ends_with(&format!("{}", content.lines().count())));
+        assert!(note.starts_with("(output truncated after"));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::schema::json_schema_for;
 use anyhow::{Result, anyhow};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{ActionLog, CancellationToken, Tool};
 use futures::StreamExt;
 use gpui::{App, Entity, Task};
 use language::OffsetRangeExt;
@@ -44,7 +44,7 @@ impl Tool for RegexSearchTool {
         "regex_search".into()
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         false
     }
 
@@ -82,6 +82,7 @@ impl Tool for RegexSearchTool {
         _messages: &[LanguageModelRequestMessage],
         project: Entity<Project>,
         _action_log: Entity<ActionLog>,
+        cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>> {
         const CONTEXT_LINES: u32 = 2;
@@ -115,6 +116,7 @@ impl Tool for RegexSearchTool {
             let mut has_more_matches = false;
 
             while let Some(SearchResult::Buffer { buffer, ranges }) = results.next().await {
+                cancellation.check()?;
                 if ranges.is_empty() {
                     continue;
                 }
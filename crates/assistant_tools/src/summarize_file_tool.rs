@@ -0,0 +1,313 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::schema::json_schema_for;
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, CancellationToken, Tool};
+use gpui::{App, Entity, Task};
+use language::ToOffset as _;
+use language_model::{LanguageModelRequestMessage, LanguageModelToolSchemaFormat};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ui::IconName;
+use util::markdown::MarkdownString;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SummarizeFileToolInput {
+    /// The relative path of the file to summarize.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - directory1
+    /// - directory2
+    ///
+    /// If you wanna access `file.txt` in `directory1`, you should use the path `directory1/file.txt`.
+    /// If you wanna access `file.txt` in `directory2`, you should use the path `directory2/file.txt`.
+    /// </example>
+    pub path: Arc<Path>,
+
+    /// How many lines to include from the start and from the end of the file.
+    ///
+    /// Defaults to 10.
+    #[serde(default = "default_context_lines")]
+    pub context_lines: usize,
+}
+
+fn default_context_lines() -> usize {
+    10
+}
+
+pub struct SummarizeFileTool;
+
+impl Tool for SummarizeFileTool {
+    fn name(&self) -> String {
+        "summarize_file".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./summarize_file_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::ListTree
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> serde_json::Value {
+        json_schema_for::<SummarizeFileToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<SummarizeFileToolInput>(input.clone()) {
+            Ok(input) => {
+                let path = MarkdownString::inline_code(&input.path.display().to_string());
+                format!("Summarize file {path}")
+            }
+            Err(_) => "Summarize file".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _messages: &[LanguageModelRequestMessage],
+        project: Entity<Project>,
+        action_log: Entity<ActionLog>,
+        _cancellation: CancellationToken,
+        cx: &mut App,
+    ) -> Task<Result<String>> {
+        let input = match serde_json::from_value::<SummarizeFileToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))),
+        };
+
+        let Some(project_path) = project.read(cx).find_project_path(&input.path, cx) else {
+            return Task::ready(Err(anyhow!(
+                "Path {} not found in project",
+                &input.path.display()
+            )));
+        };
+
+        cx.spawn(async move |cx| {
+            let buffer = cx
+                .update(|cx| {
+                    project.update(cx, |project, cx| project.open_buffer(project_path, cx))
+                })?
+                .await?;
+
+            let summary = buffer.read_with(cx, |buffer, _cx| {
+                let snapshot = buffer.snapshot();
+                let sections = snapshot.outline(None).map(|outline| {
+                    outline
+                        .items
+                        .into_iter()
+                        .map(|item| {
+                            let range = item.range.start.to_offset(&snapshot)
+                                ..item.range.end.to_offset(&snapshot);
+                            OutlineSection {
+                                depth: item.depth,
+                                text: item.text,
+                                start_row: snapshot.offset_to_point(range.start).row,
+                                end_row: snapshot.offset_to_point(range.end).row,
+                                len: range.len(),
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                });
+                summarize(&input.path, &snapshot.text(), sections, input.context_lines)
+            })?;
+
+            action_log.update(cx, |log, cx| {
+                log.buffer_read(buffer, cx);
+            })?;
+
+            anyhow::Ok(summary)
+        })
+    }
+}
+
+struct OutlineSection {
+    depth: usize,
+    text: String,
+    start_row: u32,
+    end_row: u32,
+    /// The size of the section in bytes.
+    len: usize,
+}
+
+fn summarize(
+    path: &Path,
+    text: &str,
+    sections: Option<Vec<OutlineSection>>,
+    context_lines: usize,
+) -> String {
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut summary = String::new();
+    writeln!(
+        summary,
+        "# {} ({} lines, {} bytes)\n",
+        path.display(),
+        lines.len(),
+        text.len()
+    )
+    .ok();
+
+    match sections.filter(|sections| !sections.is_empty()) {
+        Some(sections) => {
+            summary.push_str("## Outline\n\n");
+            for section in sections {
+                for _ in 0..section.depth {
+                    summary.push_str("  ");
+                }
+                // Convert to 1-based line numbers for display
+                let start_line = section.start_row + 1;
+                let end_line = section.end_row + 1;
+                if section.depth == 0 {
+                    writeln!(
+                        summary,
+                        "{} [L{}-{}, {} lines, {} bytes]",
+                        section.text,
+                        start_line,
+                        end_line,
+                        end_line - start_line + 1,
+                        section.len
+                    )
+                    .ok();
+                } else if start_line == end_line {
+                    writeln!(summary, "{} [L{}]", section.text, start_line).ok();
+                } else {
+                    writeln!(summary, "{} [L{}-{}]", section.text, start_line, end_line).ok();
+                }
+            }
+        }
+        None => summary.push_str("No outline information available for this file.\n"),
+    }
+
+    if lines.len() <= context_lines * 2 {
+        summary.push_str("\n## Content\n\n");
+        write_lines(&mut summary, &lines, 0);
+    } else {
+        writeln!(summary, "\n## First {context_lines} lines\n").ok();
+        write_lines(&mut summary, &lines[..context_lines], 0);
+        writeln!(summary, "\n## Last {context_lines} lines\n").ok();
+        let start = lines.len() - context_lines;
+        write_lines(&mut summary, &lines[start..], start);
+    }
+
+    summary
+}
+
+fn write_lines(output: &mut String, lines: &[&str], first_row: usize) {
+    for (ix, line) in lines.iter().enumerate() {
+        writeln!(output, "{}: {}", first_row + ix + 1, line).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_with_outline() {
+        let text = (1..=30)
+            .map(|ix| format!("line {ix}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let sections = vec![
+            OutlineSection {
+                depth: 0,
+                text: "struct Foo".into(),
+                start_row: 0,
+                end_row: 9,
+                len: 120,
+            },
+            OutlineSection {
+                depth: 1,
+                text: "field".into(),
+                start_row: 2,
+                end_row: 2,
+                len: 10,
+            },
+            OutlineSection {
+                depth: 0,
+                text: "fn bar".into(),
+                start_row: 12,
+                end_row: 29,
+                len: 200,
+            },
+        ];
+
+        assert_eq!(
+            summarize(Path::new("root/foo.rs"), &text, Some(sections), 2),
+            concat!(
+                "# root/foo.rs (30 lines, 230 bytes)\n",
+                "\n",
+                "## Outline\n",
+                "\n",
+                "struct Foo [L1-10, 10 lines, 120 bytes]\n",
+                "  field [L3]\n",
+                "fn bar [L13-30, 18 lines, 200 bytes]\n",
+                "\n",
+                "## First 2 lines\n",
+                "\n",
+                "1: line 1\n",
+                "2: line 2\n",
+                "\n",
+                "## Last 2 lines\n",
+                "\n",
+                "29: line 29\n",
+                "30: line 30\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_summarize_without_outline() {
+        assert_eq!(
+            summarize(Path::new("root/notes.txt"), "a\nb\nc\nd\ne\n", None, 2),
+            concat!(
+                "# root/notes.txt (5 lines, 10 bytes)\n",
+                "\n",
+                "No outline information available for this file.\n",
+                "\n",
+                "## First 2 lines\n",
+                "\n",
+                "1: a\n",
+                "2: b\n",
+                "\n",
+                "## Last 2 lines\n",
+                "\n",
+                "4: d\n",
+                "5: e\n",
+            )
+        );
+        assert_eq!(
+            summarize(
+                Path::new("root/notes.txt"),
+                "a\nb\nc\n",
+                Some(Vec::new()),
+                10
+            ),
+            concat!(
+                "# root/notes.txt (3 lines, 6 bytes)\n",
+                "\n",
+                "No outline information available for this file.\n",
+                "\n",
+                "## Content\n",
+                "\n",
+                "1: a\n",
+                "2: b\n",
+                "3: c\n",
+            )
+        );
+    }
+}
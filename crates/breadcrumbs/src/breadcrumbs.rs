@@ -67,11 +67,12 @@ impl Render for Breadcrumbs {
                     text: "⋯".into(),
                     highlights: None,
                     font: None,
+                    on_click: None,
                 }),
             );
         }
 
-        let highlighted_segments = segments.into_iter().map(|segment| {
+        let highlighted_segments = segments.into_iter().enumerate().map(|(ix, segment)| {
             let mut text_style = window.text_style();
             if let Some(font) = segment.font {
                 text_style.font_family = font.family;
@@ -81,9 +82,16 @@ impl Render for Breadcrumbs {
             }
             text_style.color = Color::Muted.color(cx);
 
-            StyledText::new(segment.text.replace('\n', "⏎"))
-                .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
-                .into_any()
+            let text = StyledText::new(segment.text.replace('\n', "⏎"))
+                .with_default_highlights(&text_style, segment.highlights.unwrap_or_default());
+            match segment.on_click {
+                Some(on_click) => ButtonLike::new(("breadcrumb-segment", ix))
+                    .child(text)
+                    .style(ButtonStyle::Transparent)
+                    .on_click(move |_, window, cx| on_click(window, cx))
+                    .into_any_element(),
+                None => text.into_any(),
+            }
         });
         let breadcrumbs = Itertools::intersperse_with(highlighted_segments, || {
             Label::new("›").color(Color::Placeholder).into_any_element()
@@ -1,6 +1,7 @@
 #[cfg(any(test, feature = "test-support"))]
 pub mod test;
 
+mod proxy;
 mod socks;
 pub mod telemetry;
 pub mod user;
@@ -24,13 +25,13 @@ use gpui::{App, AppContext as _, AsyncApp, Entity, Global, Task, WeakEntity, act
 use http_client::{AsyncBody, HttpClient, HttpClientWithUrl};
 use parking_lot::RwLock;
 use postage::watch;
+use proxy::{connect_proxy_stream, read_no_proxy_from_env};
 use rand::prelude::*;
 use release_channel::{AppVersion, ReleaseChannel};
 use rpc::proto::{AnyTypedEnvelope, EnvelopedMessage, PeerId, RequestMessage};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
-use socks::connect_socks_proxy_stream;
 use std::pin::Pin;
 use std::{
     any::TypeId,
@@ -109,11 +110,14 @@ impl Settings for ClientSettings {
 #[derive(Default, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProxySettingsContent {
     proxy: Option<String>,
+    no_proxy: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Default)]
 pub struct ProxySettings {
     pub proxy: Option<String>,
+    /// Hosts to connect to without the proxy. Falls back to the `NO_PROXY` environment variable.
+    pub no_proxy: Option<Vec<String>>,
 }
 
 impl Settings for ProxySettings {
@@ -128,6 +132,11 @@ impl Settings for ProxySettings {
                 .or(sources.server)
                 .and_then(|value| value.proxy.clone())
                 .or(sources.default.proxy.clone()),
+            no_proxy: sources
+                .user
+                .or(sources.server)
+                .and_then(|value| value.no_proxy.clone())
+                .or(sources.default.no_proxy.clone()),
         })
     }
 }
@@ -1082,6 +1091,9 @@ impl Client {
 
         let http = self.http.clone();
         let proxy = http.proxy().cloned();
+        let no_proxy = ProxySettings::try_read_global(cx, |settings| settings.no_proxy.clone())
+            .flatten()
+            .unwrap_or_else(read_no_proxy_from_env);
         let credentials = credentials.clone();
         let rpc_url = self.rpc_url(http, release_channel);
         let system_id = self.telemetry.system_id();
@@ -1105,7 +1117,7 @@ impl Client {
                 .host_str()
                 .zip(rpc_url.port_or_known_default())
                 .ok_or_else(|| anyhow!("missing host in rpc url"))?;
-            let stream = connect_socks_proxy_stream(proxy.as_ref(), rpc_host).await?;
+            let stream = connect_proxy_stream(proxy.as_ref(), &no_proxy, rpc_host).await?;
 
             log::info!("connected to rpc endpoint {}", rpc_url);
 
@@ -321,6 +321,8 @@ impl Server {
             .add_request_handler(
                 forward_read_only_project_request::<proto::LspExtSwitchSourceHeader>,
             )
+            .add_request_handler(forward_read_only_project_request::<proto::LspExtSyntaxTree>)
+            .add_request_handler(forward_read_only_project_request::<proto::LspExtParentModule>)
             .add_request_handler(
                 forward_read_only_project_request::<proto::LanguageServerIdForName>,
             )
@@ -1962,6 +1962,8 @@ async fn test_git_blame_is_forwarded(cx_a: &mut TestAppContext, cx_b: &mut TestA
         delay_ms: None,
         min_column: None,
         show_commit_summary: false,
+        show_commit_age: false,
+        commit_age_max_days: None,
     });
     cx_a.update(|cx| {
         SettingsStore::update_global(cx, |store, cx| {
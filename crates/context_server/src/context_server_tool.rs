@@ -1,7 +1,8 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Result, anyhow, bail};
-use assistant_tool::{ActionLog, Tool, ToolSource};
+use assistant_tool::{ActionLog, CancellationToken, Tool, ToolSource};
 use gpui::{App, Entity, Task};
 use icons::IconName;
 use language_model::{LanguageModelRequestMessage, LanguageModelToolSchemaFormat};
@@ -14,6 +15,7 @@ pub struct ContextServerTool {
     server_manager: Entity<ContextServerManager>,
     server_id: Arc<str>,
     tool: types::Tool,
+    timeout: Option<Duration>,
 }
 
 impl ContextServerTool {
@@ -21,11 +23,13 @@ impl ContextServerTool {
         server_manager: Entity<ContextServerManager>,
         server_id: impl Into<Arc<str>>,
         tool: types::Tool,
+        timeout: Option<Duration>,
     ) -> Self {
         Self {
             server_manager,
             server_id: server_id.into(),
             tool,
+            timeout,
         }
     }
 }
@@ -49,7 +53,7 @@ impl Tool for ContextServerTool {
         }
     }
 
-    fn needs_confirmation(&self) -> bool {
+    fn needs_confirmation(&self, _: &serde_json::Value) -> bool {
         true
     }
 
@@ -65,6 +69,10 @@ impl Tool for ContextServerTool {
         }
     }
 
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     fn ui_text(&self, _input: &serde_json::Value) -> String {
         format!("Run MCP tool `{}`", self.tool.name)
     }
@@ -75,6 +83,7 @@ impl Tool for ContextServerTool {
         _messages: &[LanguageModelRequestMessage],
         _project: Entity<Project>,
         _action_log: Entity<ActionLog>,
+        _cancellation: CancellationToken,
         cx: &mut App,
     ) -> Task<Result<String>> {
         if let Some(server) = self.server_manager.read(cx).get_server(&self.server_id) {
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use collections::HashMap;
 use gpui::App;
@@ -45,6 +46,20 @@ pub struct ContextServerSettings {
     /// Settings for context servers used in the Assistant.
     #[serde(default)]
     pub context_servers: HashMap<Arc<str>, ServerConfig>,
+    /// How many seconds a context server tool may run before it is canceled, or `None` to
+    /// let it run indefinitely.
+    ///
+    /// Default: 300
+    #[serde(default)]
+    pub context_server_tool_timeout_secs: Option<u64>,
+}
+
+impl ContextServerSettings {
+    /// Returns how long a context server tool may run before it is canceled.
+    pub fn tool_timeout(&self) -> Option<Duration> {
+        self.context_server_tool_timeout_secs
+            .map(Duration::from_secs)
+    }
 }
 
 impl Settings for ContextServerSettings {
@@ -294,6 +294,7 @@ actions!(
         DuplicateLineDown,
         DuplicateLineUp,
         DuplicateSelection,
+        ExpandMacroInPlace,
         ExpandMacroRecursively,
         FindAllReferences,
         Fold,
@@ -355,6 +356,7 @@ actions!(
         OpenContextMenu,
         OpenExcerpts,
         OpenExcerptsSplit,
+        OpenParentModule,
         OpenProposedChangesEditor,
         OpenDocs,
         OpenPermalinkToLine,
@@ -441,6 +443,7 @@ actions!(
         UnfoldRecursive,
         UniqueLinesCaseInsensitive,
         UniqueLinesCaseSensitive,
+        ViewSyntaxTree,
     ]
 );
 
@@ -30,6 +30,7 @@ pub mod items;
 mod jsx_tag_auto_close;
 mod linked_editing_ranges;
 mod lsp_ext;
+mod macro_expansion;
 mod mouse_context_menu;
 pub mod movement;
 mod persistence;
@@ -743,6 +744,7 @@ pub struct Editor {
     remote_id: Option<ViewId>,
     hover_state: HoverState,
     pending_mouse_down: Option<Rc<RefCell<Option<MouseDownEvent>>>>,
+    pending_selection_drag: Option<PendingSelectionDrag>,
     gutter_hovered: bool,
     hovered_link_state: Option<HoveredLinkState>,
     edit_prediction_provider: Option<RegisteredInlineCompletionProvider>,
@@ -1156,6 +1158,13 @@ pub(crate) struct FocusedBlock {
     focus_handle: WeakFocusHandle,
 }
 
+/// A mouse down inside a selection, which drags the selected text if the mouse moves
+/// before being released, or places the cursor otherwise.
+struct PendingSelectionDrag {
+    mouse_down_position: gpui::Point<Pixels>,
+    position: DisplayPoint,
+}
+
 #[derive(Clone)]
 enum JumpData {
     MultiBufferRow {
@@ -1516,6 +1525,7 @@ impl Editor {
             remote_id: None,
             hover_state: Default::default(),
             pending_mouse_down: None,
+            pending_selection_drag: None,
             hovered_link_state: Default::default(),
             edit_prediction_provider: None,
             active_inline_completion: None,
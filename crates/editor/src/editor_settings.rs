@@ -29,6 +29,7 @@ pub struct EditorSettings {
     pub redact_private_values: bool,
     pub expand_excerpt_lines: u32,
     pub middle_click_paste: bool,
+    pub drag_and_drop_selection: bool,
     #[serde(default)]
     pub double_click_in_multibuffer: DoubleClickInMultibuffer,
     pub search_wrap: bool,
@@ -338,6 +339,13 @@ pub struct EditorSettingsContent {
     /// Default: true
     pub middle_click_paste: Option<bool>,
 
+    /// Whether selected text can be dragged out of the editor, so that it can be
+    /// dropped onto a terminal. When disabled, dragging inside a selection starts
+    /// a new selection.
+    ///
+    /// Default: false
+    pub drag_and_drop_selection: Option<bool>,
+
     /// What to do when multibuffer is double clicked in some of its excerpts
     /// (parts of singleton buffers).
     ///
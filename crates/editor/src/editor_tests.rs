@@ -18650,6 +18650,64 @@ println!("5");
     });
 }
 
+#[gpui::test]
+async fn test_dragging_selected_text(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    let position = cx.pixel_position("one twˇo three");
+
+    // By default, dragging inside a selection starts a new selection.
+    cx.set_state("one «twoˇ» three");
+    cx.simulate_mouse_down(position, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(
+        position + point(px(20.), px(0.)),
+        MouseButton::Left,
+        Modifiers::none(),
+    );
+    assert!(!cx.update(|_, cx| cx.has_active_drag()));
+    cx.simulate_mouse_up(
+        position + point(px(20.), px(0.)),
+        MouseButton::Left,
+        Modifiers::none(),
+    );
+    assert_eq!(
+        cx.update_editor(|editor, _, cx| editor.selections.newest::<usize>(cx).tail()),
+        "one tw".len()
+    );
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<EditorSettings>(cx, |settings| {
+                settings.drag_and_drop_selection = Some(true);
+            });
+        });
+    });
+
+    // Releasing the mouse inside a selection without moving it places the cursor.
+    cx.set_state("one «twoˇ» three");
+    cx.simulate_mouse_down(position, MouseButton::Left, Modifiers::none());
+    cx.assert_editor_state("one «twoˇ» three");
+    cx.simulate_mouse_up(position, MouseButton::Left, Modifiers::none());
+    assert!(cx.update_editor(|editor, _, cx| editor.selections.newest::<usize>(cx).is_empty()));
+    assert!(!cx.update(|_, cx| cx.has_active_drag()));
+
+    // Moving it first drags the selected text instead, keeping the selection.
+    cx.set_state("one «twoˇ» three");
+    cx.simulate_mouse_down(position, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(
+        position + point(px(20.), px(0.)),
+        MouseButton::Left,
+        Modifiers::none(),
+    );
+    assert!(cx.update(|_, cx| cx.has_active_drag()));
+    cx.simulate_mouse_up(
+        position + point(px(20.), px(0.)),
+        MouseButton::Left,
+        Modifiers::none(),
+    );
+    cx.assert_editor_state("one «twoˇ» three");
+}
+
 fn empty_range(row: usize, column: usize) -> Range<DisplayPoint> {
     let point = DisplayPoint::new(DisplayRow(row as u32), column as u32);
     point..point
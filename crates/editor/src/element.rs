@@ -6,8 +6,8 @@ use crate::{
     GutterDimensions, HalfPageDown, HalfPageUp, HandleInput, HoveredCursor, InlayHintRefreshReason,
     InlineCompletion, JumpData, LineDown, LineHighlight, LineUp, MAX_LINE_LEN,
     MIN_LINE_NUMBER_DIGITS, MULTI_BUFFER_EXCERPT_HEADER_HEIGHT, OpenExcerpts, PageDown, PageUp,
-    Point, RowExt, RowRangeExt, SelectPhase, SelectedTextHighlight, Selection, SoftWrap,
-    StickyHeaderExcerpt, ToPoint, ToggleFold,
+    PendingSelectionDrag, Point, RowExt, RowRangeExt, SelectPhase, SelectedTextHighlight,
+    Selection, SoftWrap, StickyHeaderExcerpt, ToPoint, ToggleFold,
     code_context_menus::{CodeActionsMenu, MENU_ASIDE_MAX_WIDTH, MENU_ASIDE_MIN_WIDTH, MENU_GAP},
     display_map::{
         Block, BlockContext, BlockStyle, DisplaySnapshot, HighlightedChunk, ToDisplayPoint,
@@ -35,10 +35,10 @@ use gpui::{
     Action, Along, AnyElement, App, AvailableSpace, Axis as ScrollbarAxis, BorderStyle, Bounds,
     ClickEvent, ContentMask, Context, Corner, Corners, CursorStyle, DispatchPhase, Edges, Element,
     ElementInputHandler, Entity, Focusable as _, FontId, GlobalElementId, Hitbox, Hsla,
-    InteractiveElement, IntoElement, Keystroke, Length, ModifiersChangedEvent, MouseButton,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta,
-    ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement, Style, Styled,
-    TextRun, TextStyleRefinement, WeakEntity, Window, anchored, deferred, div, fill,
+    InteractiveElement, IntoElement, Keystroke, Length, Modifiers, ModifiersChangedEvent,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels,
+    ScrollDelta, ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement,
+    Style, Styled, TextRun, TextStyleRefinement, WeakEntity, Window, anchored, deferred, div, fill,
     linear_color_stop, linear_gradient, outline, point, px, quad, relative, size, solid_background,
     transparent_black,
 };
@@ -78,9 +78,11 @@ use theme::{ActiveTheme, Appearance, BufferLineHeight, PlayerColor};
 use ui::{ButtonLike, KeyBinding, POPOVER_Y_PADDING, Tooltip, h_flex, prelude::*};
 use unicode_segmentation::UnicodeSegmentation;
 use util::{RangeExt, ResultExt, debug_panic};
-use workspace::{Workspace, item::Item, notifications::NotifyTaskExt};
+use workspace::{DraggedText, Workspace, item::Item, notifications::NotifyTaskExt};
 
 const INLINE_BLAME_PADDING_EM_WIDTHS: f32 = 7.;
+/// How far the mouse has to move after pressing it inside a selection to drag the selected text.
+const SELECTION_DRAG_THRESHOLD: f64 = 2.;
 
 /// Determines what kinds of highlights should be applied to a lines background.
 #[derive(Clone, Copy, Default)]
@@ -185,6 +187,7 @@ impl EditorElement {
         });
 
         crate::rust_analyzer_ext::apply_related_actions(editor, window, cx);
+        crate::macro_expansion::apply_related_actions(editor, window, cx);
         crate::clangd_ext::apply_related_actions(editor, window, cx);
 
         register_action(editor, window, Editor::open_context_menu);
@@ -641,6 +644,21 @@ impl EditorElement {
 
         let point_for_position = position_map.point_for_position(event.position);
         let position = point_for_position.previous_valid;
+        if click_count == 1
+            && modifiers == Modifiers::none()
+            && editor.mode == EditorMode::Full
+            && EditorSettings::get_global(cx).drag_and_drop_selection
+            && Self::selected_text_at(editor, position, &position_map.snapshot, cx).is_some()
+        {
+            // Keep the selection, so that its text can be dragged.
+            editor.pending_selection_drag = Some(PendingSelectionDrag {
+                mouse_down_position: event.position,
+                position,
+            });
+            cx.stop_propagation();
+            return;
+        }
+
         if modifiers == COLUMNAR_SELECTION_MODIFIERS {
             editor.select(
                 SelectPhase::BeginColumnar {
@@ -710,6 +728,27 @@ impl EditorElement {
         }
     }
 
+    /// Returns the text of the non-empty selection containing the given position, if any.
+    fn selected_text_at(
+        editor: &Editor,
+        position: DisplayPoint,
+        snapshot: &EditorSnapshot,
+        cx: &mut App,
+    ) -> Option<String> {
+        let offset = position.to_offset(&snapshot.display_snapshot, Bias::Left);
+        editor
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .find(|selection| !selection.is_empty() && selection.range().contains(&offset))
+            .map(|selection| {
+                snapshot
+                    .buffer_snapshot
+                    .text_for_range(selection.range())
+                    .collect()
+            })
+    }
+
     fn mouse_right_down(
         editor: &mut Editor,
         event: &MouseDownEvent,
@@ -781,6 +820,22 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if let Some(selection_drag) = editor.pending_selection_drag.take() {
+            // The mouse was released without dragging the selection, so treat it as a click.
+            editor.select(
+                SelectPhase::Begin {
+                    position: selection_drag.position,
+                    add: false,
+                    click_count: 1,
+                },
+                window,
+                cx,
+            );
+            editor.select(SelectPhase::End, window, cx);
+            cx.stop_propagation();
+            return;
+        }
+
         let text_hitbox = &position_map.text_hitbox;
         let end_selection = editor.has_pending_selection();
         let pending_nonempty_selections = editor.has_pending_nonempty_selection();
@@ -851,6 +906,22 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if let Some(selection_drag) = editor.pending_selection_drag.take() {
+            if (event.position - selection_drag.mouse_down_position).magnitude()
+                <= SELECTION_DRAG_THRESHOLD
+            {
+                editor.pending_selection_drag = Some(selection_drag);
+            } else if let Some(text) =
+                Self::selected_text_at(editor, selection_drag.position, &position_map.snapshot, cx)
+            {
+                let dragged_text = DraggedText { text: text.into() };
+                let drag_view = cx.new(|_| dragged_text.clone());
+                cx.start_drag(dragged_text, drag_view, point(px(0.), px(0.)));
+                window.refresh();
+            }
+            return;
+        }
+
         if !editor.has_pending_selection() {
             return;
         }
@@ -916,12 +916,14 @@ impl Item for Editor {
             text,
             highlights: None,
             font: Some(settings.buffer_font.clone()),
+            on_click: None,
         }];
 
         breadcrumbs.extend(symbols.into_iter().map(|symbol| BreadcrumbText {
             text: symbol.text,
             highlights: Some(symbol.highlight_ranges),
             font: Some(settings.buffer_font.clone()),
+            on_click: None,
         }));
         Some(breadcrumbs)
     }
@@ -0,0 +1,496 @@
+use std::{ops::Range, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use gpui::{App, AppContext as _, Context, Entity, PromptLevel, Task, Window};
+use language::{Buffer, BufferSnapshot, Capability, Language, proto::serialize_anchor};
+use multi_buffer::MultiBuffer;
+use project::lsp_store::{
+    lsp_ext_command::{ExpandMacro, ExpandedMacro},
+    rust_analyzer_ext::RUST_ANALYZER_NAME,
+};
+use rpc::proto;
+use text::{OffsetRangeExt as _, ToOffset as _, ToPointUtf16};
+
+use crate::{
+    Editor, ExpandMacroInPlace, ExpandMacroRecursively, element::register_action,
+    lsp_ext::find_specific_language_server_in_selection,
+};
+
+/// The custom language server request used to expand a macro.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MacroExpansionRequest {
+    /// `rust-analyzer/expandMacro`, sent through [`ExpandMacro`].
+    RustAnalyzer,
+}
+
+/// A language server able to expand the macros of a language.
+struct MacroExpansionProvider {
+    language_name: &'static str,
+    server_name: &'static str,
+    request: MacroExpansionRequest,
+    /// The syntax node kind of a macro invocation, replaced when expanding in place.
+    invocation_kind: &'static str,
+}
+
+impl MacroExpansionProvider {
+    fn matches(&self, language: &Language) -> bool {
+        language.name() == self.language_name.into()
+    }
+}
+
+const MACRO_EXPANSION_PROVIDERS: &[MacroExpansionProvider] = &[MacroExpansionProvider {
+    language_name: "Rust",
+    server_name: RUST_ANALYZER_NAME,
+    request: MacroExpansionRequest::RustAnalyzer,
+    invocation_kind: "macro_invocation",
+}];
+
+pub fn apply_related_actions(editor: &Entity<Editor>, window: &mut Window, cx: &mut App) {
+    if editor
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .all_buffers()
+        .into_iter()
+        .filter_map(|buffer| buffer.read(cx).language())
+        .any(|language| {
+            MACRO_EXPANSION_PROVIDERS
+                .iter()
+                .any(|provider| provider.matches(language))
+        })
+    {
+        register_action(&editor, window, expand_macro_recursively);
+        register_action(&editor, window, expand_macro_in_place);
+    }
+}
+
+/// A macro expanded by a language server, along with where the expansion was requested.
+struct MacroExpansion {
+    trigger_anchor: text::Anchor,
+    language: Arc<Language>,
+    buffer: Entity<Buffer>,
+    expanded: ExpandedMacro,
+    invocation_kind: &'static str,
+}
+
+fn request_macro_expansion(
+    editor: &Editor,
+    cx: &mut Context<Editor>,
+) -> Task<Result<Option<MacroExpansion>>> {
+    let Some(project) = editor.project.clone() else {
+        return Task::ready(Ok(None));
+    };
+
+    let server_lookups = MACRO_EXPANSION_PROVIDERS
+        .iter()
+        .map(|provider| {
+            let lookup = find_specific_language_server_in_selection(
+                editor,
+                cx,
+                |language| provider.matches(language),
+                provider.server_name,
+            );
+            (provider, lookup)
+        })
+        .collect::<Vec<_>>();
+
+    let upstream_client = project.read(cx).lsp_store().read(cx).upstream_client();
+    cx.spawn(async move |_editor, cx| {
+        let mut server_lookup = None;
+        for (provider, lookup) in server_lookups {
+            if let Some(found) = lookup.await {
+                server_lookup = Some((provider, found));
+                break;
+            }
+        }
+        let Some((provider, (trigger_anchor, language, server_to_query, buffer))) = server_lookup
+        else {
+            return Ok(None);
+        };
+
+        let expanded = match provider.request {
+            MacroExpansionRequest::RustAnalyzer => {
+                if let Some((client, project_id)) = upstream_client {
+                    let buffer_id = buffer.update(cx, |buffer, _| buffer.remote_id())?;
+                    let request = proto::LspExtExpandMacro {
+                        project_id,
+                        buffer_id: buffer_id.to_proto(),
+                        position: Some(serialize_anchor(&trigger_anchor.text_anchor)),
+                    };
+                    let response = client
+                        .request(request)
+                        .await
+                        .context("lsp ext expand macro proto request")?;
+                    ExpandedMacro {
+                        name: response.name,
+                        expansion: response.expansion,
+                    }
+                } else {
+                    let buffer_snapshot = buffer.update(cx, |buffer, _| buffer.snapshot())?;
+                    let position = trigger_anchor.text_anchor.to_point_utf16(&buffer_snapshot);
+                    project
+                        .update(cx, |project, cx| {
+                            project.request_lsp(
+                                buffer.clone(),
+                                project::LanguageServerToQuery::Other(server_to_query),
+                                ExpandMacro { position },
+                                cx,
+                            )
+                        })?
+                        .await
+                        .context("expand macro")?
+                }
+            }
+        };
+
+        if expanded.is_empty() {
+            log::info!(
+                "Empty macro expansion for position {:?}",
+                trigger_anchor.text_anchor
+            );
+            return Ok(None);
+        }
+
+        Ok(Some(MacroExpansion {
+            trigger_anchor: trigger_anchor.text_anchor,
+            language,
+            buffer,
+            expanded,
+            invocation_kind: provider.invocation_kind,
+        }))
+    })
+}
+
+pub fn expand_macro_recursively(
+    editor: &mut Editor,
+    _: &ExpandMacroRecursively,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    if editor.selections.count() == 0 {
+        return;
+    }
+    let Some(project) = editor.project.clone() else {
+        return;
+    };
+    let Some(workspace) = editor.workspace() else {
+        return;
+    };
+
+    let macro_expansion = request_macro_expansion(editor, cx);
+    cx.spawn_in(window, async move |_editor, cx| {
+        let Some(MacroExpansion {
+            language, expanded, ..
+        }) = macro_expansion.await?
+        else {
+            return Ok(());
+        };
+
+        let buffer = project
+            .update(cx, |project, cx| project.create_buffer(cx))?
+            .await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            buffer.update(cx, |buffer, cx| {
+                buffer.set_text(expanded.expansion, cx);
+                buffer.set_language(Some(language), cx);
+                buffer.set_capability(Capability::ReadOnly, cx);
+            });
+            let multibuffer =
+                cx.new(|cx| MultiBuffer::singleton(buffer, cx).with_title(expanded.name));
+            workspace.add_item_to_active_pane(
+                Box::new(cx.new(|cx| {
+                    let mut editor = Editor::for_multibuffer(multibuffer, None, window, cx);
+                    editor.set_read_only(true);
+                    editor
+                })),
+                None,
+                true,
+                window,
+                cx,
+            );
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+pub fn expand_macro_in_place(
+    editor: &mut Editor,
+    _: &ExpandMacroInPlace,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    if editor.selections.count() == 0 || editor.read_only(cx) {
+        return;
+    }
+
+    let macro_expansion = request_macro_expansion(editor, cx);
+    cx.spawn_in(window, async move |_editor, cx| {
+        let Some(MacroExpansion {
+            trigger_anchor,
+            buffer,
+            expanded,
+            invocation_kind,
+            ..
+        }) = macro_expansion.await?
+        else {
+            return Ok(());
+        };
+
+        let invocation_range = buffer.read_with(cx, |buffer, _| {
+            if buffer.read_only() {
+                return None;
+            }
+            let snapshot = buffer.snapshot();
+            let range = macro_invocation_range(
+                &snapshot,
+                trigger_anchor.to_offset(&snapshot),
+                invocation_kind,
+            )?;
+            Some(snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end))
+        })?;
+        let Some(invocation_range) = invocation_range else {
+            log::info!("No editable macro invocation at position {trigger_anchor:?}");
+            return Ok(());
+        };
+
+        let answer = cx.update(|window, cx| {
+            window.prompt(
+                PromptLevel::Warning,
+                &format!("Replace {} with its expansion?", expanded.name),
+                Some("This edits the file in place."),
+                &["Replace", "Cancel"],
+                cx,
+            )
+        })?;
+        if answer.await != Ok(0) {
+            return Ok(());
+        }
+
+        buffer.update(cx, |buffer, cx| {
+            let snapshot = buffer.snapshot();
+            let range = invocation_range.to_point(&snapshot);
+            let indent = snapshot.indent_size_for_line(range.start.row);
+            let expansion =
+                indent_expansion(&expanded.expansion, &indent.chars().collect::<String>());
+            buffer.edit([(range, expansion)], None, cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Returns the range of the innermost `invocation_kind` node containing `offset`.
+fn macro_invocation_range(
+    snapshot: &BufferSnapshot,
+    offset: usize,
+    invocation_kind: &str,
+) -> Option<Range<usize>> {
+    snapshot
+        .syntax_layers()
+        .filter_map(|layer| {
+            let mut node = layer.node().descendant_for_byte_range(offset, offset)?;
+            while node.kind() != invocation_kind {
+                node = node.parent()?;
+            }
+            Some(node.byte_range())
+        })
+        .min_by_key(|range| range.len())
+}
+
+/// Indents every line but the first of `expansion`, which replaces text that already follows `indent`.
+fn indent_expansion(expansion: &str, indent: &str) -> String {
+    let mut lines = expansion.lines();
+    let mut indented = lines.next().unwrap_or_default().to_string();
+    for line in lines {
+        indented.push('\n');
+        if !line.is_empty() {
+            indented.push_str(indent);
+            indented.push_str(line);
+        }
+    }
+    indented
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{editor_tests::init_test, test::editor_lsp_test_context::rust_lang};
+    use futures::StreamExt as _;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::FakeLspAdapter;
+    use project::{FakeFs, Project, lsp_store::lsp_ext_command::LspExpandMacro};
+    use serde_json::json;
+    use util::path;
+    use workspace::Workspace;
+
+    #[gpui::test]
+    async fn test_expand_macro_recursively(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/a"),
+            json!({
+                "main.rs": "fn main() {}",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(rust_lang());
+        let mut fake_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                name: RUST_ANALYZER_NAME,
+                ..FakeLspAdapter::default()
+            },
+        );
+        let workspace =
+            cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let worktree_id = workspace
+            .update(cx, |workspace, _window, cx| {
+                workspace.project().update(cx, |project, cx| {
+                    project.worktrees(cx).next().unwrap().read(cx).id()
+                })
+            })
+            .unwrap();
+        let editor = workspace
+            .update(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, "main.rs"), None, true, window, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        let fake_server = fake_servers.next().await.unwrap();
+
+        let mut expand_request =
+            fake_server.set_request_handler::<LspExpandMacro, _, _>(|params, _| async move {
+                assert_eq!(
+                    params.text_document.uri,
+                    lsp::Url::from_file_path(path!("/a/main.rs")).unwrap(),
+                );
+                assert_eq!(params.position, lsp::Position::new(0, 0));
+                Ok(Some(ExpandedMacro {
+                    name: "test_macro_name".to_string(),
+                    expansion: "test_macro_expansion".to_string(),
+                }))
+            });
+
+        editor.update_in(cx, |editor, window, cx| {
+            expand_macro_recursively(editor, &ExpandMacroRecursively, window, cx)
+        });
+        expand_request.next().await.unwrap();
+        cx.run_until_parked();
+
+        workspace
+            .update(cx, |workspace, _window, cx| {
+                workspace.active_pane().update(cx, |pane, cx| {
+                    assert_eq!(
+                        pane.items_len(),
+                        2,
+                        "Should have opened the macro expansion"
+                    );
+                    let new_editor = pane.active_item().unwrap().downcast::<Editor>().unwrap();
+                    new_editor.update(cx, |editor, cx| {
+                        assert_eq!(editor.text(cx), "test_macro_expansion");
+                        assert!(editor.read_only(cx));
+                        let buffer = editor.buffer().read(cx).as_singleton().unwrap();
+                        assert_eq!(
+                            buffer.read(cx).language().map(|language| language.name()),
+                            Some("Rust".into()),
+                            "Should use the language of the originating buffer"
+                        );
+                    });
+                })
+            })
+            .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_expand_macro_in_place(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/a"),
+            json!({
+                "main.rs": "fn main() {\n    println!(\"hi\");\n}\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(rust_lang());
+        let mut fake_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                name: RUST_ANALYZER_NAME,
+                ..FakeLspAdapter::default()
+            },
+        );
+        let workspace =
+            cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let worktree_id = workspace
+            .update(cx, |workspace, _window, cx| {
+                workspace.project().update(cx, |project, cx| {
+                    project.worktrees(cx).next().unwrap().read(cx).id()
+                })
+            })
+            .unwrap();
+        let editor = workspace
+            .update(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, "main.rs"), None, true, window, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        let fake_server = fake_servers.next().await.unwrap();
+        cx.run_until_parked();
+
+        let mut expand_request =
+            fake_server.set_request_handler::<LspExpandMacro, _, _>(|params, _| async move {
+                assert_eq!(params.position, lsp::Position::new(1, 6));
+                Ok(Some(ExpandedMacro {
+                    name: "println".to_string(),
+                    expansion: "{\n    print(\"hi\\n\");\n}".to_string(),
+                }))
+            });
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges([text::Point::new(1, 6)..text::Point::new(1, 6)])
+            });
+            expand_macro_in_place(editor, &ExpandMacroInPlace, window, cx)
+        });
+        expand_request.next().await.unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            editor.update(cx, |editor, cx| editor.text(cx)),
+            "fn main() {\n    println!(\"hi\");\n}\n",
+            "Should not edit the file before confirming"
+        );
+
+        cx.simulate_prompt_answer("Replace");
+        cx.run_until_parked();
+        assert_eq!(
+            editor.update(cx, |editor, cx| editor.text(cx)),
+            "fn main() {\n    {\n        print(\"hi\\n\");\n    };\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_indent_expansion() {
+        assert_eq!(indent_expansion("a!()", "    "), "a!()");
+        assert_eq!(
+            indent_expansion("{\n    a();\n\n    b();\n}", "    "),
+            "{\n        a();\n\n        b();\n    }"
+        );
+    }
+}
@@ -5,14 +5,14 @@ use gpui::{App, AppContext as _, Context, Entity, Window};
 use language::{Capability, Language, proto::serialize_anchor};
 use multi_buffer::MultiBuffer;
 use project::lsp_store::{
-    lsp_ext_command::{DocsUrls, ExpandMacro, ExpandedMacro},
+    lsp_ext_command::{DocsUrls, ParentModule, SyntaxTree},
     rust_analyzer_ext::RUST_ANALYZER_NAME,
 };
 use rpc::proto;
-use text::ToPointUtf16;
+use text::{OffsetRangeExt as _, ToPointUtf16};
 
 use crate::{
-    Editor, ExpandMacroRecursively, OpenDocs, element::register_action,
+    Editor, OpenDocs, OpenParentModule, ViewSyntaxTree, element::register_action,
     lsp_ext::find_specific_language_server_in_selection,
 };
 
@@ -30,17 +30,48 @@ pub fn apply_related_actions(editor: &Entity<Editor>, window: &mut Window, cx: &
         .filter_map(|buffer| buffer.read(cx).language())
         .any(|language| is_rust_language(language))
     {
-        register_action(&editor, window, expand_macro_recursively);
         register_action(&editor, window, open_docs);
+        register_action(&editor, window, open_parent_module);
+        if is_rust_analyzer_running(editor, cx) {
+            register_action(&editor, window, view_syntax_tree);
+        }
     }
 }
 
-pub fn expand_macro_recursively(
-    editor: &mut Editor,
-    _: &ExpandMacroRecursively,
-    window: &mut Window,
-    cx: &mut Context<Editor>,
-) {
+/// Whether rust-analyzer runs for the buffer of the newest selection. Remote projects don't
+/// tell which servers run for a buffer, so there any running rust-analyzer counts.
+fn is_rust_analyzer_running(editor: &Entity<Editor>, cx: &mut App) -> bool {
+    let editor = editor.read(cx);
+    let Some(project) = editor.project.clone() else {
+        return false;
+    };
+    let Some(buffer) = editor
+        .selections
+        .newest_anchor()
+        .start
+        .buffer_id
+        .and_then(|buffer_id| editor.buffer().read(cx).buffer(buffer_id))
+    else {
+        return false;
+    };
+
+    if !project.read(cx).is_local() {
+        return project
+            .read(cx)
+            .language_server_statuses(cx)
+            .any(|(_, status)| status.name == RUST_ANALYZER_NAME);
+    }
+    let lsp_store = project.read(cx).lsp_store();
+    buffer.update(cx, |buffer, cx| {
+        lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store
+                .language_servers_for_local_buffer(buffer, cx)
+                .any(|(adapter, _)| adapter.name.0 == RUST_ANALYZER_NAME)
+        })
+    })
+}
+
+pub fn open_docs(editor: &mut Editor, _: &OpenDocs, window: &mut Window, cx: &mut Context<Editor>) {
     if editor.selections.count() == 0 {
         return;
     }
@@ -61,14 +92,13 @@ pub fn expand_macro_recursively(
     let project = project.clone();
     let upstream_client = project.read(cx).lsp_store().read(cx).upstream_client();
     cx.spawn_in(window, async move |_editor, cx| {
-        let Some((trigger_anchor, rust_language, server_to_query, buffer)) = server_lookup.await
-        else {
+        let Some((trigger_anchor, _, server_to_query, buffer)) = server_lookup.await else {
             return Ok(());
         };
 
-        let macro_expansion = if let Some((client, project_id)) = upstream_client {
+        let docs_urls = if let Some((client, project_id)) = upstream_client {
             let buffer_id = buffer.update(cx, |buffer, _| buffer.remote_id())?;
-            let request = proto::LspExtExpandMacro {
+            let request = proto::LspExtOpenDocs {
                 project_id,
                 buffer_id: buffer_id.to_proto(),
                 position: Some(serialize_anchor(&trigger_anchor.text_anchor)),
@@ -76,10 +106,10 @@ pub fn expand_macro_recursively(
             let response = client
                 .request(request)
                 .await
-                .context("lsp ext expand macro proto request")?;
-            ExpandedMacro {
-                name: response.name,
-                expansion: response.expansion,
+                .context("lsp ext open docs proto request")?;
+            DocsUrls {
+                web: response.web,
+                local: response.local,
             }
         } else {
             let buffer_snapshot = buffer.update(cx, |buffer, _| buffer.snapshot())?;
@@ -89,33 +119,138 @@ pub fn expand_macro_recursively(
                     project.request_lsp(
                         buffer,
                         project::LanguageServerToQuery::Other(server_to_query),
-                        ExpandMacro { position },
+                        project::lsp_store::lsp_ext_command::OpenDocs { position },
                         cx,
                     )
                 })?
                 .await
-                .context("expand macro")?
+                .context("open docs")?
         };
 
-        if macro_expansion.is_empty() {
-            log::info!(
-                "Empty macro expansion for position {:?}",
+        if docs_urls.is_empty() {
+            log::debug!(
+                "Empty docs urls for position {:?}",
                 trigger_anchor.text_anchor
             );
             return Ok(());
         }
 
+        workspace.update(cx, |_workspace, cx| {
+            // Check if the local document exists, otherwise fallback to the online document.
+            // Open with the default browser.
+            if let Some(local_url) = docs_urls.local {
+                if fs::metadata(Path::new(&local_url[8..])).is_ok() {
+                    cx.open_url(&local_url);
+                    return;
+                }
+            }
+
+            if let Some(web_url) = docs_urls.web {
+                cx.open_url(&web_url);
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+pub fn view_syntax_tree(
+    editor: &mut Editor,
+    _: &ViewSyntaxTree,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    if editor.selections.count() == 0 {
+        return;
+    }
+    let Some(project) = &editor.project else {
+        return;
+    };
+    let Some(workspace) = editor.workspace() else {
+        return;
+    };
+
+    let selection = editor.selections.newest_anchor().clone();
+    let Some(buffer) = selection
+        .start
+        .buffer_id
+        .and_then(|buffer_id| editor.buffer().read(cx).buffer(buffer_id))
+    else {
+        return;
+    };
+    if !buffer
+        .read(cx)
+        .language_at(selection.start.text_anchor)
+        .is_some_and(|language| is_rust_language(&language))
+    {
+        return;
+    }
+    // An empty selection, or one spanning several buffers, shows the tree of the whole file.
+    let range = (selection.start != selection.end
+        && selection.end.buffer_id == selection.start.buffer_id)
+        .then(|| selection.start.text_anchor..selection.end.text_anchor);
+    let server_lookup = buffer.update(cx, |buffer, cx| {
+        project.update(cx, |project, cx| {
+            project.language_server_id_for_name(buffer, RUST_ANALYZER_NAME, cx)
+        })
+    });
+
+    let project = project.clone();
+    let upstream_client = project.read(cx).lsp_store().read(cx).upstream_client();
+    cx.spawn_in(window, async move |_editor, cx| {
+        let Some(server_to_query) = server_lookup.await else {
+            return Ok(());
+        };
+
+        let syntax_tree = if let Some((client, project_id)) = upstream_client {
+            let buffer_id = buffer.update(cx, |buffer, _| buffer.remote_id())?;
+            let request = proto::LspExtSyntaxTree {
+                project_id,
+                buffer_id: buffer_id.to_proto(),
+                start: range.as_ref().map(|range| serialize_anchor(&range.start)),
+                end: range.as_ref().map(|range| serialize_anchor(&range.end)),
+            };
+            client
+                .request(request)
+                .await
+                .context("lsp ext syntax tree proto request")?
+                .syntax_tree
+        } else {
+            let buffer_snapshot = buffer.update(cx, |buffer, _| buffer.snapshot())?;
+            let range = range.map(|range| {
+                range.start.to_point_utf16(&buffer_snapshot)
+                    ..range.end.to_point_utf16(&buffer_snapshot)
+            });
+            project
+                .update(cx, |project, cx| {
+                    project.request_lsp(
+                        buffer,
+                        project::LanguageServerToQuery::Other(server_to_query),
+                        SyntaxTree { range },
+                        cx,
+                    )
+                })?
+                .await
+                .context("view syntax tree")?
+        };
+
+        if syntax_tree.is_empty() {
+            log::info!(
+                "Empty syntax tree for selection {:?}",
+                selection.start.text_anchor..selection.end.text_anchor
+            );
+            return Ok(());
+        }
+
         let buffer = project
             .update(cx, |project, cx| project.create_buffer(cx))?
             .await?;
         workspace.update_in(cx, |workspace, window, cx| {
             buffer.update(cx, |buffer, cx| {
-                buffer.set_text(macro_expansion.expansion, cx);
-                buffer.set_language(Some(rust_language), cx);
+                buffer.set_text(syntax_tree, cx);
                 buffer.set_capability(Capability::ReadOnly, cx);
             });
             let multibuffer =
-                cx.new(|cx| MultiBuffer::singleton(buffer, cx).with_title(macro_expansion.name));
+                cx.new(|cx| MultiBuffer::singleton(buffer, cx).with_title("Syntax Tree".into()));
             workspace.add_item_to_active_pane(
                 Box::new(cx.new(|cx| {
                     let mut editor = Editor::for_multibuffer(multibuffer, None, window, cx);
@@ -132,7 +267,12 @@ pub fn expand_macro_recursively(
     .detach_and_log_err(cx);
 }
 
-pub fn open_docs(editor: &mut Editor, _: &OpenDocs, window: &mut Window, cx: &mut Context<Editor>) {
+pub fn open_parent_module(
+    editor: &mut Editor,
+    _: &OpenParentModule,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
     if editor.selections.count() == 0 {
         return;
     }
@@ -151,64 +291,46 @@ pub fn open_docs(editor: &mut Editor, _: &OpenDocs, window: &mut Window, cx: &mu
     );
 
     let project = project.clone();
-    let upstream_client = project.read(cx).lsp_store().read(cx).upstream_client();
     cx.spawn_in(window, async move |_editor, cx| {
         let Some((trigger_anchor, _, server_to_query, buffer)) = server_lookup.await else {
             return Ok(());
         };
 
-        let docs_urls = if let Some((client, project_id)) = upstream_client {
-            let buffer_id = buffer.update(cx, |buffer, _| buffer.remote_id())?;
-            let request = proto::LspExtOpenDocs {
-                project_id,
-                buffer_id: buffer_id.to_proto(),
-                position: Some(serialize_anchor(&trigger_anchor.text_anchor)),
-            };
-            let response = client
-                .request(request)
-                .await
-                .context("lsp ext open docs proto request")?;
-            DocsUrls {
-                web: response.web,
-                local: response.local,
-            }
-        } else {
-            let buffer_snapshot = buffer.update(cx, |buffer, _| buffer.snapshot())?;
-            let position = trigger_anchor.text_anchor.to_point_utf16(&buffer_snapshot);
-            project
-                .update(cx, |project, cx| {
-                    project.request_lsp(
-                        buffer,
-                        project::LanguageServerToQuery::Other(server_to_query),
-                        project::lsp_store::lsp_ext_command::OpenDocs { position },
-                        cx,
-                    )
-                })?
-                .await
-                .context("open docs")?
-        };
+        let buffer_snapshot = buffer.update(cx, |buffer, _| buffer.snapshot())?;
+        let position = trigger_anchor.text_anchor.to_point_utf16(&buffer_snapshot);
+        let mut locations = project
+            .update(cx, |project, cx| {
+                project.request_lsp(
+                    buffer,
+                    project::LanguageServerToQuery::Other(server_to_query),
+                    ParentModule { position },
+                    cx,
+                )
+            })?
+            .await
+            .context("open parent module")?
+            .into_iter()
+            .map(|link| link.target);
 
-        if docs_urls.is_empty() {
-            log::debug!(
-                "Empty docs urls for position {:?}",
+        let Some(target) = locations.next() else {
+            log::info!(
+                "No parent module for position {:?}",
                 trigger_anchor.text_anchor
             );
             return Ok(());
+        };
+        for location in locations {
+            log::info!("Ignoring additional parent module location {location:?}");
         }
 
-        workspace.update(cx, |_workspace, cx| {
-            // Check if the local document exists, otherwise fallback to the online document.
-            // Open with the default browser.
-            if let Some(local_url) = docs_urls.local {
-                if fs::metadata(Path::new(&local_url[8..])).is_ok() {
-                    cx.open_url(&local_url);
-                    return;
-                }
-            }
-
-            if let Some(web_url) = docs_urls.web {
-                cx.open_url(&web_url);
-            }
+        workspace.update_in(cx, |workspace, window, cx| {
+            let range = target.range.to_point(target.buffer.read(cx));
+            let pane = workspace.active_pane().clone();
+            let editor =
+                workspace.open_project_item::<Editor>(pane, target.buffer, true, true, window, cx);
+            editor.update(cx, |editor, cx| {
+                editor.go_to_singleton_buffer_range(range, window, cx);
+            });
         })
     })
     .detach_and_log_err(cx);
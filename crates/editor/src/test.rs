@@ -14,7 +14,7 @@ use gpui::{
 use project::Project;
 use util::test::{marked_text_offsets, marked_text_ranges};
 
-pub use crate::rust_analyzer_ext::expand_macro_recursively;
+pub use crate::macro_expansion::expand_macro_recursively;
 
 #[cfg(test)]
 #[ctor::ctor]
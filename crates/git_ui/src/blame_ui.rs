@@ -1,18 +1,23 @@
+use std::{path::Path, time::Duration};
+
 use crate::{commit_tooltip::CommitTooltip, commit_view::CommitView};
-use editor::{BlameRenderer, Editor};
+use anyhow::anyhow;
+use editor::{BlameRenderer, Editor, MultiBuffer};
 use git::{
     blame::{BlameEntry, ParsedCommitMessage},
     repository::CommitSummary,
 };
 use gpui::{
     AnyElement, App, AppContext as _, ClipboardItem, Element as _, Entity, Hsla,
-    InteractiveElement as _, MouseButton, Pixels, StatefulInteractiveElement as _, Styled as _,
-    Subscription, TextStyle, WeakEntity, Window, div,
+    InteractiveElement as _, MouseButton, Pixels, Rgba, StatefulInteractiveElement as _,
+    Styled as _, Subscription, TextStyle, WeakEntity, Window, div,
 };
+use language::Capability;
 use project::{git_store::Repository, project_settings::ProjectSettings};
 use settings::Settings as _;
 use ui::{
-    ActiveTheme, Color, ContextMenu, FluentBuilder as _, Icon, IconName, ParentElement as _, h_flex,
+    ActiveTheme, Color, ContextMenu, ContextMenuEntry, FluentBuilder as _, Icon, IconName,
+    ParentElement as _, h_flex,
 };
 use workspace::Workspace;
 
@@ -65,10 +70,14 @@ impl BlameRenderer for GitBlameRenderer {
                 .on_mouse_down(MouseButton::Right, {
                     let blame_entry = blame_entry.clone();
                     let details = details.clone();
+                    let repository = repository.clone();
+                    let workspace = workspace.clone();
                     move |event, window, cx| {
                         deploy_blame_entry_context_menu(
                             &blame_entry,
                             details.as_ref(),
+                            repository.clone(),
+                            workspace.clone(),
                             editor.clone(),
                             event.position,
                             window,
@@ -124,9 +133,11 @@ impl BlameRenderer for GitBlameRenderer {
     ) -> Option<AnyElement> {
         let relative_timestamp = blame_entry_relative_timestamp(&blame_entry);
         let author = blame_entry.author.as_deref().unwrap_or_default();
-        let summary_enabled = ProjectSettings::get_global(cx)
-            .git
-            .show_inline_commit_summary();
+        let git_settings = &ProjectSettings::get_global(cx).git;
+        let summary_enabled = git_settings.show_inline_commit_summary();
+        let age_color = git_settings
+            .inline_blame_commit_age_range()
+            .map(|max_age| blame_entry_age_color(&blame_entry, max_age, cx));
 
         let text = match blame_entry.summary.as_ref() {
             Some(summary) if summary_enabled => {
@@ -142,6 +153,9 @@ impl BlameRenderer for GitBlameRenderer {
                 .font_family(style.font().family)
                 .text_color(cx.theme().status().hint)
                 .line_height(style.line_height)
+                .when_some(age_color, |this, age_color| {
+                    this.pl_1().border_l_2().border_color(age_color)
+                })
                 .child(Icon::new(IconName::FileGit).color(Color::Hint))
                 .child(text)
                 .gap_2()
@@ -191,6 +205,8 @@ impl BlameRenderer for GitBlameRenderer {
 fn deploy_blame_entry_context_menu(
     blame_entry: &BlameEntry,
     details: Option<&ParsedCommitMessage>,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
     editor: Entity<Editor>,
     position: gpui::Point<Pixels>,
     window: &mut Window,
@@ -198,10 +214,28 @@ fn deploy_blame_entry_context_menu(
 ) {
     let context_menu = ContextMenu::build(window, cx, move |menu, _, _| {
         let sha = format!("{}", blame_entry.sha);
+        let has_parent = blame_entry.previous.is_some();
+        let open_at_parent = {
+            let blame_entry = blame_entry.clone();
+            move |window: &mut Window, cx: &mut App| {
+                open_file_at_parent_commit(
+                    &blame_entry,
+                    repository.downgrade(),
+                    workspace.clone(),
+                    window,
+                    cx,
+                )
+            }
+        };
         menu.on_blur_subscription(Subscription::new(|| {}))
             .entry("Copy commit SHA", None, move |_, cx| {
                 cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
             })
+            .item(
+                ContextMenuEntry::new("Open file at parent commit")
+                    .disabled(!has_parent)
+                    .handler(open_at_parent),
+            )
             .when_some(
                 details.and_then(|details| details.permalink.clone()),
                 |this, url| {
@@ -218,6 +252,102 @@ fn deploy_blame_entry_context_menu(
     });
 }
 
+/// Opens a read-only editor showing the blamed file as it was in the parent of the blamed
+/// commit, i.e. right before the line was last changed.
+fn open_file_at_parent_commit(
+    blame_entry: &BlameEntry,
+    repository: WeakEntity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    // `previous` is "<parent sha> <path at parent>", which accounts for renames.
+    let Some(parent_path) = blame_entry
+        .previous
+        .as_deref()
+        .and_then(|previous| previous.split_once(' '))
+        .map(|(_, path)| path.to_string())
+    else {
+        return;
+    };
+    let short_sha = blame_entry.sha.display_short();
+    let Some(commit_diff) = repository
+        .update(cx, |repository, _| {
+            repository.load_commit_diff(blame_entry.sha.to_string())
+        })
+        .ok()
+    else {
+        return;
+    };
+
+    window
+        .spawn(cx, async move |cx| {
+            let commit_diff = commit_diff.await??;
+            let parent_text = commit_diff
+                .files
+                .into_iter()
+                .find(|file| *file.path == *Path::new(&parent_path))
+                .and_then(|file| file.old_text)
+                .ok_or_else(|| {
+                    anyhow!("{parent_path} is not part of the parent of commit {short_sha}")
+                })?;
+
+            let project = workspace.read_with(cx, |workspace, _| workspace.project().clone())?;
+            let languages = project.read_with(cx, |project, _| project.languages().clone())?;
+            let language = languages
+                .language_for_file_path(Path::new(&parent_path))
+                .await
+                .ok();
+            let buffer = project
+                .update(cx, |project, cx| project.create_buffer(cx))?
+                .await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                buffer.update(cx, |buffer, cx| {
+                    buffer.set_text(parent_text, cx);
+                    buffer.set_language(language, cx);
+                    buffer.set_capability(Capability::ReadOnly, cx);
+                });
+                let title = format!("{parent_path} @ {short_sha}^");
+                let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx).with_title(title));
+                workspace.add_item_to_active_pane(
+                    Box::new(cx.new(|cx| {
+                        let mut editor = Editor::for_multibuffer(multibuffer, None, window, cx);
+                        editor.set_read_only(true);
+                        editor
+                    })),
+                    None,
+                    true,
+                    window,
+                    cx,
+                );
+            })
+        })
+        .detach_and_log_err(cx);
+}
+
+/// Picks the color for a blame entry from a gradient that goes from the theme's "added"
+/// color for fresh commits to its muted text color for commits `max_age` old or older.
+/// Entries without a commit time belong to the working tree and get the "modified" color.
+fn blame_entry_age_color(blame_entry: &BlameEntry, max_age: Duration, cx: &App) -> Hsla {
+    let colors = cx.theme().colors();
+    let Some(committer_time) = blame_entry.committer_time else {
+        return colors.version_control_modified;
+    };
+
+    let age = time::OffsetDateTime::now_utc().unix_timestamp() - committer_time;
+    let progress = (age.max(0) as f32 / max_age.as_secs_f32()).min(1.);
+    let newest = Rgba::from(colors.version_control_added);
+    let oldest = Rgba::from(colors.text_muted);
+    let lerp = |from: f32, to: f32| from + (to - from) * progress;
+    Rgba {
+        r: lerp(newest.r, oldest.r),
+        g: lerp(newest.g, oldest.g),
+        b: lerp(newest.b, oldest.b),
+        a: lerp(newest.a, oldest.a),
+    }
+    .into()
+}
+
 fn blame_entry_relative_timestamp(blame_entry: &BlameEntry) -> String {
     match blame_entry.author_offset_date_time() {
         Ok(timestamp) => {
@@ -1508,6 +1508,22 @@ impl App {
         self.active_drag.is_some()
     }
 
+    /// Starts dragging the given value, rendering the given view at the cursor until it is
+    /// dropped. Custom elements can use this to start drags that [`crate::InteractiveElement::on_drag`]
+    /// can't express, such as ones that only start from part of the element.
+    pub fn start_drag<T: 'static, W: Render>(
+        &mut self,
+        value: T,
+        view: Entity<W>,
+        cursor_offset: Point<Pixels>,
+    ) {
+        self.active_drag = Some(AnyDrag {
+            view: view.into(),
+            value: Arc::new(value),
+            cursor_offset,
+        });
+    }
+
     /// Set the prompt renderer for GPUI. This will replace the default or platform specific
     /// prompts with this custom implementation.
     pub fn set_prompt_builder(
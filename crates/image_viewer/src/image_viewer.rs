@@ -159,6 +159,7 @@ impl Item for ImageView {
             text,
             highlights: None,
             font: None,
+            on_click: None,
         }])
     }
 
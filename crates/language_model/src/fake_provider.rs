@@ -7,7 +7,7 @@ use futures::{FutureExt, StreamExt, channel::mpsc, future::BoxFuture, stream::Bo
 use gpui::{AnyView, App, AsyncApp, Entity, Task, Window};
 use http_client::Result;
 use parking_lot::Mutex;
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 
 pub fn language_model_id() -> LanguageModelId {
     LanguageModelId::from("fake".to_string())
@@ -87,6 +87,7 @@ pub struct ToolUseRequest {
 #[derive(Default)]
 pub struct FakeLanguageModel {
     current_completion_txs: Mutex<Vec<(LanguageModelRequest, mpsc::UnboundedSender<String>)>>,
+    completion_errors: Mutex<VecDeque<anyhow::Error>>,
 }
 
 impl FakeLanguageModel {
@@ -98,6 +99,11 @@ impl FakeLanguageModel {
             .collect()
     }
 
+    /// Makes the next completion requests fail with the given errors, one per request.
+    pub fn fail_next_completions(&self, errors: impl IntoIterator<Item = anyhow::Error>) {
+        self.completion_errors.lock().extend(errors);
+    }
+
     pub fn completion_count(&self) -> usize {
         self.current_completion_txs.lock().len()
     }
@@ -165,6 +171,10 @@ impl LanguageModel for FakeLanguageModel {
         request: LanguageModelRequest,
         _: &AsyncApp,
     ) -> BoxFuture<'static, Result<BoxStream<'static, Result<LanguageModelCompletionEvent>>>> {
+        if let Some(error) = self.completion_errors.lock().pop_front() {
+            return futures::future::ready(Err(error)).boxed();
+        }
+
         let (tx, rx) = mpsc::unbounded();
         self.current_completion_txs.lock().push((request, tx));
         async move {
@@ -21,6 +21,7 @@ use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::fmt;
 use std::ops::{Add, Sub};
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use util::serde::is_default;
 
@@ -290,6 +291,36 @@ pub enum AuthenticateError {
     Other(#[from] anyhow::Error),
 }
 
+/// An error returned when a provider rejected a request because its rate limit
+/// was exceeded.
+#[derive(Debug, Error)]
+pub struct RateLimitExceededError {
+    /// How long the provider asked to wait before retrying, if it said so.
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitExceededError {
+    /// Reads the delay from a `Retry-After` header expressed in seconds.
+    pub fn from_headers(headers: &http_client::http::HeaderMap) -> Self {
+        Self {
+            retry_after: anthropic::retry_after_from_headers(headers),
+        }
+    }
+}
+
+impl fmt::Display for RateLimitExceededError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.retry_after {
+            Some(retry_after) => write!(
+                f,
+                "Rate limit exceeded. Retry after {} seconds.",
+                retry_after.as_secs()
+            ),
+            None => write!(f, "Rate limit exceeded."),
+        }
+    }
+}
+
 pub trait LanguageModelProvider: 'static {
     fn id(&self) -> LanguageModelProviderId;
     fn name(&self) -> LanguageModelProviderName;
@@ -14,7 +14,8 @@ use http_client::HttpClient;
 use language_model::{
     AuthenticateError, LanguageModel, LanguageModelCacheConfiguration, LanguageModelId,
     LanguageModelName, LanguageModelProvider, LanguageModelProviderId, LanguageModelProviderName,
-    LanguageModelProviderState, LanguageModelRequest, MessageContent, RateLimiter, Role,
+    LanguageModelProviderState, LanguageModelRequest, MessageContent, RateLimitExceededError,
+    RateLimiter, Role,
 };
 use language_model::{LanguageModelCompletionEvent, LanguageModelToolUse, StopReason};
 use schemars::JsonSchema;
@@ -377,7 +378,15 @@ impl AnthropicModel {
             let api_key = api_key.ok_or_else(|| anyhow!("Missing Anthropic API Key"))?;
             let request =
                 anthropic::stream_completion(http_client.as_ref(), &api_url, &api_key, request);
-            request.await.context("failed to stream completion")
+            request.await.map_err(|error| match error {
+                AnthropicError::RateLimit { retry_after } => {
+                    anyhow!(RateLimitExceededError { retry_after })
+                }
+                AnthropicError::ApiError(error) if error.is_rate_limit_error() => {
+                    anyhow!(RateLimitExceededError { retry_after: None })
+                }
+                error => anyhow!(error).context("failed to stream completion"),
+            })
         }
         .boxed()
     }
@@ -20,7 +20,8 @@ use language_model::{
 };
 use language_model::{
     LanguageModelAvailability, LanguageModelCompletionEvent, LanguageModelProvider, LlmApiToken,
-    MaxMonthlySpendReachedError, PaymentRequiredError, RefreshLlmTokenListener,
+    MaxMonthlySpendReachedError, PaymentRequiredError, RateLimitExceededError,
+    RefreshLlmTokenListener,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
@@ -549,6 +550,10 @@ impl CloudLanguageModel {
 
                 retries_remaining -= 1;
                 retry_delay *= 2; // If it fails again, wait longer.
+            } else if status == StatusCode::TOO_MANY_REQUESTS {
+                return Err(anyhow!(RateLimitExceededError::from_headers(
+                    response.headers()
+                )));
             } else if status == StatusCode::PAYMENT_REQUIRED {
                 return Err(anyhow!(PaymentRequiredError));
             } else {
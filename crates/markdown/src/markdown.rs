@@ -13,10 +13,14 @@ use gpui::{
     FocusHandle, Focusable, FontStyle, FontWeight, GlobalElementId, Hitbox, Hsla, KeyContext,
     Length, MouseDownEvent, MouseEvent, MouseMoveEvent, MouseUpEvent, Point, Render, Stateful,
     StrikethroughStyle, StyleRefinement, StyledText, Task, TextLayout, TextRun, TextStyle,
-    TextStyleRefinement, actions, point, quad,
+    TextStyleRefinement, UnderlineStyle, actions, point, quad,
 };
 use language::{Language, LanguageRegistry, Rope};
-use parser::{MarkdownEvent, MarkdownTag, MarkdownTagEnd, parse_links_only, parse_markdown};
+use parser::{
+    AnsiColor, AnsiHandling, AnsiStyle, MarkdownEvent, MarkdownParseOptions, MarkdownTag,
+    MarkdownTagEnd, MetadataBlockKind, code_block_language, parse_links_only,
+    parse_markdown_with_options,
+};
 use pulldown_cmark::Alignment;
 use theme::SyntaxTheme;
 use ui::{Tooltip, prelude::*};
@@ -38,6 +42,18 @@ pub struct MarkdownStyle {
     pub selection_background_color: Hsla,
     pub heading: StyleRefinement,
     pub table_overflow_x_scroll: bool,
+    /// Accent colors for code blocks, keyed by the language names [`code_block_language`]
+    /// resolves fenced languages to.
+    pub code_block_language_accents: HashMap<SharedString, Hsla>,
+}
+
+impl MarkdownStyle {
+    /// The accent color for code blocks fenced with the given language or one of its aliases.
+    pub fn code_block_accent(&self, language: &str) -> Option<Hsla> {
+        self.code_block_language_accents
+            .get(&code_block_language(language))
+            .copied()
+    }
 }
 
 impl Default for MarkdownStyle {
@@ -55,6 +71,7 @@ impl Default for MarkdownStyle {
             selection_background_color: Default::default(),
             heading: Default::default(),
             table_overflow_x_scroll: false,
+            code_block_language_accents: HashMap::default(),
         }
     }
 }
@@ -80,6 +97,7 @@ pub struct Markdown {
 struct Options {
     parse_links_only: bool,
     copy_code_block_buttons: bool,
+    parse_options: MarkdownParseOptions,
 }
 
 actions!(markdown, [Copy]);
@@ -108,6 +126,7 @@ impl Markdown {
             options: Options {
                 parse_links_only: false,
                 copy_code_block_buttons: true,
+                parse_options: MarkdownParseOptions::default(),
             },
             open_url: None,
             copied_code_blocks: HashSet::new(),
@@ -143,6 +162,7 @@ impl Markdown {
             options: Options {
                 parse_links_only: true,
                 copy_code_block_buttons: true,
+                parse_options: MarkdownParseOptions::default(),
             },
             open_url: None,
             copied_code_blocks: HashSet::new(),
@@ -197,6 +217,7 @@ impl Markdown {
 
         let source = self.source.clone();
         let parse_text_only = self.options.parse_links_only;
+        let parse_options = self.options.parse_options.clone();
         let language_registry = self.language_registry.clone();
         let fallback = self.fallback_code_block_language.clone();
         let parsed = cx.background_spawn(async move {
@@ -207,7 +228,7 @@ impl Markdown {
                     languages: HashMap::default(),
                 });
             }
-            let (events, language_names) = parse_markdown(&source);
+            let (events, language_names) = parse_markdown_with_options(&source, &parse_options);
             let mut languages = HashMap::with_capacity(language_names.len());
             for name in language_names {
                 if let Some(registry) = language_registry.as_ref() {
@@ -254,6 +275,78 @@ impl Markdown {
         self.options.copy_code_block_buttons = should_copy;
         self
     }
+
+    /// Sets how ANSI escape sequences in code blocks are handled, reparsing the source if needed.
+    pub fn set_code_block_ansi(&mut self, code_block_ansi: AnsiHandling, cx: &mut Context<Self>) {
+        if self.options.parse_options.code_block_ansi != code_block_ansi {
+            self.options.parse_options.code_block_ansi = code_block_ansi;
+            self.parse(cx);
+        }
+    }
+
+    /// Restricts which URL schemes links may use, reparsing the source if needed. See
+    /// [`MarkdownParseOptions::allowed_link_schemes`].
+    pub fn set_allowed_link_schemes(
+        &mut self,
+        allowed_link_schemes: Option<HashSet<SharedString>>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.options.parse_options.allowed_link_schemes != allowed_link_schemes {
+            self.options.parse_options.allowed_link_schemes = allowed_link_schemes;
+            self.parse(cx);
+        }
+    }
+
+    /// Widens the URL schemes autolinks are created for, reparsing the source if needed. See
+    /// [`MarkdownParseOptions::autolink_schemes`].
+    pub fn set_autolink_schemes(
+        &mut self,
+        autolink_schemes: HashSet<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.options.parse_options.autolink_schemes != autolink_schemes {
+            self.options.parse_options.autolink_schemes = autolink_schemes;
+            self.parse(cx);
+        }
+    }
+
+    /// Enables parsing definition lists, reparsing the source if needed.
+    pub fn set_definition_lists(&mut self, definition_lists: bool, cx: &mut Context<Self>) {
+        if self.options.parse_options.definition_lists != definition_lists {
+            self.options.parse_options.definition_lists = definition_lists;
+            self.parse(cx);
+        }
+    }
+
+    /// Enables parsing `---`-delimited YAML front matter, reparsing the source if needed. See
+    /// [`Self::metadata`].
+    pub fn set_yaml_metadata_blocks(&mut self, yaml_metadata_blocks: bool, cx: &mut Context<Self>) {
+        if self.options.parse_options.yaml_metadata_blocks != yaml_metadata_blocks {
+            self.options.parse_options.yaml_metadata_blocks = yaml_metadata_blocks;
+            self.parse(cx);
+        }
+    }
+
+    /// Enables replacing `:shortcode:` sequences with emoji, reparsing the source if needed.
+    pub fn set_emoji_shortcodes(&mut self, emoji_shortcodes: bool, cx: &mut Context<Self>) {
+        if self.options.parse_options.emoji_shortcodes != emoji_shortcodes {
+            self.options.parse_options.emoji_shortcodes = emoji_shortcodes;
+            self.parse(cx);
+        }
+    }
+
+    /// Turns `[[Page]]` wiki links into links to the given URL template, reparsing the source
+    /// if needed. See [`MarkdownParseOptions::wiki_link_url_template`].
+    pub fn set_wiki_link_url_template(
+        &mut self,
+        wiki_link_url_template: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.options.parse_options.wiki_link_url_template != wiki_link_url_template {
+            self.options.parse_options.wiki_link_url_template = wiki_link_url_template;
+            self.parse(cx);
+        }
+    }
 }
 
 impl Render for Markdown {
@@ -313,6 +406,20 @@ impl ParsedMarkdown {
     pub fn events(&self) -> &Arc<[(Range<usize>, MarkdownEvent)]> {
         &self.events
     }
+
+    /// How many fenced code blocks of each language the document has.
+    pub fn code_block_language_histogram(&self) -> HashMap<SharedString, usize> {
+        parser::code_block_language_histogram(&self.events)
+    }
+
+    /// The raw front matter of the document and its delimiter style, if it has any. YAML front
+    /// matter is only parsed when enabled with [`Self::set_yaml_metadata_blocks`].
+    pub fn metadata(&self) -> Option<(MetadataBlockKind, &SharedString)> {
+        self.events.iter().find_map(|(_, event)| match event {
+            MarkdownEvent::MetadataBlock { kind, content } => Some((*kind, content)),
+            _ => None,
+        })
+    }
 }
 
 pub struct MarkdownElement {
@@ -607,10 +714,16 @@ impl Element for MarkdownElement {
                             );
                         }
                         MarkdownTag::CodeBlock(kind) => {
-                            let language = if let CodeBlockKind::Fenced(language) = kind {
-                                parsed_markdown.languages.get(language).cloned()
+                            let (language, accent) = if let CodeBlockKind::Fenced(language) = kind {
+                                (
+                                    parsed_markdown
+                                        .languages
+                                        .get(&code_block_language(language))
+                                        .cloned(),
+                                    self.style.code_block_accent(language),
+                                )
                             } else {
-                                None
+                                (None, None)
                             };
 
                             // This is a parent container that we can position the copy button inside.
@@ -628,6 +741,9 @@ impl Element for MarkdownElement {
                                     }
                                 });
                             code_block.style().refine(&self.style.code_block);
+                            if let Some(accent) = accent {
+                                code_block = code_block.border_l_2().border_color(accent);
+                            }
                             if let Some(code_block_text_style) = &self.style.code_block.text {
                                 builder.push_text_style(code_block_text_style.to_owned());
                             }
@@ -683,6 +799,19 @@ impl Element for MarkdownElement {
                             }
                         }
                         MarkdownTag::MetadataBlock(_) => {}
+                        MarkdownTag::DefinitionList => {
+                            builder.push_div(div().flex().flex_col().mb_2(), range, markdown_end);
+                        }
+                        MarkdownTag::DefinitionListTitle => {
+                            builder.push_div(div(), range, markdown_end);
+                            builder.push_text_style(TextStyleRefinement {
+                                font_weight: Some(FontWeight::BOLD),
+                                ..Default::default()
+                            });
+                        }
+                        MarkdownTag::DefinitionListDefinition => {
+                            builder.push_div(div().pl_4(), range, markdown_end);
+                        }
                         MarkdownTag::Table(alignments) => {
                             builder.table_alignments = alignments.clone();
                             builder.push_div(
@@ -823,6 +952,12 @@ impl Element for MarkdownElement {
                         builder.pop_div();
                     }
                     MarkdownTagEnd::HtmlBlock => builder.pop_div(),
+                    MarkdownTagEnd::DefinitionList => builder.pop_div(),
+                    MarkdownTagEnd::DefinitionListTitle => {
+                        builder.pop_text_style();
+                        builder.pop_div()
+                    }
+                    MarkdownTagEnd::DefinitionListDefinition => builder.pop_div(),
                     MarkdownTagEnd::List(_) => {
                         builder.pop_list();
                         builder.pop_div();
@@ -859,11 +994,42 @@ impl Element for MarkdownElement {
                 MarkdownEvent::Text(parsed) => {
                     builder.push_text(parsed, range.start);
                 }
+                MarkdownEvent::SubstitutedText(text) => {
+                    builder.push_substituted_text(text, range.clone());
+                }
+                MarkdownEvent::AnsiText { text, style, link } => {
+                    if let Some(link) = link {
+                        builder.push_link(link.clone(), range.clone());
+                        builder.push_text_style(self.style.link.clone());
+                    }
+                    builder.push_text_style(ansi_text_style(style, cx));
+                    builder.push_unhighlighted_text(text, range.start);
+                    builder.pop_text_style();
+                    if link.is_some() {
+                        builder.pop_text_style();
+                    }
+                }
                 MarkdownEvent::Code => {
                     builder.push_text_style(self.style.inline_code.clone());
                     builder.push_text(&parsed_markdown.source[range.clone()], range.start);
                     builder.pop_text_style();
                 }
+                MarkdownEvent::InlineMath(tex) => {
+                    builder.push_text_style(self.style.inline_code.clone());
+                    builder.push_text(tex, range.start);
+                    builder.pop_text_style();
+                }
+                MarkdownEvent::DisplayMath(tex) => {
+                    builder.push_div(
+                        div().w_full().my_2().flex().justify_center(),
+                        range,
+                        markdown_end,
+                    );
+                    builder.push_text_style(self.style.inline_code.clone());
+                    builder.push_text(tex, range.start);
+                    builder.pop_text_style();
+                    builder.pop_div();
+                }
                 MarkdownEvent::Html => {
                     builder.push_text(&parsed_markdown.source[range.clone()], range.start);
                 }
@@ -881,6 +1047,8 @@ impl Element for MarkdownElement {
                     );
                     builder.pop_div()
                 }
+                // Front matter is exposed through `ParsedMarkdown::metadata` instead.
+                MarkdownEvent::MetadataBlock { .. } => {}
                 MarkdownEvent::SoftBreak => builder.push_text(" ", range.start),
                 MarkdownEvent::HardBreak => builder.push_text("\n", range.start),
                 _ => log::error!("unsupported markdown event {:?}", event),
@@ -1124,6 +1292,25 @@ impl MarkdownElementBuilder {
         });
     }
 
+    /// Pushes text standing for the given source range, whose length may differ from it.
+    fn push_substituted_text(&mut self, text: &str, source_range: Range<usize>) {
+        self.push_text(text, source_range.start);
+        self.current_source_index = source_range.end;
+    }
+
+    /// Pushes text without applying the syntax highlighting of the enclosing code block.
+    fn push_unhighlighted_text(&mut self, text: &str, source_index: usize) {
+        self.pending_line.source_mappings.push(SourceMapping {
+            rendered_index: self.pending_line.text.len(),
+            source_index,
+        });
+        self.pending_line.text.push_str(text);
+        self.current_source_index = source_index + text.len();
+        self.pending_line
+            .runs
+            .push(self.text_style().to_run(text.len()));
+    }
+
     fn push_text(&mut self, text: &str, source_index: usize) {
         self.pending_line.source_mappings.push(SourceMapping {
             rendered_index: self.pending_line.text.len(),
@@ -1391,6 +1578,70 @@ impl RenderedText {
     }
 }
 
+fn ansi_text_style(style: &AnsiStyle, cx: &App) -> TextStyleRefinement {
+    let mut color = style.foreground.map(|color| ansi_color(color, cx));
+    if style.dim {
+        color = Some(color.unwrap_or(cx.theme().colors().text).opacity(0.7));
+    }
+    TextStyleRefinement {
+        color,
+        background_color: style.background.map(|color| ansi_color(color, cx)),
+        font_weight: style.bold.then_some(FontWeight::BOLD),
+        font_style: style.italic.then_some(FontStyle::Italic),
+        underline: style.underline.then_some(UnderlineStyle {
+            thickness: px(1.),
+            color: None,
+            wavy: false,
+        }),
+        strikethrough: style.strikethrough.then_some(StrikethroughStyle {
+            thickness: px(1.),
+            color: None,
+        }),
+        ..Default::default()
+    }
+}
+
+/// Resolves a color from an escape sequence, using the theme's terminal palette for the 16
+/// standard colors and the xterm color cube and grayscale ramp for the other indexed colors.
+fn ansi_color(color: AnsiColor, cx: &App) -> Hsla {
+    let colors = cx.theme().colors();
+    let (r, g, b) = match color {
+        AnsiColor::Indexed(index) => match index {
+            0 => return colors.terminal_ansi_black,
+            1 => return colors.terminal_ansi_red,
+            2 => return colors.terminal_ansi_green,
+            3 => return colors.terminal_ansi_yellow,
+            4 => return colors.terminal_ansi_blue,
+            5 => return colors.terminal_ansi_magenta,
+            6 => return colors.terminal_ansi_cyan,
+            7 => return colors.terminal_ansi_white,
+            8 => return colors.terminal_ansi_bright_black,
+            9 => return colors.terminal_ansi_bright_red,
+            10 => return colors.terminal_ansi_bright_green,
+            11 => return colors.terminal_ansi_bright_yellow,
+            12 => return colors.terminal_ansi_bright_blue,
+            13 => return colors.terminal_ansi_bright_magenta,
+            14 => return colors.terminal_ansi_bright_cyan,
+            15 => return colors.terminal_ansi_bright_white,
+            16..=231 => {
+                let index = index - 16;
+                let component = |value: u8| if value == 0 { 0 } else { value * 40 + 55 };
+                (
+                    component(index / 36),
+                    component(index / 6 % 6),
+                    component(index % 6),
+                )
+            }
+            232..=255 => {
+                let value = (index - 232) * 10 + 8;
+                (value, value, value)
+            }
+        },
+        AnsiColor::Rgb(r, g, b) => (r, g, b),
+    };
+    gpui::rgb(u32::from_be_bytes([0, r, g, b])).into()
+}
+
 /// Some markdown blocks are indented, and others have e.g. ```rust … ``` around them.
 /// If this block is fenced with backticks, strip them off (and the language name).
 /// We use this when copying code blocks to the clipboard.
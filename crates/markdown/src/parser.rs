@@ -1,8 +1,23 @@
+mod ansi;
+mod code_block_language;
+mod emoji;
+mod incremental;
+mod wiki_link;
+
+use ansi::AnsiParser;
+pub use ansi::{AnsiColor, AnsiHandling, AnsiStyle};
+pub use code_block_language::{CODE_BLOCK_LANGUAGE_ALIASES, code_block_language};
 use gpui::SharedString;
-use linkify::LinkFinder;
+pub use incremental::{IncrementalMarkdown, parse_markdown_incremental};
+use linkify::{LinkFinder, LinkKind};
+pub use pulldown_cmark::MetadataBlockKind;
 pub use pulldown_cmark::TagEnd as MarkdownTagEnd;
-use pulldown_cmark::{Alignment, HeadingLevel, LinkType, MetadataBlockKind, Options, Parser};
-use std::{collections::HashSet, ops::Range};
+use pulldown_cmark::{Alignment, HeadingLevel, LinkType, Options, Parser};
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    ops::Range,
+};
 
 const PARSE_OPTIONS: Options = Options::ENABLE_TABLES
     .union(Options::ENABLE_FOOTNOTES)
@@ -12,53 +27,211 @@ const PARSE_OPTIONS: Options = Options::ENABLE_TABLES
     .union(Options::ENABLE_HEADING_ATTRIBUTES)
     .union(Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS)
     .union(Options::ENABLE_OLD_FOOTNOTES)
-    .union(Options::ENABLE_GFM);
+    .union(Options::ENABLE_GFM)
+    .union(Options::ENABLE_MATH);
+
+/// Optional behavior of [`parse_markdown_with_options`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkdownParseOptions {
+    /// How ANSI escape sequences inside code blocks, e.g. from pasted terminal output, are handled.
+    pub code_block_ansi: AnsiHandling,
+    /// The URL schemes, in lowercase, that links may use. Autolinks with any other scheme are
+    /// left as text, and so is the text of explicit links to them. URLs without a scheme, such
+    /// as relative paths and anchors, are always allowed. All schemes are allowed when `None`.
+    pub allowed_link_schemes: Option<HashSet<SharedString>>,
+    /// The URL schemes, in lowercase, that URLs in plain text and `<...>` autolinks may use in
+    /// addition to `http` and `https`. Schemes that can run code, such as `javascript:`, are never
+    /// autolinked.
+    pub autolink_schemes: HashSet<SharedString>,
+    /// Whether to parse definition lists, i.e. a term followed by lines starting with `: `.
+    pub definition_lists: bool,
+    /// Whether a `---`-delimited block at the start of the document is YAML front matter,
+    /// rather than a thematic break and a heading.
+    pub yaml_metadata_blocks: bool,
+    /// When set, `[[Page]]` and `[[Page|alias]]` wiki links in text are turned into links to
+    /// this URL, with `{page}` replaced by the page name.
+    pub wiki_link_url_template: Option<SharedString>,
+    /// Whether to replace known `:shortcode:` sequences outside of code, such as `:rocket:`,
+    /// with their emoji.
+    pub emoji_shortcodes: bool,
+}
+
+impl MarkdownParseOptions {
+    fn parser_options(&self) -> Options {
+        let mut options = PARSE_OPTIONS;
+        if self.definition_lists {
+            options.insert(Options::ENABLE_DEFINITION_LIST);
+        }
+        if self.yaml_metadata_blocks {
+            options.insert(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
+        }
+        options
+    }
+
+    fn is_link_allowed(&self, url: &str) -> bool {
+        let Some(allowed_schemes) = &self.allowed_link_schemes else {
+            return true;
+        };
+        match url_scheme(url) {
+            Some(scheme) => allowed_schemes.contains(scheme.to_ascii_lowercase().as_str()),
+            None => true,
+        }
+    }
+
+    fn is_autolink_allowed(&self, url: &str) -> bool {
+        let Some(scheme) = url_scheme(url) else {
+            return false;
+        };
+        let scheme = scheme.to_ascii_lowercase();
+        if NEVER_AUTOLINKED_SCHEMES.contains(&scheme.as_str()) {
+            return false;
+        }
+        DEFAULT_AUTOLINK_SCHEMES.contains(&scheme.as_str())
+            || self.autolink_schemes.contains(scheme.as_str())
+    }
+}
+
+/// The schemes URLs are autolinked for regardless of [`MarkdownParseOptions::autolink_schemes`].
+const DEFAULT_AUTOLINK_SCHEMES: &[&str] = &["http", "https"];
+
+/// Schemes that are never autolinked, as following such links can run code.
+const NEVER_AUTOLINKED_SCHEMES: &[&str] = &["javascript", "vbscript", "data"];
+
+/// Returns the scheme of the URL, as defined by RFC 3986: a letter followed by
+/// letters, digits, `+`, `-` or `.`, up to the first `:`.
+fn url_scheme(url: &str) -> Option<&str> {
+    let (scheme, _) = url.trim_start().split_once(':')?;
+    let mut chars = scheme.chars();
+    let starts_with_letter = chars.next()?.is_ascii_alphabetic();
+    (starts_with_letter
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.')))
+    .then_some(scheme)
+}
 
 pub fn parse_markdown(text: &str) -> (Vec<(Range<usize>, MarkdownEvent)>, HashSet<SharedString>) {
+    parse_markdown_with_options(text, &MarkdownParseOptions::default())
+}
+
+pub fn parse_markdown_with_options(
+    text: &str,
+    options: &MarkdownParseOptions,
+) -> (Vec<(Range<usize>, MarkdownEvent)>, HashSet<SharedString>) {
+    let (mut events, languages) = parse_events(text, options);
+    assign_heading_ids(&mut events, text);
+    (events, languages)
+}
+
+/// Parses the text without giving headings ids, as they depend on the whole document.
+fn parse_events(
+    text: &str,
+    options: &MarkdownParseOptions,
+) -> (Vec<(Range<usize>, MarkdownEvent)>, HashSet<SharedString>) {
     let mut events = Vec::new();
     let mut languages = HashSet::new();
     let mut within_link = false;
-    let mut within_metadata = false;
-    for (pulldown_event, mut range) in Parser::new_ext(text, PARSE_OPTIONS).into_offset_iter() {
-        if within_metadata {
-            if let pulldown_cmark::Event::End(pulldown_cmark::TagEnd::MetadataBlock { .. }) =
-                pulldown_event
-            {
-                within_metadata = false;
+    let mut within_disallowed_link = false;
+    let mut metadata: Option<(MetadataBlockKind, String)> = None;
+    let mut code_block_ansi = None;
+    for (pulldown_event, mut range) in
+        Parser::new_ext(text, options.parser_options()).into_offset_iter()
+    {
+        if let Some((kind, content)) = metadata.as_mut() {
+            match pulldown_event {
+                pulldown_cmark::Event::Text(text) => content.push_str(&text),
+                pulldown_cmark::Event::End(pulldown_cmark::TagEnd::MetadataBlock(_)) => {
+                    events.push((
+                        range,
+                        MarkdownEvent::MetadataBlock {
+                            kind: *kind,
+                            content: SharedString::from(mem::take(content)),
+                        },
+                    ));
+                    metadata = None;
+                }
+                _ => {}
             }
             continue;
         }
         match pulldown_event {
             pulldown_cmark::Event::Start(tag) => {
                 match tag {
-                    pulldown_cmark::Tag::Link { .. } => within_link = true,
-                    pulldown_cmark::Tag::MetadataBlock { .. } => within_metadata = true,
-                    pulldown_cmark::Tag::CodeBlock(pulldown_cmark::CodeBlockKind::Fenced(
-                        ref language,
-                    )) => {
-                        languages.insert(SharedString::from(language.to_string()));
+                    pulldown_cmark::Tag::Link {
+                        link_type,
+                        ref dest_url,
+                        ..
+                    } => {
+                        within_link = true;
+                        if !options.is_link_allowed(dest_url)
+                            || (link_type == LinkType::Autolink
+                                && !options.is_autolink_allowed(dest_url))
+                        {
+                            within_disallowed_link = true;
+                            continue;
+                        }
+                    }
+                    pulldown_cmark::Tag::MetadataBlock(kind) => {
+                        metadata = Some((kind, String::new()));
+                        continue;
+                    }
+                    pulldown_cmark::Tag::CodeBlock(ref kind) => {
+                        if let pulldown_cmark::CodeBlockKind::Fenced(language) = kind {
+                            languages.insert(code_block_language(language));
+                        }
+                        if options.code_block_ansi != AnsiHandling::Verbatim {
+                            code_block_ansi = Some(AnsiParser::default());
+                        }
                     }
                     _ => {}
                 }
                 events.push((range, MarkdownEvent::Start(tag.into())))
             }
             pulldown_cmark::Event::End(tag) => {
-                if let pulldown_cmark::TagEnd::Link = tag {
-                    within_link = false;
+                match tag {
+                    pulldown_cmark::TagEnd::Link => {
+                        within_link = false;
+                        if within_disallowed_link {
+                            within_disallowed_link = false;
+                            continue;
+                        }
+                    }
+                    pulldown_cmark::TagEnd::CodeBlock => code_block_ansi = None,
+                    _ => {}
                 }
                 events.push((range, MarkdownEvent::End(tag)));
             }
+            pulldown_cmark::Event::Text(parsed) if code_block_ansi.is_some() => {
+                if let Some(ansi) = code_block_ansi.as_mut() {
+                    push_ansi_events(
+                        ansi,
+                        &parsed,
+                        range,
+                        options.code_block_ansi == AnsiHandling::Interpret,
+                        &mut events,
+                    );
+                }
+            }
             pulldown_cmark::Event::Text(parsed) => {
                 // Automatically detect links in text if we're not already within a markdown
                 // link.
                 let mut parsed = parsed.as_ref();
                 if !within_link {
-                    let mut finder = LinkFinder::new();
-                    finder.kinds(&[linkify::LinkKind::Url]);
                     let text_range = range.clone();
-                    for link in finder.links(&text[text_range.clone()]) {
-                        let link_range =
-                            text_range.start + link.start()..text_range.start + link.end();
+                    for link in autolink_finder().links(&text[text_range.clone()]) {
+                        let link_str = trim_trailing_punctuation(link.as_str());
+                        let (link_type, dest_url) = autolink_destination(link.kind(), link_str);
+                        if !options.is_link_allowed(&dest_url)
+                            || (link_type == LinkType::Autolink
+                                && !options.is_autolink_allowed(&dest_url))
+                        {
+                            continue;
+                        }
+
+                        let link_start = text_range.start + link.start();
+                        let link_range = link_start..link_start + link_str.len();
+                        // Never nest links, e.g. for an email in the query of a URL.
+                        if link_range.start < range.start {
+                            continue;
+                        }
 
                         if link_range.start > range.start {
                             let (text, tail) = parsed.split_at(link_range.start - range.start);
@@ -72,8 +245,8 @@ pub fn parse_markdown(text: &str) -> (Vec<(Range<usize>, MarkdownEvent)>, HashSe
                         events.push((
                             link_range.clone(),
                             MarkdownEvent::Start(MarkdownTag::Link {
-                                link_type: LinkType::Autolink,
-                                dest_url: SharedString::from(link.as_str().to_string()),
+                                link_type,
+                                dest_url,
                                 title: SharedString::default(),
                                 id: SharedString::default(),
                             }),
@@ -108,24 +281,256 @@ pub fn parse_markdown(text: &str) -> (Vec<(Range<usize>, MarkdownEvent)>, HashSe
             pulldown_cmark::Event::HardBreak => events.push((range, MarkdownEvent::HardBreak)),
             pulldown_cmark::Event::Rule => events.push((range, MarkdownEvent::Rule)),
             pulldown_cmark::Event::TaskListMarker(checked) => {
+                let range = task_list_marker_range(range, text);
                 events.push((range, MarkdownEvent::TaskListMarker(checked)))
             }
-            pulldown_cmark::Event::InlineMath(_) | pulldown_cmark::Event::DisplayMath(_) => {}
+            pulldown_cmark::Event::InlineMath(tex) => {
+                let range = math_source_range(range, &tex);
+                events.push((
+                    range,
+                    MarkdownEvent::InlineMath(SharedString::from(tex.to_string())),
+                ))
+            }
+            pulldown_cmark::Event::DisplayMath(tex) => {
+                let range = math_source_range(range, &tex);
+                events.push((
+                    range,
+                    MarkdownEvent::DisplayMath(SharedString::from(tex.to_string())),
+                ))
+            }
         }
     }
+    if let Some(url_template) = &options.wiki_link_url_template {
+        events = wiki_link::insert_wiki_links(events, text, url_template, options);
+    }
+    if options.emoji_shortcodes {
+        events = emoji::insert_emoji(events, text);
+    }
+    assign_image_alt_text(&mut events, text);
     (events, languages)
 }
 
+/// Sets the alt text of each image to the plain text between its start and end, dropping
+/// any formatting like emphasis.
+fn assign_image_alt_text(events: &mut [(Range<usize>, MarkdownEvent)], source: &str) {
+    let mut images: Vec<(usize, String)> = Vec::new();
+    for ix in 0..events.len() {
+        let (range, event) = &events[ix];
+        let text: &str = match event {
+            MarkdownEvent::Start(MarkdownTag::Image { .. }) => {
+                images.push((ix, String::new()));
+                continue;
+            }
+            MarkdownEvent::End(MarkdownTagEnd::Image) => {
+                let Some((start_ix, text)) = images.pop() else {
+                    continue;
+                };
+                if let MarkdownEvent::Start(MarkdownTag::Image { alt_text, .. }) =
+                    &mut events[start_ix].1
+                {
+                    *alt_text = SharedString::from(text);
+                }
+                continue;
+            }
+            MarkdownEvent::Text(text) | MarkdownEvent::InlineMath(text) => text,
+            MarkdownEvent::Code => &source[range.clone()],
+            MarkdownEvent::SoftBreak | MarkdownEvent::HardBreak => " ",
+            _ => continue,
+        };
+        // Nested images contribute their alt text to the enclosing ones.
+        for (_, alt_text) in &mut images {
+            alt_text.push_str(text);
+        }
+    }
+}
+
+/// Gives the headings without an explicit `{#id}` a GitHub-style slug of their text, so
+/// that intra-document links can point at them. Duplicates get a `-1`, `-2`, … suffix.
+fn assign_heading_ids(events: &mut [(Range<usize>, MarkdownEvent)], source: &str) {
+    let mut used_ids = events
+        .iter()
+        .filter_map(|(_, event)| match event {
+            MarkdownEvent::Start(MarkdownTag::Heading { id: Some(id), .. }) => Some(id.to_string()),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+    let mut heading: Option<(usize, String)> = None;
+    for ix in 0..events.len() {
+        let (range, event) = &events[ix];
+        match event {
+            MarkdownEvent::Start(MarkdownTag::Heading { id: None, .. }) => {
+                heading = Some((ix, String::new()));
+            }
+            MarkdownEvent::Text(text) | MarkdownEvent::InlineMath(text) => {
+                if let Some((_, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(text);
+                }
+            }
+            MarkdownEvent::Code => {
+                if let Some((_, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(&source[range.clone()]);
+                }
+            }
+            MarkdownEvent::End(MarkdownTagEnd::Heading(_)) => {
+                let Some((start_ix, heading_text)) = heading.take() else {
+                    continue;
+                };
+                let slug = heading_slug(&heading_text);
+                if slug.is_empty() {
+                    continue;
+                }
+                let mut id = slug.clone();
+                let mut suffix = 1;
+                while used_ids.contains(&id) {
+                    id = format!("{slug}-{suffix}");
+                    suffix += 1;
+                }
+                used_ids.insert(id.clone());
+                if let MarkdownEvent::Start(MarkdownTag::Heading { id: heading_id, .. }) =
+                    &mut events[start_ix].1
+                {
+                    *heading_id = Some(SharedString::from(id));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Lowercases the text, drops punctuation and turns spaces into hyphens, like GitHub does.
+fn heading_slug(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Narrows the range of a math event to the TeX source, excluding the `$` or `$$` delimiters.
+fn math_source_range(range: Range<usize>, tex: &str) -> Range<usize> {
+    let delimiter_len = range.len().saturating_sub(tex.len()) / 2;
+    range.start + delimiter_len..range.end - delimiter_len
+}
+
+/// Narrows the range of a task list marker to its brackets, e.g. `[ ]` or `[x]`.
+fn task_list_marker_range(range: Range<usize>, text: &str) -> Range<usize> {
+    let marker = &text[range.clone()];
+    match (marker.find('['), marker.rfind(']')) {
+        (Some(start), Some(end)) if start < end => range.start + start..range.start + end + 1,
+        _ => range,
+    }
+}
+
+fn push_ansi_events(
+    ansi: &mut AnsiParser,
+    parsed: &str,
+    range: Range<usize>,
+    interpret: bool,
+    events: &mut Vec<(Range<usize>, MarkdownEvent)>,
+) {
+    // Offsets within the parsed text only map onto the source when it was taken verbatim,
+    // which isn't the case for e.g. code blocks nested in indented containers.
+    let verbatim = parsed.len() == range.len();
+    for run in ansi.parse(parsed) {
+        let source_range = if verbatim {
+            range.start + run.range.start..range.start + run.range.end
+        } else {
+            range.clone()
+        };
+        let text = SharedString::new(&parsed[run.range]);
+        let event = if interpret {
+            MarkdownEvent::AnsiText {
+                text,
+                style: run.style,
+                link: run.link,
+            }
+        } else {
+            MarkdownEvent::Text(text)
+        };
+        events.push((source_range, event));
+    }
+}
+
+/// Counts the fenced code blocks of parsed markdown per language, as resolved by
+/// [`code_block_language`]. Blocks without a language are counted as `plaintext`.
+pub fn code_block_language_histogram(
+    events: &[(Range<usize>, MarkdownEvent)],
+) -> HashMap<SharedString, usize> {
+    let mut histogram = HashMap::new();
+    for (_, event) in events {
+        if let MarkdownEvent::Start(MarkdownTag::CodeBlock(CodeBlockKind::Fenced(info))) = event {
+            let mut language = code_block_language(info);
+            if language.is_empty() {
+                language = SharedString::new_static("plaintext");
+            }
+            *histogram.entry(language).or_default() += 1;
+        }
+    }
+    histogram
+}
+
+/// Finds the URLs and email addresses in plain text.
+fn autolink_finder() -> LinkFinder {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url, LinkKind::Email]);
+    finder
+}
+
+/// Returns the link type and destination of a link found by [`autolink_finder`], with
+/// email addresses pointing at a `mailto:` URL.
+fn autolink_destination(kind: &LinkKind, link: &str) -> (LinkType, SharedString) {
+    match kind {
+        LinkKind::Email => (
+            LinkType::Email,
+            SharedString::from(format!("mailto:{link}")),
+        ),
+        _ => (LinkType::Autolink, SharedString::from(link.to_string())),
+    }
+}
+
+/// Trims punctuation that more likely belongs to the surrounding prose than to an
+/// autolinked URL, like the `).` in `(see https://zed.dev).`. Closing brackets are kept
+/// when they balance an opening one in the URL, as in Wikipedia links.
+fn trim_trailing_punctuation(link: &str) -> &str {
+    let mut link = link;
+    while let Some(last) = link.chars().next_back() {
+        let trim = match last {
+            '.' | ',' | ':' | ';' | '!' | '?' | '\'' | '"' => true,
+            ')' => link.matches('(').count() < link.matches(')').count(),
+            ']' => link.matches('[').count() < link.matches(']').count(),
+            _ => false,
+        };
+        if !trim {
+            break;
+        }
+        link = &link[..link.len() - last.len_utf8()];
+    }
+    link
+}
+
 pub fn parse_links_only(mut text: &str) -> Vec<(Range<usize>, MarkdownEvent)> {
+    let options = MarkdownParseOptions::default();
     let mut events = Vec::new();
-    let mut finder = LinkFinder::new();
-    finder.kinds(&[linkify::LinkKind::Url]);
     let mut text_range = Range {
         start: 0,
         end: text.len(),
     };
-    for link in finder.links(text) {
-        let link_range = link.start()..link.end();
+    for link in autolink_finder().links(text) {
+        let link_str = trim_trailing_punctuation(link.as_str());
+        let link_range = link.start()..link.start() + link_str.len();
+        // Never nest links, e.g. for an email in the query of a URL.
+        if link_range.start < text_range.start {
+            continue;
+        }
+        let (link_type, dest_url) = autolink_destination(link.kind(), link_str);
+        if link_type == LinkType::Autolink && !options.is_autolink_allowed(&dest_url) {
+            continue;
+        }
 
         if link_range.start > text_range.start {
             let (head, tail) = text.split_at(link_range.start - text_range.start);
@@ -140,8 +545,8 @@ pub fn parse_links_only(mut text: &str) -> Vec<(Range<usize>, MarkdownEvent)> {
         events.push((
             link_range.clone(),
             MarkdownEvent::Start(MarkdownTag::Link {
-                link_type: LinkType::Autolink,
-                dest_url: SharedString::from(link.as_str().to_string()),
+                link_type,
+                dest_url,
                 title: SharedString::default(),
                 id: SharedString::default(),
             }),
@@ -192,7 +597,32 @@ pub enum MarkdownEvent {
     /// A horizontal ruler.
     Rule,
     /// A task list marker, rendered as a checkbox in HTML. Contains a true when it is checked.
+    ///
+    /// The event's range covers exactly the `[ ]` or `[x]` marker, so a renderer can toggle
+    /// the checkbox by replacing the character at `range.start + 1` with `x` or a space.
     TaskListMarker(bool),
+    /// Inline math delimited by `$`, containing the raw TeX source.
+    InlineMath(SharedString),
+    /// Display math delimited by `$$`, containing the raw TeX source.
+    DisplayMath(SharedString),
+    /// A front matter block at the start of the document, delimited by `+++` (TOML) or, when
+    /// [enabled](MarkdownParseOptions::yaml_metadata_blocks), `---` (YAML). It isn't rendered,
+    /// but lets callers read e.g. a title or tags.
+    MetadataBlock {
+        kind: MetadataBlockKind,
+        /// The raw text between the delimiters.
+        content: SharedString,
+    },
+    /// Text replacing the source in its range, such as the emoji of a `:shortcode:`.
+    SubstitutedText(SharedString),
+    /// A run of code block text styled by ANSI escape sequences, emitted instead of `Text`
+    /// when [`AnsiHandling::Interpret`] is enabled.
+    AnsiText {
+        text: SharedString,
+        style: AnsiStyle,
+        /// The target of an OSC 8 hyperlink wrapping the text.
+        link: Option<SharedString>,
+    },
 }
 
 /// Tags for elements that can contain other elements.
@@ -265,6 +695,8 @@ pub enum MarkdownTag {
         title: SharedString,
         /// Identifier of reference links, e.g. `world` in the link `[hello][world]`.
         id: SharedString,
+        /// The plain text of the image's description, e.g. `alt` in `![alt](image.png)`.
+        alt_text: SharedString,
     },
 
     /// A metadata block.
@@ -355,6 +787,7 @@ impl From<pulldown_cmark::Tag<'_>> for MarkdownTag {
                 dest_url: SharedString::from(dest_url.into_string()),
                 title: SharedString::from(title.into_string()),
                 id: SharedString::from(id.into_string()),
+                alt_text: SharedString::default(),
             },
             pulldown_cmark::Tag::HtmlBlock => MarkdownTag::HtmlBlock,
             pulldown_cmark::Tag::MetadataBlock(kind) => MarkdownTag::MetadataBlock(kind),
@@ -369,22 +802,839 @@ impl From<pulldown_cmark::Tag<'_>> for MarkdownTag {
 mod tests {
     use super::*;
 
-    const UNWANTED_OPTIONS: Options = Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
-        .union(Options::ENABLE_MATH)
-        .union(Options::ENABLE_DEFINITION_LIST);
+    /// Options that are only enabled when requested through [`MarkdownParseOptions`].
+    const OPTIONAL_OPTIONS: Options =
+        Options::ENABLE_DEFINITION_LIST.union(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
 
     #[test]
     fn all_options_considered() {
         // The purpose of this is to fail when new options are added to pulldown_cmark, so that they
         // can be evaluated for inclusion.
-        assert_eq!(PARSE_OPTIONS.union(UNWANTED_OPTIONS), Options::all());
+        assert_eq!(PARSE_OPTIONS.union(OPTIONAL_OPTIONS), Options::all());
     }
 
     #[test]
-    fn wanted_and_unwanted_options_disjoint() {
+    fn default_and_optional_options_disjoint() {
         assert_eq!(
-            PARSE_OPTIONS.intersection(UNWANTED_OPTIONS),
+            PARSE_OPTIONS.intersection(OPTIONAL_OPTIONS),
             Options::empty()
         );
     }
+
+    fn parse_with_ansi(
+        text: &str,
+        code_block_ansi: AnsiHandling,
+    ) -> Vec<(Range<usize>, MarkdownEvent)> {
+        let options = MarkdownParseOptions {
+            code_block_ansi,
+            ..Default::default()
+        };
+        parse_markdown_with_options(text, &options)
+            .0
+            .into_iter()
+            .filter(|(_, event)| {
+                matches!(
+                    event,
+                    MarkdownEvent::Text(_) | MarkdownEvent::AnsiText { .. }
+                )
+            })
+            .collect()
+    }
+
+    fn ansi_text(text: &str, style: AnsiStyle) -> MarkdownEvent {
+        MarkdownEvent::AnsiText {
+            text: text.into(),
+            style,
+            link: None,
+        }
+    }
+
+    #[test]
+    fn test_code_block_ansi_verbatim_by_default() {
+        let text = "```\n\x1b[31mred\x1b[0m\n```";
+        assert_eq!(
+            parse_with_ansi(text, AnsiHandling::Verbatim),
+            vec![(4..17, MarkdownEvent::Text("\x1b[31mred\x1b[0m\n".into()))]
+        );
+    }
+
+    #[test]
+    fn test_code_block_ansi_strip() {
+        let text = "```\n\x1b[31mred\x1b[0m\n```";
+        assert_eq!(
+            parse_with_ansi(text, AnsiHandling::Strip),
+            vec![
+                (9..12, MarkdownEvent::Text("red".into())),
+                (16..17, MarkdownEvent::Text("\n".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_block_ansi_interpret() {
+        let text = "```\n\x1b[1;32mok\x1b[22m plain\x1b[0m \x1b[91;4mbright\x1b[m\n```";
+        let green = Some(AnsiColor::Indexed(2));
+        assert_eq!(
+            parse_with_ansi(text, AnsiHandling::Interpret),
+            vec![
+                (
+                    11..13,
+                    ansi_text(
+                        "ok",
+                        AnsiStyle {
+                            foreground: green,
+                            bold: true,
+                            ..Default::default()
+                        }
+                    )
+                ),
+                (
+                    18..24,
+                    ansi_text(
+                        " plain",
+                        AnsiStyle {
+                            foreground: green,
+                            ..Default::default()
+                        }
+                    )
+                ),
+                (28..29, ansi_text(" ", AnsiStyle::default())),
+                (
+                    36..42,
+                    ansi_text(
+                        "bright",
+                        AnsiStyle {
+                            foreground: Some(AnsiColor::Indexed(9)),
+                            underline: true,
+                            ..Default::default()
+                        }
+                    )
+                ),
+                (45..46, ansi_text("\n", AnsiStyle::default())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_block_ansi_extended_colors() {
+        let text = "```\n\x1b[38;5;208;48;2;10;20;30mwarm\x1b[39;49;3mplain\n```";
+        assert_eq!(
+            parse_with_ansi(text, AnsiHandling::Interpret)
+                .into_iter()
+                .map(|(_, event)| event)
+                .collect::<Vec<_>>(),
+            vec![
+                ansi_text(
+                    "warm",
+                    AnsiStyle {
+                        foreground: Some(AnsiColor::Indexed(208)),
+                        background: Some(AnsiColor::Rgb(10, 20, 30)),
+                        ..Default::default()
+                    }
+                ),
+                ansi_text(
+                    "plain\n",
+                    AnsiStyle {
+                        italic: true,
+                        ..Default::default()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_block_ansi_hyperlinks() {
+        let text = "```\nsee \x1b]8;;https://zed.dev\x1b\\Zed\x1b]8;;\x07!\n```";
+        assert_eq!(
+            parse_with_ansi(text, AnsiHandling::Interpret)
+                .into_iter()
+                .map(|(_, event)| event)
+                .collect::<Vec<_>>(),
+            vec![
+                ansi_text("see ", AnsiStyle::default()),
+                MarkdownEvent::AnsiText {
+                    text: "Zed".into(),
+                    style: AnsiStyle::default(),
+                    link: Some("https://zed.dev".into()),
+                },
+                ansi_text("!\n", AnsiStyle::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ansi_outside_code_blocks_untouched() {
+        let text = "\x1b[31mred\x1b[0m";
+        let mut parsed = String::new();
+        for (_, event) in parse_with_ansi(text, AnsiHandling::Interpret) {
+            let MarkdownEvent::Text(text) = event else {
+                panic!("unexpected event {event:?}");
+            };
+            parsed.push_str(&text);
+        }
+        assert_eq!(parsed, text);
+    }
+
+    fn parse_with_allowed_schemes(text: &str, schemes: &[&str]) -> Vec<MarkdownEvent> {
+        let options = MarkdownParseOptions {
+            allowed_link_schemes: Some(
+                schemes
+                    .iter()
+                    .map(|scheme| SharedString::from(scheme.to_string()))
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        parse_markdown_with_options(text, &options)
+            .0
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect()
+    }
+
+    fn link_urls(events: &[MarkdownEvent]) -> Vec<&str> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                MarkdownEvent::Start(MarkdownTag::Link { dest_url, .. }) => Some(dest_url.as_ref()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_allowed_link_schemes() {
+        let text = "see https://zed.dev and [docs](HTTP://zed.dev/docs) or [readme](README.md)";
+        let events = parse_with_allowed_schemes(text, &["http", "https"]);
+        assert_eq!(
+            link_urls(&events),
+            ["https://zed.dev", "HTTP://zed.dev/docs", "README.md"]
+        );
+    }
+
+    #[test]
+    fn test_disallowed_link_schemes() {
+        let text = "[click](javascript:alert(1)) file:///etc/passwd <ftp://example.com>";
+        let events = parse_with_allowed_schemes(text, &["https"]);
+        assert!(link_urls(&events).is_empty());
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, MarkdownEvent::End(MarkdownTagEnd::Link)))
+        );
+
+        // The text of the blocked links is kept.
+        let text_content = events
+            .iter()
+            .filter_map(|event| match event {
+                MarkdownEvent::Text(text) => Some(text.as_ref()),
+                _ => None,
+            })
+            .collect::<String>();
+        assert_eq!(text_content, "click file:///etc/passwd ftp://example.com");
+    }
+
+    fn parse_with_autolink_schemes(text: &str, schemes: &[&str]) -> Vec<MarkdownEvent> {
+        let options = MarkdownParseOptions {
+            autolink_schemes: schemes
+                .iter()
+                .map(|scheme| SharedString::from(scheme.to_string()))
+                .collect(),
+            ..Default::default()
+        };
+        parse_markdown_with_options(text, &options)
+            .0
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect()
+    }
+
+    #[test]
+    fn test_autolink_schemes() {
+        let text = "see file:///home/notes.md, ftp://example.com/a.txt and https://zed.dev";
+        assert_eq!(
+            link_urls(&parse_with_autolink_schemes(text, &[])),
+            ["https://zed.dev"]
+        );
+        assert_eq!(
+            link_urls(&parse_with_autolink_schemes(text, &["file", "ftp"])),
+            [
+                "file:///home/notes.md",
+                "ftp://example.com/a.txt",
+                "https://zed.dev"
+            ]
+        );
+        assert_eq!(
+            link_urls(&parse_with_autolink_schemes(
+                "<ftp://example.com>",
+                &["ftp"]
+            )),
+            ["ftp://example.com"]
+        );
+    }
+
+    #[test]
+    fn test_dangerous_schemes_never_autolinked() {
+        let text = "javascript://alert(1) <javascript:alert(1)>";
+        assert!(link_urls(&parse_with_autolink_schemes(text, &[])).is_empty());
+        assert!(link_urls(&parse_with_autolink_schemes(text, &["javascript"])).is_empty());
+    }
+
+    #[test]
+    fn test_autolink_trailing_punctuation() {
+        assert_eq!(
+            parse_markdown("(see https://zed.dev).").0,
+            vec![
+                (0..22, MarkdownEvent::Start(MarkdownTag::Paragraph)),
+                (0..5, MarkdownEvent::Text("(see ".into())),
+                (
+                    5..20,
+                    MarkdownEvent::Start(MarkdownTag::Link {
+                        link_type: LinkType::Autolink,
+                        dest_url: "https://zed.dev".into(),
+                        title: SharedString::default(),
+                        id: SharedString::default(),
+                    })
+                ),
+                (5..20, MarkdownEvent::Text("https://zed.dev".into())),
+                (5..20, MarkdownEvent::End(MarkdownTagEnd::Link)),
+                (20..22, MarkdownEvent::Text(").".into())),
+                (0..22, MarkdownEvent::End(MarkdownTagEnd::Paragraph)),
+            ]
+        );
+        assert_eq!(
+            link_urls(&parse_with_autolink_schemes("https://zed.dev, or not", &[])),
+            ["https://zed.dev"]
+        );
+    }
+
+    #[test]
+    fn test_autolink_balanced_parens() {
+        let text = "See https://en.wikipedia.org/wiki/Rust_(programming_language).";
+        let events = parse_with_autolink_schemes(text, &[]);
+        assert_eq!(
+            link_urls(&events),
+            ["https://en.wikipedia.org/wiki/Rust_(programming_language)"]
+        );
+        assert_eq!(
+            events.last(),
+            Some(&MarkdownEvent::End(MarkdownTagEnd::Paragraph))
+        );
+        assert_eq!(events[events.len() - 2], MarkdownEvent::Text(".".into()));
+
+        assert_eq!(
+            parse_links_only("(https://en.wikipedia.org/wiki/Rust_(programming_language))")
+                .into_iter()
+                .map(|(range, _)| range)
+                .collect::<Vec<_>>(),
+            [0..1, 1..58, 1..58, 1..58, 58..59]
+        );
+    }
+
+    #[test]
+    fn test_task_list_marker_range() {
+        let text = "- [ ] todo\n- [x] done\n  - [X] nested";
+        let markers = parse_markdown(text)
+            .0
+            .into_iter()
+            .filter_map(|(range, event)| match event {
+                MarkdownEvent::TaskListMarker(checked) => Some((checked, &text[range])),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(markers, [(false, "[ ]"), (true, "[x]"), (true, "[X]")]);
+    }
+
+    #[test]
+    fn test_image_alt_text() {
+        let alt_texts = |text: &str| {
+            parse_markdown(text)
+                .0
+                .into_iter()
+                .filter_map(|(_, event)| match event {
+                    MarkdownEvent::Start(MarkdownTag::Image { alt_text, .. }) => Some(alt_text),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(alt_texts("![a cat](cat.png)"), ["a cat"]);
+        assert_eq!(alt_texts("![](empty.png)"), [""]);
+        assert_eq!(
+            alt_texts("![a *very* `fast` **cat**](cat.png \"Title\")"),
+            ["a very fast cat"]
+        );
+    }
+
+    #[test]
+    fn test_math() {
+        let text = "$a+b$ and $$\\int$$";
+        let math_events = parse_markdown(text)
+            .0
+            .into_iter()
+            .filter(|(_, event)| {
+                matches!(
+                    event,
+                    MarkdownEvent::InlineMath(_) | MarkdownEvent::DisplayMath(_)
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            math_events,
+            [
+                (1..4, MarkdownEvent::InlineMath("a+b".into())),
+                (12..16, MarkdownEvent::DisplayMath("\\int".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toml_metadata_block() {
+        let text = "+++\ntitle = \"Notes\"\ntags = [\"math\"]\n+++\n# Body\n";
+        let events = parse_markdown(text).0;
+        assert_eq!(
+            events[0].1,
+            MarkdownEvent::MetadataBlock {
+                kind: MetadataBlockKind::PlusesStyle,
+                content: "title = \"Notes\"\ntags = [\"math\"]\n".into(),
+            }
+        );
+        assert_eq!(
+            events[1].1,
+            MarkdownEvent::Start(MarkdownTag::Heading {
+                level: HeadingLevel::H1,
+                id: Some("body".into()),
+                classes: Vec::new(),
+                attrs: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_yaml_metadata_block() {
+        let text = "---\ntitle: Notes\n---\nBody\n";
+        let events = parse_markdown(text).0;
+        assert!(
+            !events
+                .iter()
+                .any(|(_, event)| matches!(event, MarkdownEvent::MetadataBlock { .. })),
+            "YAML front matter is only parsed when requested"
+        );
+
+        let options = MarkdownParseOptions {
+            yaml_metadata_blocks: true,
+            ..Default::default()
+        };
+        let events = parse_markdown_with_options(text, &options).0;
+        assert_eq!(
+            events[0].1,
+            MarkdownEvent::MetadataBlock {
+                kind: MetadataBlockKind::YamlStyle,
+                content: "title: Notes\n".into(),
+            }
+        );
+        assert_eq!(events[1].1, MarkdownEvent::Start(MarkdownTag::Paragraph));
+    }
+
+    #[test]
+    fn test_definition_lists() {
+        let text = "Term\n: Definition\n";
+        let is_definition_list = |event: &MarkdownEvent| {
+            matches!(
+                event,
+                MarkdownEvent::Start(MarkdownTag::DefinitionList)
+                    | MarkdownEvent::Start(MarkdownTag::DefinitionListTitle)
+                    | MarkdownEvent::Start(MarkdownTag::DefinitionListDefinition)
+            )
+        };
+
+        let (events, _) = parse_markdown(text);
+        assert!(!events.iter().any(|(_, event)| is_definition_list(event)));
+
+        let options = MarkdownParseOptions {
+            definition_lists: true,
+            ..Default::default()
+        };
+        let (events, _) = parse_markdown_with_options(text, &options);
+        assert_eq!(
+            events
+                .into_iter()
+                .filter(|(_, event)| is_definition_list(event)
+                    || matches!(event, MarkdownEvent::Text(_)))
+                .map(|(_, event)| event)
+                .collect::<Vec<_>>(),
+            [
+                MarkdownEvent::Start(MarkdownTag::DefinitionList),
+                MarkdownEvent::Start(MarkdownTag::DefinitionListTitle),
+                MarkdownEvent::Text("Term".into()),
+                MarkdownEvent::Start(MarkdownTag::DefinitionListDefinition),
+                MarkdownEvent::Text("Definition".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_block_language_histogram() {
+        let text = "```rust\nfn main() {}\n```\n\n```python\nprint()\n```\n\n\
+            ```rs\nstruct Foo;\n```\n\n```Rust\nenum Bar {}\n```\n\n```\nplain\n```\n\n    indented\n";
+        assert_eq!(
+            code_block_language_histogram(&parse_markdown(text).0),
+            HashMap::from_iter([
+                (SharedString::from("rust"), 3),
+                (SharedString::from("python"), 1),
+                (SharedString::from("plaintext"), 1),
+            ])
+        );
+        assert!(code_block_language_histogram(&parse_markdown("no code here").0).is_empty());
+    }
+
+    fn parse_with_wiki_links(text: &str) -> Vec<MarkdownEvent> {
+        let options = MarkdownParseOptions {
+            wiki_link_url_template: Some("notes://{page}".into()),
+            ..Default::default()
+        };
+        parse_markdown_with_options(text, &options)
+            .0
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect()
+    }
+
+    fn link_texts(events: &[MarkdownEvent]) -> Vec<&str> {
+        let mut within_link = false;
+        let mut texts = Vec::new();
+        for event in events {
+            match event {
+                MarkdownEvent::Start(MarkdownTag::Link { .. }) => within_link = true,
+                MarkdownEvent::End(MarkdownTagEnd::Link) => within_link = false,
+                MarkdownEvent::Text(text) if within_link => texts.push(text.as_ref()),
+                _ => {}
+            }
+        }
+        texts
+    }
+
+    #[test]
+    fn test_wiki_links() {
+        let text = "See [[Page Name]], [[Page|the alias]] and [regular](https://zed.dev).";
+        let events = parse_with_wiki_links(text);
+        assert_eq!(
+            link_urls(&events),
+            ["notes://Page%20Name", "notes://Page", "https://zed.dev"]
+        );
+        assert_eq!(link_texts(&events), ["Page Name", "the alias", "regular"]);
+
+        // The text around the links is kept.
+        let text_content = events
+            .iter()
+            .filter_map(|event| match event {
+                MarkdownEvent::Text(text) => Some(text.as_ref()),
+                _ => None,
+            })
+            .collect::<String>();
+        assert_eq!(text_content, "See Page Name, the alias and regular.");
+
+        // Wiki links are only parsed when enabled.
+        let (events, _) = parse_markdown("[[Page]]");
+        assert!(
+            !events
+                .iter()
+                .any(|(_, event)| matches!(event, MarkdownEvent::Start(MarkdownTag::Link { .. })))
+        );
+    }
+
+    #[test]
+    fn test_nested_and_escaped_wiki_links() {
+        assert_eq!(
+            link_urls(&parse_with_wiki_links("[[a [[b]] c]]")),
+            ["notes://b"]
+        );
+        assert_eq!(
+            link_urls(&parse_with_wiki_links("[[[Page]]]")),
+            ["notes://Page"]
+        );
+        assert!(link_urls(&parse_with_wiki_links("\\[[Escaped]]")).is_empty());
+        assert!(link_urls(&parse_with_wiki_links("[[Escaped\\]]")).is_empty());
+        assert!(link_urls(&parse_with_wiki_links("[[]] and [[ | alias]]")).is_empty());
+        assert!(link_urls(&parse_with_wiki_links("`[[Code]]`")).is_empty());
+        assert!(link_urls(&parse_with_wiki_links("```\n[[Code]]\n```")).is_empty());
+        assert_eq!(
+            link_urls(&parse_with_wiki_links("[[[Inner]](https://zed.dev)]]")),
+            ["https://zed.dev"]
+        );
+    }
+
+    #[test]
+    fn test_email_links() {
+        let events = parse_links_only("foo@bar.com")
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [
+                MarkdownEvent::Start(MarkdownTag::Link {
+                    link_type: LinkType::Email,
+                    dest_url: "mailto:foo@bar.com".into(),
+                    title: SharedString::default(),
+                    id: SharedString::default(),
+                }),
+                MarkdownEvent::Text("foo@bar.com".into()),
+                MarkdownEvent::End(MarkdownTagEnd::Link),
+            ]
+        );
+
+        let events = parse_markdown("Mail foo@bar.com.").0;
+        let events = events
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(link_urls(&events), ["mailto:foo@bar.com"]);
+    }
+
+    #[test]
+    fn test_email_links_mixed_with_urls() {
+        let text = "see https://zed.dev/?to=foo@bar.com or write to foo@bar.com";
+        let expected_urls = ["https://zed.dev/?to=foo@bar.com", "mailto:foo@bar.com"];
+
+        let events = parse_links_only(text)
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(link_urls(&events), expected_urls);
+        assert_eq!(link_depths(&events), Some(1));
+
+        let events = parse_markdown(text).0;
+        let events = events
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(link_urls(&events), expected_urls);
+        assert_eq!(link_depths(&events), Some(1));
+    }
+
+    /// Returns the maximum nesting of links, or `None` if they are unbalanced.
+    fn link_depths(events: &[MarkdownEvent]) -> Option<usize> {
+        let mut depth = 0_usize;
+        let mut max_depth = 0;
+        for event in events {
+            match event {
+                MarkdownEvent::Start(MarkdownTag::Link { .. }) => {
+                    depth += 1;
+                    max_depth = max_depth.max(depth);
+                }
+                MarkdownEvent::End(MarkdownTagEnd::Link) => depth = depth.checked_sub(1)?,
+                _ => {}
+            }
+        }
+        (depth == 0).then_some(max_depth)
+    }
+
+    #[test]
+    fn test_duplicate_heading_ids() {
+        let text = "# Intro\n## Intro\n# Setup {#intro-1}\n### Intro\n";
+        assert_eq!(
+            heading_ids(text),
+            [
+                Some("intro".into()),
+                Some("intro-2".into()),
+                Some("intro-1".into()),
+                Some("intro-3".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heading_ids_with_punctuation_and_code() {
+        let text =
+            "# What's new in v0.2?\n## The `parse_markdown` API!\n## Émojis & ünïcode\n# ?!\n";
+        assert_eq!(
+            heading_ids(text),
+            [
+                Some("whats-new-in-v02".into()),
+                Some("the-parse_markdown-api".into()),
+                Some("émojis--ünïcode".into()),
+                None,
+            ]
+        );
+    }
+
+    fn heading_ids(text: &str) -> Vec<Option<SharedString>> {
+        parse_markdown(text)
+            .0
+            .into_iter()
+            .filter_map(|(_, event)| match event {
+                MarkdownEvent::Start(MarkdownTag::Heading { id, .. }) => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_emoji_shortcodes() {
+        let text = "Ship it :rocket::fire: at 12:30 :not_an_emoji: :+1:";
+        assert_eq!(
+            parse_with_emoji(text),
+            [
+                (0..51, MarkdownEvent::Start(MarkdownTag::Paragraph)),
+                (0..8, MarkdownEvent::Text("Ship it ".into())),
+                (8..16, MarkdownEvent::SubstitutedText("🚀".into())),
+                (16..22, MarkdownEvent::SubstitutedText("🔥".into())),
+                (
+                    22..47,
+                    MarkdownEvent::Text(" at 12:30 :not_an_emoji: ".into())
+                ),
+                (47..51, MarkdownEvent::SubstitutedText("👍".into())),
+                (0..51, MarkdownEvent::End(MarkdownTagEnd::Paragraph)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_emoji_shortcodes_in_code() {
+        let text = "`:rocket:` :white_check_mark:\n\n```\n:rocket:\n```\n";
+        let events = parse_with_emoji(text)
+            .into_iter()
+            .map(|(_, event)| event)
+            .filter(|event| {
+                matches!(
+                    event,
+                    MarkdownEvent::Code
+                        | MarkdownEvent::Text(_)
+                        | MarkdownEvent::SubstitutedText(_)
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [
+                MarkdownEvent::Code,
+                MarkdownEvent::Text(" ".into()),
+                MarkdownEvent::SubstitutedText("✅".into()),
+                MarkdownEvent::Text(":rocket:\n".into()),
+            ]
+        );
+        assert_eq!(
+            parse_markdown(":rocket:").0[1].1,
+            MarkdownEvent::Text(":rocket:".into())
+        );
+    }
+
+    fn parse_with_emoji(text: &str) -> Vec<(Range<usize>, MarkdownEvent)> {
+        let options = MarkdownParseOptions {
+            emoji_shortcodes: true,
+            ..MarkdownParseOptions::default()
+        };
+        parse_markdown_with_options(text, &options).0
+    }
+
+    #[test]
+    fn test_incremental_parse_matches_full_parse() {
+        const SNIPPETS: &[&str] = &[
+            "\n",
+            "\n\n",
+            " ",
+            "    ",
+            "text",
+            "# ",
+            "## Title",
+            "Title\n===\n",
+            "---",
+            "***",
+            "```",
+            "```rust\n",
+            "~~~",
+            "- ",
+            "* item",
+            "1. ",
+            "> ",
+            "|a|b|\n|-|-|\n",
+            "|c|d|",
+            "*",
+            "**",
+            "_",
+            "`",
+            "[x](https://zed.dev)",
+            "https://zed.dev",
+            "<div>",
+            "</div>",
+            "<!--",
+            "-->",
+            "$x$",
+            "$$",
+            "- [ ] ",
+            "+++\na = 1\n+++\n",
+            "---\nb: 2\n---\n",
+            "\\",
+        ];
+        // A small xorshift generator, so that failures are reproducible.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound as u64) as usize
+        };
+
+        for _ in 0..50 {
+            let mut text = String::new();
+            for _ in 0..next(20) {
+                text.push_str(SNIPPETS[next(SNIPPETS.len())]);
+            }
+            let mut parse = IncrementalMarkdown::new(&text);
+            for _ in 0..20 {
+                let start = next(text.len() + 1);
+                let end = start + next(text.len() - start + 1).min(8);
+                let inserted = if next(3) == 0 {
+                    ""
+                } else {
+                    SNIPPETS[next(SNIPPETS.len())]
+                };
+                let mut new_text = text.clone();
+                new_text.replace_range(start..end, inserted);
+
+                parse = parse_markdown_incremental(&parse, &new_text, start..end);
+                let (events, languages) = parse_markdown(&new_text);
+                assert_eq!(
+                    parse.events(),
+                    events.as_slice(),
+                    "replacing {:?} of {text:?} with {inserted:?}",
+                    start..end
+                );
+                assert_eq!(parse.languages(), &languages);
+                text = new_text;
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_parse_reuses_trailing_blocks() {
+        let text = "# One\n\nSome text\n\n```\ncode\n```\n\n# Two\n";
+        let parse = IncrementalMarkdown::new(text);
+
+        let new_text = text.replace("Some text", "Some more text");
+        let parse = parse_markdown_incremental(&parse, &new_text, 12..12);
+        assert_eq!(parse.events(), parse_markdown(&new_text).0.as_slice());
+
+        // Opening a fence swallows the blocks that follow it.
+        let newer_text = new_text.replace("Some more text", "```\nSome more text");
+        let parse = parse_markdown_incremental(&parse, &newer_text, 7..7);
+        assert_eq!(parse.events(), parse_markdown(&newer_text).0.as_slice());
+    }
+
+    #[test]
+    fn test_code_block_language_aliases() {
+        assert_eq!(code_block_language("rs"), "rust");
+        assert_eq!(code_block_language("js"), "javascript");
+        assert_eq!(code_block_language("TS"), "typescript");
+        assert_eq!(code_block_language(" py "), "python");
+        assert_eq!(code_block_language("sh"), "shell script");
+        assert_eq!(code_block_language("Rust"), "rust");
+        assert_eq!(code_block_language("unknown"), "unknown");
+        assert_eq!(code_block_language(""), "");
+
+        let (_, languages) = parse_markdown("```rs\nfn main() {}\n```\n\n```rust\n```\n");
+        assert_eq!(languages, HashSet::from_iter([SharedString::from("rust")]));
+    }
 }
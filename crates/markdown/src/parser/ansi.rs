@@ -0,0 +1,189 @@
+use std::ops::Range;
+
+use gpui::SharedString;
+
+/// How ANSI escape sequences found inside code blocks are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnsiHandling {
+    /// Leave escape sequences in the text untouched.
+    #[default]
+    Verbatim,
+    /// Remove escape sequences, keeping only the plain text.
+    Strip,
+    /// Remove escape sequences and report the styling and hyperlinks they
+    /// describe as [`MarkdownEvent::AnsiText`](super::MarkdownEvent::AnsiText) events.
+    Interpret,
+}
+
+/// A color set by an SGR escape sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnsiColor {
+    /// One of the 256 indexed terminal colors. The first 16 are the standard
+    /// and bright colors of the terminal palette.
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+/// Text attributes set by SGR escape sequences.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    pub foreground: Option<AnsiColor>,
+    pub background: Option<AnsiColor>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+/// A run of visible text between escape sequences.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AnsiRun {
+    /// The range of the run within the parsed text.
+    pub range: Range<usize>,
+    pub style: AnsiStyle,
+    /// The target of the OSC 8 hyperlink the run is part of, if any.
+    pub link: Option<SharedString>,
+}
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+
+/// Splits text into styled runs, keeping the style and hyperlink state across
+/// calls so that sequences affect all the following text of a code block.
+#[derive(Default)]
+pub(crate) struct AnsiParser {
+    style: AnsiStyle,
+    link: Option<SharedString>,
+}
+
+impl AnsiParser {
+    pub fn parse(&mut self, text: &str) -> Vec<AnsiRun> {
+        let mut runs = Vec::new();
+        let mut run_start = 0;
+        let mut chars = text.char_indices().peekable();
+        while let Some((ix, ch)) = chars.next() {
+            if ch != ESC {
+                continue;
+            }
+
+            if run_start < ix {
+                runs.push(self.run(run_start..ix));
+            }
+
+            match chars.next() {
+                // Control Sequence Introducer: parameters and intermediates followed by a final byte.
+                Some((_, '[')) => {
+                    let params_start = ix + 2;
+                    let mut sequence_end = text.len();
+                    for (ix, ch) in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&ch) {
+                            if ch == 'm' {
+                                self.apply_sgr(&text[params_start..ix]);
+                            }
+                            sequence_end = ix + 1;
+                            break;
+                        }
+                    }
+                    run_start = sequence_end;
+                }
+                // Operating System Command, terminated by BEL or ESC \.
+                Some((_, ']')) => {
+                    let command_start = ix + 2;
+                    let mut command_end = text.len();
+                    let mut sequence_end = text.len();
+                    while let Some((ix, ch)) = chars.next() {
+                        if ch == BEL {
+                            command_end = ix;
+                            sequence_end = ix + 1;
+                            break;
+                        } else if ch == ESC && chars.peek().is_some_and(|(_, ch)| *ch == '\\') {
+                            chars.next();
+                            command_end = ix;
+                            sequence_end = ix + 2;
+                            break;
+                        }
+                    }
+                    self.apply_osc(&text[command_start..command_end]);
+                    run_start = sequence_end;
+                }
+                Some((ix, ch)) => run_start = ix + ch.len_utf8(),
+                None => run_start = text.len(),
+            }
+        }
+
+        if run_start < text.len() {
+            runs.push(self.run(run_start..text.len()));
+        }
+        runs
+    }
+
+    fn run(&self, range: Range<usize>) -> AnsiRun {
+        AnsiRun {
+            range,
+            style: self.style,
+            link: self.link.clone(),
+        }
+    }
+
+    fn apply_osc(&mut self, command: &str) {
+        // Hyperlinks are written as `8;params;uri`, and an empty uri ends the link.
+        let mut parts = command.splitn(3, ';');
+        if parts.next() != Some("8") {
+            return;
+        }
+        let Some(uri) = parts.nth(1) else {
+            return;
+        };
+        self.link = (!uri.is_empty()).then(|| SharedString::from(uri.to_string()));
+    }
+
+    fn apply_sgr(&mut self, params: &str) {
+        if params.is_empty() {
+            self.style = AnsiStyle::default();
+            return;
+        }
+
+        let mut params = params
+            .split([';', ':'])
+            .map(|param| param.parse::<u16>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => self.style = AnsiStyle::default(),
+                1 => self.style.bold = true,
+                2 => self.style.dim = true,
+                3 => self.style.italic = true,
+                4 => self.style.underline = true,
+                9 => self.style.strikethrough = true,
+                22 => {
+                    self.style.bold = false;
+                    self.style.dim = false;
+                }
+                23 => self.style.italic = false,
+                24 => self.style.underline = false,
+                29 => self.style.strikethrough = false,
+                30..=37 => self.style.foreground = Some(AnsiColor::Indexed((param - 30) as u8)),
+                38 => self.style.foreground = extended_color(&mut params),
+                39 => self.style.foreground = None,
+                40..=47 => self.style.background = Some(AnsiColor::Indexed((param - 40) as u8)),
+                48 => self.style.background = extended_color(&mut params),
+                49 => self.style.background = None,
+                90..=97 => self.style.foreground = Some(AnsiColor::Indexed((param - 90 + 8) as u8)),
+                100..=107 => {
+                    self.style.background = Some(AnsiColor::Indexed((param - 100 + 8) as u8))
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Reads the color of a `38` or `48` parameter, either `5;index` or `2;r;g;b`.
+fn extended_color(params: &mut impl Iterator<Item = u16>) -> Option<AnsiColor> {
+    let mut component = || params.next().map(|value| value.min(255) as u8);
+    match component()? {
+        5 => Some(AnsiColor::Indexed(component()?)),
+        2 => Some(AnsiColor::Rgb(component()?, component()?, component()?)),
+        _ => None,
+    }
+}
@@ -0,0 +1,33 @@
+use gpui::SharedString;
+
+/// Common aliases used in fenced code blocks, mapped to the lowercased name of the
+/// language they stand for.
+pub const CODE_BLOCK_LANGUAGE_ALIASES: &[(&str, &str)] = &[
+    ("bash", "shell script"),
+    ("cpp", "c++"),
+    ("golang", "go"),
+    ("js", "javascript"),
+    ("jsx", "javascript"),
+    ("md", "markdown"),
+    ("py", "python"),
+    ("rb", "ruby"),
+    ("rs", "rust"),
+    ("sh", "shell script"),
+    ("shell", "shell script"),
+    ("ts", "typescript"),
+    ("yml", "yaml"),
+    ("zsh", "shell script"),
+];
+
+/// Resolves the language of a fenced code block to the name both highlighting and styling
+/// look it up by: lowercased, with [`CODE_BLOCK_LANGUAGE_ALIASES`] expanded.
+pub fn code_block_language(language: &str) -> SharedString {
+    let language = language.trim().to_lowercase();
+    CODE_BLOCK_LANGUAGE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == language)
+        .map_or_else(
+            || SharedString::from(language),
+            |(_, name)| SharedString::new_static(name),
+        )
+}
@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use gpui::SharedString;
+
+use super::{MarkdownEvent, MarkdownTag, MarkdownTagEnd, wiki_link::run_text};
+
+/// Replaces the known `:shortcode:` sequences found in runs of text outside of code with
+/// the emoji they stand for, keeping unknown ones and everything else as is.
+pub(crate) fn insert_emoji(
+    events: Vec<(Range<usize>, MarkdownEvent)>,
+    source: &str,
+) -> Vec<(Range<usize>, MarkdownEvent)> {
+    let mut result = Vec::with_capacity(events.len());
+    // Pulldown splits text around characters such as `_`, which shortcodes can contain,
+    // so shortcodes are looked for in runs of adjacent text events.
+    let mut run: Vec<(Range<usize>, SharedString)> = Vec::new();
+    let mut within_code_block = false;
+    for (range, event) in events {
+        match &event {
+            MarkdownEvent::Text(text) if !within_code_block => {
+                if run
+                    .last()
+                    .is_some_and(|(run_range, _)| run_range.end != range.start)
+                {
+                    push_run(&mut run, source, &mut result);
+                }
+                run.push((range, text.clone()));
+                continue;
+            }
+            MarkdownEvent::Start(MarkdownTag::CodeBlock(_)) => within_code_block = true,
+            MarkdownEvent::End(MarkdownTagEnd::CodeBlock) => within_code_block = false,
+            _ => {}
+        }
+        push_run(&mut run, source, &mut result);
+        result.push((range, event));
+    }
+    push_run(&mut run, source, &mut result);
+    result
+}
+
+/// Pushes the events of a run of text, substituting the shortcodes it contains.
+fn push_run(
+    run: &mut Vec<(Range<usize>, SharedString)>,
+    source: &str,
+    result: &mut Vec<(Range<usize>, MarkdownEvent)>,
+) {
+    let (Some((first, _)), Some((last, _))) = (run.first(), run.last()) else {
+        return;
+    };
+    let run_range = first.start..last.end;
+
+    let mut cursor = run_range.start;
+    for (shortcode_range, emoji) in find_shortcodes(&source[run_range.clone()]) {
+        let shortcode_range =
+            run_range.start + shortcode_range.start..run_range.start + shortcode_range.end;
+        if cursor < shortcode_range.start {
+            result.push((
+                cursor..shortcode_range.start,
+                MarkdownEvent::Text(run_text(run, cursor..shortcode_range.start, source)),
+            ));
+        }
+        result.push((
+            shortcode_range.clone(),
+            MarkdownEvent::SubstitutedText(SharedString::new_static(emoji)),
+        ));
+        cursor = shortcode_range.end;
+    }
+
+    if cursor == run_range.start {
+        result.extend(
+            run.drain(..)
+                .map(|(range, text)| (range, MarkdownEvent::Text(text))),
+        );
+    } else {
+        if cursor < run_range.end {
+            result.push((
+                cursor..run_range.end,
+                MarkdownEvent::Text(run_text(run, cursor..run_range.end, source)),
+            ));
+        }
+        run.clear();
+    }
+}
+
+/// Finds the known shortcodes in the text, returning their range, colons included, and
+/// their emoji.
+fn find_shortcodes(text: &str) -> Vec<(Range<usize>, &'static str)> {
+    let mut shortcodes = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = text[offset..].find(':').map(|ix| offset + ix) {
+        let name_start = open + 1;
+        let Some(close) = text[name_start..].find(':').map(|ix| name_start + ix) else {
+            break;
+        };
+        let name = &text[name_start..close];
+        let is_shortcode_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '+' | '-'));
+        match is_shortcode_name
+            .then(|| emojis::get_by_shortcode(name))
+            .flatten()
+        {
+            Some(emoji) => {
+                shortcodes.push((open..close + 1, emoji.as_str()));
+                offset = close + 1;
+            }
+            // The closing colon may open the next shortcode, as in `12:30 :rocket:`.
+            None => offset = close,
+        }
+    }
+    shortcodes
+}
@@ -0,0 +1,220 @@
+use std::{collections::HashSet, ops::Range};
+
+use gpui::SharedString;
+
+use super::{
+    CodeBlockKind, MarkdownEvent, MarkdownParseOptions, MarkdownTag, assign_heading_ids,
+    code_block_language, parse_events,
+};
+
+/// A parse of a markdown document that can be updated as the document is edited, without
+/// reparsing all of it. See [`parse_markdown_incremental`].
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalMarkdown {
+    source: String,
+    /// The events before headings are given ids, which depend on the whole document.
+    raw_events: Vec<(Range<usize>, MarkdownEvent)>,
+    events: Vec<(Range<usize>, MarkdownEvent)>,
+    languages: HashSet<SharedString>,
+}
+
+impl IncrementalMarkdown {
+    /// Parses the whole text, like [`super::parse_markdown`].
+    pub fn new(text: &str) -> Self {
+        let (raw_events, _) = parse_events(text, &MarkdownParseOptions::default());
+        Self::from_raw_events(text, raw_events)
+    }
+
+    fn from_raw_events(text: &str, raw_events: Vec<(Range<usize>, MarkdownEvent)>) -> Self {
+        let languages = raw_events
+            .iter()
+            .filter_map(|(_, event)| match event {
+                MarkdownEvent::Start(MarkdownTag::CodeBlock(CodeBlockKind::Fenced(language))) => {
+                    Some(code_block_language(language))
+                }
+                _ => None,
+            })
+            .collect();
+        let mut events = raw_events.clone();
+        assign_heading_ids(&mut events, text);
+        Self {
+            source: text.to_string(),
+            raw_events,
+            events,
+            languages,
+        }
+    }
+
+    /// The same events as [`super::parse_markdown`] returns for the text.
+    pub fn events(&self) -> &[(Range<usize>, MarkdownEvent)] {
+        &self.events
+    }
+
+    /// The languages of the fenced code blocks.
+    pub fn languages(&self) -> &HashSet<SharedString> {
+        &self.languages
+    }
+}
+
+/// A top-level block of the document: either a balanced run of events or a single event
+/// such as a thematic break.
+struct Block {
+    events: Range<usize>,
+    source: Range<usize>,
+}
+
+/// Updates the parse of a document after `edited_range` of its previous text was replaced,
+/// giving `text`.
+///
+/// Only the blocks around the edit are reparsed, along with the block that follows them,
+/// which must parse the same as before so that the trailing events can be reused with
+/// shifted ranges. When the edit can't be isolated this way, e.g. because it opens a code
+/// fence swallowing the rest of the document, or because the document has reference
+/// definitions or footnotes that blocks depend on, the whole text is parsed again.
+pub fn parse_markdown_incremental(
+    prev: &IncrementalMarkdown,
+    text: &str,
+    edited_range: Range<usize>,
+) -> IncrementalMarkdown {
+    reparse_edited_blocks(prev, text, edited_range)
+        .unwrap_or_else(|| IncrementalMarkdown::new(text))
+}
+
+fn reparse_edited_blocks(
+    prev: &IncrementalMarkdown,
+    text: &str,
+    edited_range: Range<usize>,
+) -> Option<IncrementalMarkdown> {
+    let old_len = prev.source.len();
+    if edited_range.start > edited_range.end || edited_range.end > old_len {
+        return None;
+    }
+    let unchanged_len = old_len - edited_range.len();
+    let inserted_len = text.len().checked_sub(unchanged_len)?;
+    let edit_new_end = edited_range.start + inserted_len;
+    if !prev.source.is_char_boundary(edited_range.start)
+        || !prev.source.is_char_boundary(edited_range.end)
+        || !text.is_char_boundary(edited_range.start)
+        || !text.is_char_boundary(edit_new_end)
+        || prev.source[..edited_range.start] != text[..edited_range.start]
+        || prev.source[edited_range.end..] != text[edit_new_end..]
+    {
+        return None;
+    }
+    // Reference definitions and footnotes can change how any block parses.
+    if has_cross_block_references(&prev.source) || has_cross_block_references(text) {
+        return None;
+    }
+
+    let blocks = top_level_blocks(&prev.raw_events);
+    let first_edited_ix = blocks
+        .iter()
+        .position(|block| block.source.end >= edited_range.start)
+        .unwrap_or(blocks.len());
+    // Also reparse the block before the edit, which it may extend, e.g. by turning a
+    // paragraph into a setext heading or a table.
+    let (kept_events, region_start) = match first_edited_ix.checked_sub(1) {
+        Some(anchor_ix) => {
+            let anchor = &blocks[anchor_ix];
+            let line_start = prev.source[..anchor.source.start]
+                .rfind('\n')
+                .map_or(0, |ix| ix + 1);
+            (anchor.events.start, line_start)
+        }
+        None => (0, 0),
+    };
+    let next_block = blocks[first_edited_ix..]
+        .iter()
+        .find(|block| block.source.start > edited_range.end);
+    let delta = inserted_len as isize - edited_range.len() as isize;
+    let shift = |range: &Range<usize>, by: isize| {
+        range.start.checked_add_signed(by).unwrap()..range.end.checked_add_signed(by).unwrap()
+    };
+
+    let region_end = match next_block {
+        Some(next_block) => next_block.source.end.checked_add_signed(delta)?,
+        None => text.len(),
+    };
+    let (region_events, _) = parse_events(
+        &text[region_start..region_end],
+        &MarkdownParseOptions::default(),
+    );
+    // Metadata blocks are only recognized at the start of the document.
+    if region_start > 0
+        && region_events
+            .iter()
+            .any(|(_, event)| matches!(event, MarkdownEvent::MetadataBlock { .. }))
+    {
+        return None;
+    }
+    let region_start = region_start as isize;
+    let mut region_events = region_events
+        .into_iter()
+        .map(|(range, event)| (shift(&range, region_start), event))
+        .collect::<Vec<_>>();
+
+    let mut raw_events = prev.raw_events[..kept_events].to_vec();
+    match next_block {
+        Some(next_block) => {
+            // The block after the edit must be unaffected by it, in which case so are the
+            // ones following it.
+            let reparsed_next_block = top_level_blocks(&region_events).pop()?;
+            let prev_events = &prev.raw_events[next_block.events.clone()];
+            let reparsed_events = &region_events[reparsed_next_block.events.clone()];
+            let unaffected = prev_events.len() == reparsed_events.len()
+                && prev_events.iter().zip(reparsed_events).all(
+                    |((prev_range, prev_event), (range, event))| {
+                        shift(prev_range, delta) == *range && prev_event == event
+                    },
+                );
+            if !unaffected {
+                return None;
+            }
+
+            region_events.truncate(reparsed_next_block.events.start);
+            raw_events.extend(region_events);
+            raw_events.extend(
+                prev.raw_events[next_block.events.start..]
+                    .iter()
+                    .map(|(range, event)| (shift(range, delta), event.clone())),
+            );
+        }
+        None => raw_events.extend(region_events),
+    }
+    Some(IncrementalMarkdown::from_raw_events(text, raw_events))
+}
+
+fn has_cross_block_references(text: &str) -> bool {
+    text.contains("]:") || text.contains("[^")
+}
+
+fn top_level_blocks(events: &[(Range<usize>, MarkdownEvent)]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut depth = 0_usize;
+    let mut block_start = 0;
+    for (ix, (range, event)) in events.iter().enumerate() {
+        match event {
+            MarkdownEvent::Start(_) => {
+                if depth == 0 {
+                    block_start = ix;
+                }
+                depth += 1;
+            }
+            MarkdownEvent::End(_) => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    blocks.push(Block {
+                        source: events[block_start].0.start..range.end,
+                        events: block_start..ix + 1,
+                    });
+                }
+            }
+            _ if depth == 0 => blocks.push(Block {
+                events: ix..ix + 1,
+                source: range.clone(),
+            }),
+            _ => {}
+        }
+    }
+    blocks
+}
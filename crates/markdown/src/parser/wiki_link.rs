@@ -0,0 +1,214 @@
+use std::ops::Range;
+
+use gpui::SharedString;
+use pulldown_cmark::LinkType;
+
+use super::{MarkdownEvent, MarkdownParseOptions, MarkdownTag, MarkdownTagEnd};
+
+/// The placeholder replaced by the page name in wiki link URL templates.
+const WIKI_LINK_PAGE_PLACEHOLDER: &str = "{page}";
+
+/// A `[[Page]]` or `[[Page|alias]]` wiki link found in text.
+#[derive(Debug, PartialEq)]
+struct WikiLink {
+    /// The range of the whole link, brackets included.
+    range: Range<usize>,
+    page: Range<usize>,
+    /// The range of the alias, or of the page when there is none.
+    label: Range<usize>,
+}
+
+/// Replaces the wiki links found in runs of text outside of links and code with link events
+/// pointing at `url_template`, keeping everything else as is.
+pub(crate) fn insert_wiki_links(
+    events: Vec<(Range<usize>, MarkdownEvent)>,
+    source: &str,
+    url_template: &str,
+    options: &MarkdownParseOptions,
+) -> Vec<(Range<usize>, MarkdownEvent)> {
+    let mut result = Vec::with_capacity(events.len());
+    // Pulldown splits text around brackets, so wiki links are looked for in runs of
+    // adjacent text events.
+    let mut run: Vec<(Range<usize>, SharedString)> = Vec::new();
+    let mut link_depth = 0_usize;
+    let mut within_code_block = false;
+    for (range, event) in events {
+        match &event {
+            MarkdownEvent::Text(text) if link_depth == 0 && !within_code_block => {
+                if run
+                    .last()
+                    .is_some_and(|(run_range, _)| run_range.end != range.start)
+                {
+                    push_run(&mut run, source, url_template, options, &mut result);
+                }
+                run.push((range, text.clone()));
+                continue;
+            }
+            MarkdownEvent::Start(MarkdownTag::Link { .. } | MarkdownTag::Image { .. }) => {
+                link_depth += 1
+            }
+            MarkdownEvent::End(MarkdownTagEnd::Link | MarkdownTagEnd::Image) => {
+                link_depth = link_depth.saturating_sub(1)
+            }
+            MarkdownEvent::Start(MarkdownTag::CodeBlock(_)) => within_code_block = true,
+            MarkdownEvent::End(MarkdownTagEnd::CodeBlock) => within_code_block = false,
+            _ => {}
+        }
+        push_run(&mut run, source, url_template, options, &mut result);
+        result.push((range, event));
+    }
+    push_run(&mut run, source, url_template, options, &mut result);
+    result
+}
+
+/// Pushes the events of a run of text, splitting it around the wiki links it contains.
+fn push_run(
+    run: &mut Vec<(Range<usize>, SharedString)>,
+    source: &str,
+    url_template: &str,
+    options: &MarkdownParseOptions,
+    result: &mut Vec<(Range<usize>, MarkdownEvent)>,
+) {
+    let (Some((first, _)), Some((last, _))) = (run.first(), run.last()) else {
+        return;
+    };
+    let run_range = first.start..last.end;
+
+    let mut cursor = run_range.start;
+    for wiki_link in find_wiki_links(&source[run_range.clone()]) {
+        let offset =
+            |range: Range<usize>| run_range.start + range.start..run_range.start + range.end;
+        let link_range = offset(wiki_link.range);
+        let page = &source[offset(wiki_link.page)];
+        let dest_url = url_template.replace(WIKI_LINK_PAGE_PLACEHOLDER, &page.replace(' ', "%20"));
+        if !options.is_link_allowed(&dest_url) {
+            continue;
+        }
+
+        if cursor < link_range.start {
+            result.push((
+                cursor..link_range.start,
+                MarkdownEvent::Text(run_text(run, cursor..link_range.start, source)),
+            ));
+        }
+        result.push((
+            link_range.clone(),
+            MarkdownEvent::Start(MarkdownTag::Link {
+                link_type: LinkType::Inline,
+                dest_url: SharedString::from(dest_url),
+                title: SharedString::default(),
+                id: SharedString::default(),
+            }),
+        ));
+        let label_range = offset(wiki_link.label);
+        result.push((
+            label_range.clone(),
+            MarkdownEvent::Text(SharedString::from(source[label_range].to_string())),
+        ));
+        result.push((link_range.clone(), MarkdownEvent::End(MarkdownTagEnd::Link)));
+        cursor = link_range.end;
+    }
+
+    if cursor == run_range.start {
+        result.extend(
+            run.drain(..)
+                .map(|(range, text)| (range, MarkdownEvent::Text(text))),
+        );
+    } else {
+        if cursor < run_range.end {
+            result.push((
+                cursor..run_range.end,
+                MarkdownEvent::Text(run_text(run, cursor..run_range.end, source)),
+            ));
+        }
+        run.clear();
+    }
+}
+
+/// Returns the parsed text of the run within the given source range.
+pub(super) fn run_text(
+    run: &[(Range<usize>, SharedString)],
+    range: Range<usize>,
+    source: &str,
+) -> SharedString {
+    let mut text = String::new();
+    for (event_range, event_text) in run {
+        let start = event_range.start.max(range.start);
+        let end = event_range.end.min(range.end);
+        if start >= end {
+            continue;
+        }
+
+        if start == event_range.start && end == event_range.end {
+            text.push_str(event_text);
+        } else if event_text.len() == event_range.len() {
+            text.push_str(&event_text[start - event_range.start..end - event_range.start]);
+        } else {
+            // The parsed text differs from the source, e.g. because of escapes,
+            // so offsets can't be mapped onto it.
+            text.push_str(&source[start..end]);
+        }
+    }
+    SharedString::from(text)
+}
+
+/// Finds the wiki links in the text. Brackets can't be nested inside a link, so only
+/// the innermost of nested links is recognized, and an escaped `\[[` doesn't open one.
+fn find_wiki_links(text: &str) -> Vec<WikiLink> {
+    let mut links = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = text[offset..].find("[[").map(|ix| offset + ix) {
+        let content_start = open + 2;
+        if text[..open].ends_with('\\') {
+            offset = open + 1;
+            continue;
+        }
+
+        let Some(bracket) = text[content_start..]
+            .find(['[', ']'])
+            .map(|ix| content_start + ix)
+        else {
+            break;
+        };
+        if !text[bracket..].starts_with("]]") {
+            offset = if text[bracket..].starts_with('[') {
+                open + 1
+            } else {
+                bracket + 1
+            };
+            continue;
+        }
+        offset = bracket + 2;
+
+        let content = &text[content_start..bracket];
+        if content.ends_with('\\') {
+            continue;
+        }
+        let (page, alias) = match content.find('|') {
+            Some(pipe) => (
+                trimmed(text, content_start..content_start + pipe),
+                Some(trimmed(text, content_start + pipe + 1..bracket)),
+            ),
+            None => (trimmed(text, content_start..bracket), None),
+        };
+        if page.is_empty() {
+            continue;
+        }
+
+        links.push(WikiLink {
+            range: open..bracket + 2,
+            label: alias
+                .filter(|alias| !alias.is_empty())
+                .unwrap_or(page.clone()),
+            page,
+        });
+    }
+    links
+}
+
+fn trimmed(text: &str, range: Range<usize>) -> Range<usize> {
+    let slice = &text[range.clone()];
+    let start = range.start + slice.len() - slice.trim_start().len();
+    let end = range.end - (slice.len() - slice.trim_end().len());
+    start..end.max(start)
+}
@@ -969,7 +969,7 @@ fn language_server_for_buffer(
         .ok_or_else(|| anyhow!("no language server found for buffer"))
 }
 
-async fn location_links_from_proto(
+pub(crate) async fn location_links_from_proto(
     proto_links: Vec<proto::LocationLink>,
     lsp_store: Entity<LspStore>,
     mut cx: AsyncApp,
@@ -1033,7 +1033,7 @@ async fn location_links_from_proto(
     Ok(links)
 }
 
-async fn location_links_from_lsp(
+pub(crate) async fn location_links_from_lsp(
     message: Option<lsp::GotoDefinitionResponse>,
     lsp_store: Entity<LspStore>,
     buffer: Entity<Buffer>,
@@ -1115,7 +1115,7 @@ async fn location_links_from_lsp(
     Ok(definitions)
 }
 
-fn location_links_to_proto(
+pub(crate) fn location_links_to_proto(
     links: Vec<LocationLink>,
     lsp_store: &mut LspStore,
     peer_id: PeerId,
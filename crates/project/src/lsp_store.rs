@@ -3429,6 +3429,9 @@ impl LspStore {
         client.add_entity_request_handler(
             Self::handle_lsp_command::<lsp_ext_command::SwitchSourceHeader>,
         );
+        client.add_entity_request_handler(Self::handle_lsp_command::<lsp_ext_command::SyntaxTree>);
+        client
+            .add_entity_request_handler(Self::handle_lsp_command::<lsp_ext_command::ParentModule>);
     }
 
     pub fn as_remote(&self) -> Option<&RemoteLspStore> {
@@ -1,12 +1,23 @@
-use crate::{lsp_command::LspCommand, lsp_store::LspStore, make_text_document_identifier};
+use crate::{
+    LocationLink,
+    lsp_command::{
+        LspCommand, location_links_from_lsp, location_links_from_proto, location_links_to_proto,
+    },
+    lsp_store::LspStore,
+    make_text_document_identifier,
+};
 use anyhow::{Context as _, Result};
 use async_trait::async_trait;
 use gpui::{App, AsyncApp, Entity};
-use language::{Buffer, point_to_lsp, proto::deserialize_anchor};
+use language::{
+    Buffer, point_to_lsp,
+    proto::{deserialize_anchor, serialize_anchor},
+    range_to_lsp,
+};
 use lsp::{LanguageServer, LanguageServerId};
 use rpc::proto::{self, PeerId};
 use serde::{Deserialize, Serialize};
-use std::{path::Path, sync::Arc};
+use std::{ops::Range, path::Path, sync::Arc};
 use text::{BufferId, PointUtf16, ToPointUtf16};
 
 pub enum LspExpandMacro {}
@@ -266,6 +277,214 @@ impl LspCommand for OpenDocs {
     }
 }
 
+pub enum LspSyntaxTree {}
+
+impl lsp::request::Request for LspSyntaxTree {
+    type Params = SyntaxTreeParams;
+    type Result = String;
+    const METHOD: &'static str = "rust-analyzer/syntaxTree";
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SyntaxTreeParams {
+    pub text_document: lsp::TextDocumentIdentifier,
+    pub range: Option<lsp::Range>,
+}
+
+/// Requests rust-analyzer's syntax tree of a range, or of the whole file if no range is given.
+#[derive(Debug)]
+pub struct SyntaxTree {
+    pub range: Option<Range<PointUtf16>>,
+}
+
+#[async_trait(?Send)]
+impl LspCommand for SyntaxTree {
+    type Response = String;
+    type LspRequest = LspSyntaxTree;
+    type ProtoRequest = proto::LspExtSyntaxTree;
+
+    fn display_name(&self) -> &str {
+        "View syntax tree"
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<SyntaxTreeParams> {
+        Ok(SyntaxTreeParams {
+            text_document: make_text_document_identifier(path)?,
+            range: self.range.clone().map(range_to_lsp).transpose()?,
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: String,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> anyhow::Result<String> {
+        Ok(message)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::LspExtSyntaxTree {
+        let range = self.range.as_ref();
+        proto::LspExtSyntaxTree {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            start: range.map(|range| serialize_anchor(&buffer.anchor_before(range.start))),
+            end: range.map(|range| serialize_anchor(&buffer.anchor_after(range.end))),
+        }
+    }
+
+    async fn from_proto(
+        message: Self::ProtoRequest,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> anyhow::Result<Self> {
+        let range = match (message.start, message.end) {
+            (Some(start), Some(end)) => {
+                let start = deserialize_anchor(start).context("invalid start")?;
+                let end = deserialize_anchor(end).context("invalid end")?;
+                Some(buffer.update(&mut cx, |buffer, _| {
+                    start.to_point_utf16(buffer)..end.to_point_utf16(buffer)
+                })?)
+            }
+            _ => None,
+        };
+        Ok(Self { range })
+    }
+
+    fn response_to_proto(
+        response: String,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::LspExtSyntaxTreeResponse {
+        proto::LspExtSyntaxTreeResponse {
+            syntax_tree: response,
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::LspExtSyntaxTreeResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> anyhow::Result<String> {
+        Ok(message.syntax_tree)
+    }
+
+    fn buffer_id_from_proto(message: &proto::LspExtSyntaxTree) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+pub enum LspParentModule {}
+
+impl lsp::request::Request for LspParentModule {
+    type Params = lsp::TextDocumentPositionParams;
+    type Result = Option<lsp::GotoDefinitionResponse>;
+    const METHOD: &'static str = "experimental/parentModule";
+}
+
+#[derive(Debug)]
+pub struct ParentModule {
+    pub position: PointUtf16,
+}
+
+#[async_trait(?Send)]
+impl LspCommand for ParentModule {
+    type Response = Vec<LocationLink>;
+    type LspRequest = LspParentModule;
+    type ProtoRequest = proto::LspExtParentModule;
+
+    fn display_name(&self) -> &str {
+        "Open parent module"
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TextDocumentPositionParams> {
+        Ok(lsp::TextDocumentPositionParams {
+            text_document: make_text_document_identifier(path)?,
+            position: point_to_lsp(self.position),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<lsp::GotoDefinitionResponse>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> anyhow::Result<Vec<LocationLink>> {
+        location_links_from_lsp(message, lsp_store, buffer, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::LspExtParentModule {
+        proto::LspExtParentModule {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+        }
+    }
+
+    async fn from_proto(
+        message: Self::ProtoRequest,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> anyhow::Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        Ok(Self {
+            position: buffer.update(&mut cx, |buffer, _| position.to_point_utf16(buffer))?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<LocationLink>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::LspExtParentModuleResponse {
+        proto::LspExtParentModuleResponse {
+            links: location_links_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::LspExtParentModuleResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> anyhow::Result<Vec<LocationLink>> {
+        location_links_from_proto(message.links, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::LspExtParentModule) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
 pub enum LspSwitchSourceHeader {}
 
 impl lsp::request::Request for LspSwitchSourceHeader {
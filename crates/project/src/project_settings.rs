@@ -209,6 +209,27 @@ impl GitSettings {
         }
     }
 
+    /// Returns the commit age at which the inline blame reaches the end of
+    /// its color gradient, or `None` when commit age tinting is disabled.
+    pub fn inline_blame_commit_age_range(&self) -> Option<Duration> {
+        const DEFAULT_COMMIT_AGE_MAX_DAYS: u32 = 365;
+        const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+        match self.inline_blame {
+            Some(InlineBlameSettings {
+                show_commit_age: true,
+                commit_age_max_days,
+                ..
+            }) => {
+                let days = commit_age_max_days
+                    .unwrap_or(DEFAULT_COMMIT_AGE_MAX_DAYS)
+                    .max(1);
+                Some(Duration::from_secs(days as u64 * SECONDS_PER_DAY))
+            }
+            _ => None,
+        }
+    }
+
     pub fn show_inline_commit_summary(&self) -> bool {
         match self.inline_blame {
             Some(InlineBlameSettings {
@@ -263,6 +284,18 @@ pub struct InlineBlameSettings {
     /// Default: false
     #[serde(default)]
     pub show_commit_summary: bool,
+    /// Whether to tint the inline blame by the age of the commit,
+    /// from the theme's "added" color for fresh commits to its muted
+    /// text color for old ones.
+    ///
+    /// Default: true
+    #[serde(default = "true_value")]
+    pub show_commit_age: bool,
+    /// The age in days at which a commit is drawn with the oldest color
+    /// of the commit age gradient.
+    ///
+    /// Default: 365
+    pub commit_age_max_days: Option<u32>,
 }
 
 const fn true_value() -> bool {
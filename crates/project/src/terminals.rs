@@ -1,7 +1,7 @@
 use crate::{Project, ProjectPath};
 use anyhow::{Context as _, Result};
 use collections::HashMap;
-use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, Task, WeakEntity};
+use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, EntityId, Task, WeakEntity};
 use itertools::Itertools;
 use language::LanguageName;
 use settings::{Settings, SettingsLocation};
@@ -25,14 +25,23 @@ use util::ResultExt;
 
 pub struct Terminals {
     pub(crate) local_handles: Vec<WeakEntity<terminal::Terminal>>,
+    /// The kind each local terminal was created with, to be able to restart it.
+    pub(crate) kinds: HashMap<EntityId, TerminalKind>,
 }
 
 /// Terminals are opened either for the users shell, or to run a task.
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum TerminalKind {
     /// Run a shell at the given path (or $HOME if None)
-    Shell(Option<PathBuf>),
+    Shell {
+        working_directory: Option<PathBuf>,
+        /// The name of a terminal profile whose environment variables are added to the shell.
+        profile: Option<String>,
+        /// Extra environment variables for the shell, such as the ones inherited from the
+        /// terminal it was split from.
+        env: HashMap<String, String>,
+    },
     /// Run a task.
     Task(SpawnInTerminal),
     /// Run a debug terminal.
@@ -93,7 +102,11 @@ impl Project {
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
         let path: Option<Arc<Path>> = match &kind {
-            TerminalKind::Shell(path) => path.as_ref().map(|path| Arc::from(path.as_ref())),
+            TerminalKind::Shell {
+                working_directory, ..
+            } => working_directory
+                .as_ref()
+                .map(|path| Arc::from(path.as_ref())),
             TerminalKind::Task(spawn_task) => {
                 if let Some(cwd) = &spawn_task.cwd {
                     Some(Arc::from(cwd.as_ref()))
@@ -131,6 +144,12 @@ impl Project {
         })
     }
 
+    /// Returns the kind the given terminal was created with, which can be used to create
+    /// a fresh terminal running the same shell or task.
+    pub fn terminal_kind(&self, terminal: &Entity<Terminal>) -> Option<&TerminalKind> {
+        self.terminals.kinds.get(&terminal.entity_id())
+    }
+
     pub fn terminal_settings<'a>(
         &'a self,
         path: &'a Option<PathBuf>,
@@ -196,8 +215,13 @@ impl Project {
         cx: &mut Context<Self>,
     ) -> Result<Entity<Terminal>> {
         let this = &mut *self;
+        let restart_kind = kind.clone();
         let path: Option<Arc<Path>> = match &kind {
-            TerminalKind::Shell(path) => path.as_ref().map(|path| Arc::from(path.as_ref())),
+            TerminalKind::Shell {
+                working_directory, ..
+            } => working_directory
+                .as_ref()
+                .map(|path| Arc::from(path.as_ref())),
             TerminalKind::Task(spawn_task) => {
                 if let Some(cwd) = &spawn_task.cwd {
                     Some(Arc::from(cwd.as_ref()))
@@ -240,9 +264,27 @@ impl Project {
 
         let mut python_venv_activate_command = None;
         let debug_terminal = matches!(kind, TerminalKind::Debug { .. });
+        // The variables added on top of the inherited environment and the `env` setting,
+        // so that terminals cloned from this one can be spawned with them too.
+        let mut spawn_env = HashMap::default();
 
         let (spawn_task, shell) = match kind {
-            TerminalKind::Shell(_) => {
+            TerminalKind::Shell {
+                profile,
+                env: extra_env,
+                ..
+            } => {
+                spawn_env.extend(extra_env);
+                // Profile variables take precedence over the `env` setting.
+                if let Some(profile) = profile {
+                    let profile = settings
+                        .profiles
+                        .get(&profile)
+                        .with_context(|| format!("unknown terminal profile {profile:?}"))?;
+                    spawn_env.extend(profile.env.clone());
+                }
+                env.extend(spawn_env.clone());
+
                 if let Some(python_venv_directory) = &python_venv_directory {
                     python_venv_activate_command =
                         this.python_activate_command(python_venv_directory, &settings.detect_venv);
@@ -288,7 +330,8 @@ impl Project {
                     completion_rx,
                 });
 
-                env.extend(spawn_task.env);
+                spawn_env.extend(spawn_task.env);
+                env.extend(spawn_env.clone());
 
                 if let Some(venv_path) = &python_venv_directory {
                     env.insert(
@@ -342,7 +385,8 @@ impl Project {
                 title,
                 ..
             } => {
-                env.extend(envs);
+                spawn_env.extend(envs);
+                env.extend(spawn_env.clone());
 
                 let shell = if let Some(program) = command {
                     Shell::WithArguments {
@@ -363,6 +407,7 @@ impl Project {
             spawn_task,
             shell,
             env,
+            spawn_env,
             settings.cursor_shape.unwrap_or_default(),
             settings.alternate_scroll,
             settings.max_scroll_history_lines,
@@ -380,7 +425,9 @@ impl Project {
                 .push(terminal_handle.downgrade());
 
             let id = terminal_handle.entity_id();
+            this.terminals.kinds.insert(id, restart_kind);
             cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
+                project.terminals.kinds.remove(&id);
                 let handles = &mut project.terminals.local_handles;
 
                 if let Some(index) = handles
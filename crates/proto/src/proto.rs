@@ -346,8 +346,12 @@ messages!(
     (LspExtExpandMacroResponse, Background),
     (LspExtOpenDocs, Background),
     (LspExtOpenDocsResponse, Background),
+    (LspExtParentModule, Background),
+    (LspExtParentModuleResponse, Background),
     (LspExtSwitchSourceHeader, Background),
     (LspExtSwitchSourceHeaderResponse, Background),
+    (LspExtSyntaxTree, Background),
+    (LspExtSyntaxTreeResponse, Background),
     (MarkNotificationRead, Foreground),
     (MoveChannel, Foreground),
     (MultiLspQuery, Background),
@@ -597,6 +601,8 @@ request_messages!(
     (CreateContext, CreateContextResponse),
     (SynchronizeContexts, SynchronizeContextsResponse),
     (LspExtSwitchSourceHeader, LspExtSwitchSourceHeaderResponse),
+    (LspExtSyntaxTree, LspExtSyntaxTreeResponse),
+    (LspExtParentModule, LspExtParentModuleResponse),
     (AddWorktree, AddWorktreeResponse),
     (ShutdownRemoteServer, Ack),
     (RemoveWorktree, Ack),
@@ -715,6 +721,8 @@ entity_messages!(
     UpdateContext,
     SynchronizeContexts,
     LspExtSwitchSourceHeader,
+    LspExtSyntaxTree,
+    LspExtParentModule,
     LanguageServerLog,
     Toast,
     HideToast,
@@ -9,6 +9,7 @@ use settings::{Settings, SettingsSources};
 #[derive(Debug, Default)]
 pub struct JupyterSettings {
     pub kernel_selections: HashMap<String, String>,
+    pub reconnect_to_running_kernels: bool,
 }
 
 impl JupyterSettings {
@@ -18,6 +19,14 @@ impl JupyterSettings {
         // This allows the editor to set up context for key bindings/actions.
         EditorSettings::jupyter_enabled(cx)
     }
+
+    /// Returns the name of the default kernel selected for the language, ignoring case.
+    pub fn kernel_selection(&self, language: &str) -> Option<&String> {
+        self.kernel_selections
+            .iter()
+            .find(|(selection_language, _)| selection_language.eq_ignore_ascii_case(language))
+            .map(|(_, kernel_name)| kernel_name)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, JsonSchema, Debug)]
@@ -26,12 +35,18 @@ pub struct JupyterSettingsContent {
     ///
     /// Default: `{}`
     pub kernel_selections: Option<HashMap<String, String>>,
+    /// Whether reopening a file reattaches it to the kernel it was running code in, if that
+    /// kernel is still running. New kernels are never started automatically.
+    ///
+    /// Default: false
+    pub reconnect_to_running_kernels: Option<bool>,
 }
 
 impl Default for JupyterSettingsContent {
     fn default() -> Self {
         JupyterSettingsContent {
             kernel_selections: Some(HashMap::new()),
+            reconnect_to_running_kernels: Some(false),
         }
     }
 }
@@ -56,6 +71,9 @@ impl Settings for JupyterSettings {
                     settings.kernel_selections.insert(k.clone(), v.clone());
                 }
             }
+            if let Some(reconnect) = value.reconnect_to_running_kernels {
+                settings.reconnect_to_running_kernels = reconnect;
+            }
         }
 
         Ok(settings)
@@ -253,28 +253,40 @@ impl NativeRunningKernel {
 
             let stderr = process.stderr.take();
 
-            cx.spawn(async move |_cx| {
-                if stderr.is_none() {
-                    return;
-                }
-                let reader = BufReader::new(stderr.unwrap());
-                let mut lines = reader.lines();
-                while let Some(Ok(line)) = lines.next().await {
-                    log::error!("kernel: {}", line);
+            cx.spawn({
+                let session = session.clone();
+                async move |cx| {
+                    if stderr.is_none() {
+                        return;
+                    }
+                    let reader = BufReader::new(stderr.unwrap());
+                    let mut lines = reader.lines();
+                    while let Some(Ok(line)) = lines.next().await {
+                        log::error!("kernel: {}", line);
+                        session
+                            .update(cx, |session, _| session.append_startup_log(line))
+                            .ok();
+                    }
                 }
             })
             .detach();
 
             let stdout = process.stdout.take();
 
-            cx.spawn(async move |_cx| {
-                if stdout.is_none() {
-                    return;
-                }
-                let reader = BufReader::new(stdout.unwrap());
-                let mut lines = reader.lines();
-                while let Some(Ok(line)) = lines.next().await {
-                    log::info!("kernel: {}", line);
+            cx.spawn({
+                let session = session.clone();
+                async move |cx| {
+                    if stdout.is_none() {
+                        return;
+                    }
+                    let reader = BufReader::new(stdout.unwrap());
+                    let mut lines = reader.lines();
+                    while let Some(Ok(line)) = lines.next().await {
+                        log::info!("kernel: {}", line);
+                        session
+                            .update(cx, |session, _| session.append_startup_log(line))
+                            .ok();
+                    }
                 }
             })
             .detach();
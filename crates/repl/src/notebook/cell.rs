@@ -7,6 +7,7 @@ use gpui::{App, Entity, Hsla, Task, TextStyleRefinement, prelude::*};
 use language::{Buffer, Language, LanguageRegistry};
 use markdown_preview::{markdown_parser::parse_markdown, markdown_renderer::render_markdown_block};
 use nbformat::v4::{CellId, CellMetadata, CellType};
+use runtimelib::Stdio;
 use settings::Settings as _;
 use theme::ThemeSettings;
 use ui::{IconButtonShape, prelude::*};
@@ -91,8 +92,13 @@ fn convert_outputs(
     outputs
         .into_iter()
         .map(|output| match output {
-            nbformat::v4::Output::Stream { text, .. } => Output::Stream {
+            nbformat::v4::Output::Stream { name, text, .. } => Output::Stream {
                 content: cx.new(|cx| TerminalOutput::from(&text.0, window, cx)),
+                name: if name == "stderr" {
+                    Stdio::Stderr
+                } else {
+                    Stdio::Stdout
+                },
             },
             nbformat::v4::Output::DisplayData(display_data) => {
                 Output::new(&display_data.data, None, window, cx)
@@ -41,7 +41,7 @@ use gpui::{
     percentage,
 };
 use language::Buffer;
-use runtimelib::{ExecutionState, JupyterMessageContent, MimeBundle, MimeType};
+use runtimelib::{ExecutionState, JupyterMessageContent, MimeBundle, MimeType, Stdio};
 use ui::{Context, IntoElement, Styled, Tooltip, Window, div, prelude::*, v_flex};
 
 mod image;
@@ -111,6 +111,7 @@ pub enum Output {
     },
     Stream {
         content: Entity<TerminalOutput>,
+        name: Stdio,
     },
     Image {
         content: Entity<ImageView>,
@@ -264,6 +265,56 @@ impl Output {
         }
     }
 
+    /// Converts the output to a notebook output, following the nbformat v4 schema. Outputs
+    /// produced by Zed itself, rather than the kernel, are skipped.
+    pub(crate) fn to_notebook_output(&self, cx: &App) -> Option<serde_json::Value> {
+        let display_data = |mime_type: &str, data: serde_json::Value| {
+            let mut bundle = serde_json::Map::new();
+            bundle.insert(mime_type.to_string(), data);
+            serde_json::json!({
+                "output_type": "display_data",
+                "data": bundle,
+                "metadata": {},
+            })
+        };
+
+        match self {
+            Output::Plain { content, .. } => Some(display_data(
+                "text/plain",
+                content.read(cx).full_text().into(),
+            )),
+            Output::Stream { content, name } => Some(serde_json::json!({
+                "output_type": "stream",
+                "name": name,
+                "text": content.read(cx).full_text(),
+            })),
+            Output::Image { content, .. } => {
+                let (mime_type, data) = content.read(cx).notebook_data();
+                Some(display_data(mime_type, data.into()))
+            }
+            Output::Markdown { content, .. } => Some(display_data(
+                "text/markdown",
+                content.read(cx).raw_text().into(),
+            )),
+            Output::Table { content, .. } => Some(display_data(
+                "application/vnd.dataresource+json",
+                serde_json::to_value(&content.read(cx).table).ok()?,
+            )),
+            Output::ErrorOutput(error) => Some(serde_json::json!({
+                "output_type": "error",
+                "ename": error.ename,
+                "evalue": error.evalue,
+                "traceback": error
+                    .traceback
+                    .read(cx)
+                    .full_text()
+                    .lines()
+                    .collect::<Vec<_>>(),
+            })),
+            Output::Message(_) | Output::ClearOutputWaitMarker => None,
+        }
+    }
+
     pub fn new(
         data: &MimeBundle,
         display_id: Option<String>,
@@ -336,6 +387,14 @@ impl ExecutionView {
         }
     }
 
+    /// The error the execution raised, if any.
+    pub fn error(&self) -> Option<&ErrorView> {
+        self.outputs.iter().find_map(|output| match output {
+            Output::ErrorOutput(error) => Some(error),
+            _ => None,
+        })
+    }
+
     /// Accept a Jupyter message belonging to this execution
     pub fn push_message(
         &mut self,
@@ -358,7 +417,9 @@ impl ExecutionView {
             ),
             JupyterMessageContent::StreamContent(result) => {
                 // Previous stream data will combine together, handling colors, carriage returns, etc
-                if let Some(new_terminal) = self.apply_terminal_text(&result.text, window, cx) {
+                if let Some(new_terminal) =
+                    self.apply_terminal_text(&result.name, &result.text, window, cx)
+                {
                     new_terminal
                 } else {
                     return;
@@ -446,6 +507,7 @@ impl ExecutionView {
 
     fn apply_terminal_text(
         &mut self,
+        name: &Stdio,
         text: &str,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -453,20 +515,25 @@ impl ExecutionView {
         if let Some(last_output) = self.outputs.last_mut() {
             if let Output::Stream {
                 content: last_stream,
+                name: last_name,
             } = last_output
             {
                 // Don't need to add a new output, we already have a terminal output
-                // and can just update the most recent terminal output
-                last_stream.update(cx, |last_stream, cx| {
-                    last_stream.append_text(text, cx);
-                    cx.notify();
-                });
-                return None;
+                // and can just update the most recent terminal output. Streams are kept
+                // apart, so that they're exported under their own names.
+                if last_name == name {
+                    last_stream.update(cx, |last_stream, cx| {
+                        last_stream.append_text(text, cx);
+                        cx.notify();
+                    });
+                    return None;
+                }
             }
         }
 
         Some(Output::Stream {
             content: cx.new(|cx| TerminalOutput::from(text, window, cx)),
+            name: name.clone(),
         })
     }
 }
@@ -72,6 +72,23 @@ impl ImageView {
     }
 }
 
+impl ImageView {
+    /// Returns the MIME type and base64 encoded data of the image, as stored in notebooks.
+    pub(crate) fn notebook_data(&self) -> (&'static str, String) {
+        let mime_type = match self.clipboard_image.format {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Gif => "image/gif",
+            ImageFormat::Webp => "image/webp",
+            ImageFormat::Tiff => "image/tiff",
+            ImageFormat::Bmp => "image/bmp",
+            ImageFormat::Svg => "image/svg+xml",
+        };
+        let data = base64::engine::general_purpose::STANDARD.encode(&self.clipboard_image.bytes);
+        (mime_type, data)
+    }
+}
+
 impl Render for ImageView {
     fn render(&mut self, window: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
         let line_height = window.line_height();
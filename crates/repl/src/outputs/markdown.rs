@@ -37,6 +37,11 @@ impl MarkdownView {
             parsing_markdown_task: Some(task),
         }
     }
+
+    /// The markdown source of the output.
+    pub(crate) fn raw_text(&self) -> &str {
+        &self.raw_text
+    }
 }
 
 impl OutputContent for MarkdownView {
@@ -20,7 +20,8 @@ pub use crate::jupyter_settings::JupyterSettings;
 pub use crate::kernels::{Kernel, KernelSpecification, KernelStatus};
 pub use crate::repl_editor::*;
 pub use crate::repl_sessions_ui::{
-    ClearOutputs, Interrupt, ReplSessionsPage, Restart, Run, Sessions, Shutdown,
+    ClearOutputs, Interrupt, ReplSessionsPage, Restart, RestartAndRunAll, Run, RunAll, Sessions,
+    Shutdown, ShutdownAll, ToggleKernelPin, open_sessions,
 };
 use crate::repl_store::ReplStore;
 pub use crate::session::Session;
@@ -1,18 +1,24 @@
 //! REPL operations on an [`Editor`].
 
 use std::ops::Range;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::{Context as _, Result};
-use editor::Editor;
-use gpui::{App, Entity, WeakEntity, Window, prelude::*};
-use language::{BufferSnapshot, Language, LanguageName, Point};
-use project::{ProjectItem as _, WorktreeId};
+use anyhow::{Context as _, Result, anyhow};
+use editor::{Editor, MultiBuffer, scroll::Autoscroll};
+use futures::{StreamExt as _, channel::mpsc};
+use gpui::{App, AsyncWindowContext, Entity, WeakEntity, Window, prelude::*};
+use language::{BufferSnapshot, Language, LanguageName, OffsetRangeExt as _, Point};
+use project::{Fs, ProjectItem as _, WorktreeId};
+use settings::Settings as _;
+use workspace::notifications::NotifyTaskExt as _;
 
+use crate::outputs::{ExecutionStatus, ExecutionView};
 use crate::repl_store::ReplStore;
 use crate::session::SessionEvent;
 use crate::{
-    ClearOutputs, Interrupt, JupyterSettings, KernelSpecification, Restart, Session, Shutdown,
+    ClearOutputs, Interrupt, JupyterSettings, Kernel, KernelSpecification, Restart,
+    RestartAndRunAll, RunAll, Session, Shutdown, ToggleKernelPin,
 };
 
 pub fn assign_kernelspec(
@@ -30,10 +36,19 @@ pub fn assign_kernelspec(
         .context("editor is not in a worktree")?;
 
     store.update(cx, |store, cx| {
-        store.set_active_kernelspec(worktree_id, kernel_specification.clone(), cx);
+        // A pinned file keeps its own kernel, so selecting a kernel there
+        // re-pins it rather than changing the kernel for the whole worktree.
+        if let Some(path) =
+            pinned_kernel_path(&weak_editor, cx).filter(|path| store.is_kernel_pinned(path))
+        {
+            store.pin_kernelspec(path, &kernel_specification, cx);
+        } else {
+            store.set_active_kernelspec(worktree_id, kernel_specification.clone(), cx);
+        }
     });
 
     let fs = store.read(cx).fs().clone();
+    remember_default_kernel(&kernel_specification, fs.clone(), cx);
 
     if let Some(session) = store.read(cx).get_session(weak_editor.entity_id()).cloned() {
         // Drop previous session, start new one
@@ -47,6 +62,18 @@ pub fn assign_kernelspec(
     let session =
         cx.new(|cx| Session::new(weak_editor.clone(), fs, kernel_specification, window, cx));
 
+    insert_session(weak_editor, session, &store, cx);
+
+    Ok(())
+}
+
+/// Stores the session for the editor, removing it again once its kernel shuts down.
+fn insert_session(
+    weak_editor: WeakEntity<Editor>,
+    session: Entity<Session>,
+    store: &Entity<ReplStore>,
+    cx: &mut App,
+) {
     weak_editor
         .update(cx, |_editor, cx| {
             cx.notify();
@@ -66,10 +93,104 @@ pub fn assign_kernelspec(
         .ok();
 
     store.update(cx, |store, _cx| {
-        store.insert_session(weak_editor.entity_id(), session.clone());
+        store.insert_session(weak_editor.entity_id(), session);
     });
+}
 
-    Ok(())
+/// Reattaches the editor to the session that was running code from its file before the file
+/// was closed, if [`JupyterSettings::reconnect_to_running_kernels`] is on and that session's
+/// kernel is still running. Never starts a new kernel.
+pub fn reconnect_session(editor: WeakEntity<Editor>, cx: &mut App) {
+    if !JupyterSettings::get_global(cx).reconnect_to_running_kernels {
+        return;
+    }
+
+    let store = ReplStore::global(cx);
+    if !store.read(cx).is_enabled() || store.read(cx).get_session(editor.entity_id()).is_some() {
+        return;
+    }
+
+    let Some(editor_entity) = editor.upgrade() else {
+        return;
+    };
+    let Some(project_path) = editor_entity
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .as_singleton()
+        .and_then(|buffer| buffer.read(cx).project_path(cx))
+    else {
+        return;
+    };
+    let Some(language) = get_language(editor.clone(), cx) else {
+        return;
+    };
+    let Some(kernel_specification) =
+        kernelspec_for_editor(&store, &editor, project_path.worktree_id, language, cx)
+    else {
+        return;
+    };
+
+    let Some((previous_editor_id, session)) =
+        store
+            .read(cx)
+            .detached_session(&project_path, &kernel_specification, cx)
+    else {
+        return;
+    };
+    store.update(cx, |store, _cx| store.remove_session(previous_editor_id));
+
+    // A kernel that has died since the file was closed leaves the editor inactive.
+    if !matches!(session.read(cx).kernel, Kernel::RunningKernel(_)) {
+        return;
+    }
+
+    session.update(cx, |session, cx| session.reattach(editor_entity, cx));
+    insert_session(editor, session, &store, cx);
+}
+
+/// Saves the kernel as the default one for its language, so that new editors select it.
+fn remember_default_kernel(kernel_specification: &KernelSpecification, fs: Arc<dyn Fs>, cx: &App) {
+    let language = kernel_specification.language().to_lowercase();
+    let kernel_name = kernel_specification.name().to_string();
+    if JupyterSettings::get_global(cx).kernel_selection(&language) == Some(&kernel_name) {
+        return;
+    }
+
+    settings::update_settings_file::<JupyterSettings>(fs, cx, move |settings, _| {
+        settings
+            .kernel_selections
+            .get_or_insert_default()
+            .retain(|selection_language, _| !selection_language.eq_ignore_ascii_case(&language));
+        settings
+            .kernel_selections
+            .get_or_insert_default()
+            .insert(language, kernel_name);
+    });
+}
+
+/// Returns the name of the default kernel remembered for the language of the editor.
+pub fn default_kernel_name(editor: WeakEntity<Editor>, cx: &mut App) -> Option<String> {
+    let language = get_language(editor, cx)?;
+    JupyterSettings::get_global(cx)
+        .kernel_selection(language.code_fence_block_name().as_ref())
+        .cloned()
+}
+
+/// Forgets the default kernel remembered for the language of the editor.
+pub fn clear_default_kernel(editor: WeakEntity<Editor>, cx: &mut App) {
+    let Some(language) = get_language(editor, cx) else {
+        return;
+    };
+    let language = language.code_fence_block_name().to_string();
+    let fs = ReplStore::global(cx).read(cx).fs().clone();
+    settings::update_settings_file::<JupyterSettings>(fs, cx, move |settings, _| {
+        if let Some(kernel_selections) = settings.kernel_selections.as_mut() {
+            kernel_selections.retain(|selection_language, _| {
+                !selection_language.eq_ignore_ascii_case(&language)
+            });
+        }
+    });
 }
 
 pub fn run(
@@ -87,15 +208,151 @@ pub fn run(
     let selected_range = editor
         .update(cx, |editor, cx| editor.selections.newest_adjusted(cx))
         .range();
+    run_range(editor, selected_range, move_down, window, cx)?;
+    Ok(())
+}
+
+/// Runs all the cells of the editor, from top to bottom.
+pub fn run_all(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) -> Result<()> {
+    let store = ReplStore::global(cx);
+    if !store.read(cx).is_enabled() {
+        return Ok(());
+    }
+
+    let editor = editor.upgrade().context("editor was dropped")?;
+    let max_point = editor.read(cx).buffer().read(cx).read(cx).max_point();
+    run_range(editor, Point::zero()..max_point, false, window, cx)?;
+    Ok(())
+}
+
+/// Runs the editor's cells one at a time, from top to bottom, waiting for each to finish
+/// before starting the next. Cells are the code fences of a markdown file, the Jupytext cells
+/// of a script that has them, or else the whole script.
+///
+/// Stops at the first cell that raises an error, and moves the cursor to it.
+pub fn run_all_cells(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+    if !has_active_session(editor.clone(), cx) {
+        return;
+    }
+    let Some(buffer) = editor
+        .upgrade()
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+    else {
+        return;
+    };
+
+    let cells = {
+        let buffer = buffer.read(cx).snapshot();
+        let (cells, _) = runnable_ranges(&buffer, Point::zero()..buffer.max_point());
+        cells
+            .into_iter()
+            .map(|cell| buffer.anchor_before(cell.start)..buffer.anchor_after(cell.end))
+            .collect::<Vec<_>>()
+    };
+
+    window
+        .spawn(cx, async move |cx| {
+            for cell in cells {
+                let cell = buffer.read_with(cx, |buffer, _| cell.to_point(buffer))?;
+                let executions = cx.update(|window, cx| {
+                    let editor = editor.upgrade().context("editor was dropped")?;
+                    run_range(editor, cell.clone(), false, window, cx)
+                })??;
+
+                for execution in executions {
+                    if let Err(error) = wait_for_execution(execution.downgrade(), cx).await {
+                        editor.update_in(cx, |editor, window, cx| {
+                            editor.change_selections(
+                                Some(Autoscroll::center()),
+                                window,
+                                cx,
+                                |selections| selections.select_ranges([cell.start..cell.start]),
+                            );
+                        })?;
+                        return Err(error.context("Cell failed"));
+                    }
+                }
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_notify_err(window, cx);
+}
+
+/// Waits for the execution to finish, re-checking its status whenever it changes.
+///
+/// Fails if the execution errors, or if its output block goes away before it finishes
+/// (the cell was edited, its output closed, or the session shut down).
+async fn wait_for_execution(
+    execution: WeakEntity<ExecutionView>,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let (changed_tx, mut changed_rx) = mpsc::unbounded();
+    let _subscriptions = cx.update(|_, cx| {
+        let execution = execution.upgrade().context("cell output was removed")?;
+        let released_tx = changed_tx.clone();
+        anyhow::Ok([
+            cx.observe(&execution, move |_, _| {
+                changed_tx.unbounded_send(()).ok();
+            }),
+            cx.observe_release(&execution, move |_, _| {
+                released_tx.unbounded_send(()).ok();
+            }),
+        ])
+    })??;
+
+    loop {
+        let finished = execution
+            .read_with(cx, execution_finished)
+            .map_err(|_| anyhow!("Cell output was removed before it finished"))??;
+        if finished {
+            return Ok(());
+        }
+        if changed_rx.next().await.is_none() {
+            return Err(anyhow!("Cell output was removed before it finished"));
+        }
+    }
+}
+
+/// Returns whether the execution has finished, or the error it failed with.
+fn execution_finished(execution: &ExecutionView, _: &App) -> Result<bool> {
+    match &execution.status {
+        ExecutionStatus::Finished => match execution.error() {
+            Some(error) => Err(anyhow!("{}: {}", error.ename, error.evalue)),
+            None => Ok(true),
+        },
+        ExecutionStatus::KernelErrored(error) => Err(anyhow!("Kernel error: {error}")),
+        ExecutionStatus::ShuttingDown | ExecutionStatus::Shutdown => {
+            Err(anyhow!("Kernel shut down"))
+        }
+        // Executions requested while the kernel restarts are never sent to it.
+        ExecutionStatus::Restarting => Err(anyhow!("Kernel is restarting")),
+        ExecutionStatus::Unknown
+        | ExecutionStatus::ConnectingToKernel
+        | ExecutionStatus::Queued
+        | ExecutionStatus::Executing => Ok(false),
+    }
+}
+
+/// Runs the cells in the given range, returning their executions.
+fn run_range(
+    editor: Entity<Editor>,
+    selected_range: Range<Point>,
+    move_down: bool,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<Vec<Entity<ExecutionView>>> {
+    let store = ReplStore::global(cx);
     let multibuffer = editor.read(cx).buffer().clone();
     let Some(buffer) = multibuffer.read(cx).as_singleton() else {
-        return Ok(());
+        return Ok(Vec::new());
     };
 
     let Some(project_path) = buffer.read(cx).project_path(cx) else {
-        return Ok(());
+        return Ok(Vec::new());
     };
 
+    let mut executions = Vec::new();
+
     let (runnable_ranges, next_cell_point) =
         runnable_ranges(&buffer.read(cx).snapshot(), selected_range);
 
@@ -104,10 +361,14 @@ pub fn run(
             continue;
         };
 
-        let kernel_specification = store
-            .read(cx)
-            .active_kernelspec(project_path.worktree_id, Some(language.clone()), cx)
-            .ok_or_else(|| anyhow::anyhow!("No kernel found for language: {}", language.name()))?;
+        let kernel_specification = kernelspec_for_editor(
+            &store,
+            &editor.downgrade(),
+            project_path.worktree_id,
+            language.clone(),
+            cx,
+        )
+        .ok_or_else(|| anyhow::anyhow!("No kernel found for language: {}", language.name()))?;
 
         let fs = store.read(cx).fs().clone();
 
@@ -155,7 +416,7 @@ pub fn run(
             next_cursor = next_cell_point.map(|point| snapshot.anchor_after(point));
         }
 
-        session.update(cx, |session, cx| {
+        let execution = session.update(cx, |session, cx| {
             session.execute(
                 selected_text,
                 anchor_range,
@@ -163,11 +424,12 @@ pub fn run(
                 move_down,
                 window,
                 cx,
-            );
+            )
         });
+        executions.extend(execution);
     }
 
-    anyhow::Ok(())
+    anyhow::Ok(executions)
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -209,9 +471,7 @@ pub fn session(editor: WeakEntity<Editor>, cx: &mut App) -> SessionSupport {
         return SessionSupport::Unsupported;
     };
 
-    let kernelspec = store
-        .read(cx)
-        .active_kernelspec(worktree_id, Some(language.clone()), cx);
+    let kernelspec = kernelspec_for_editor(&store, &editor, worktree_id, language.clone(), cx);
 
     match kernelspec {
         Some(kernelspec) => SessionSupport::Inactive(kernelspec),
@@ -225,6 +485,66 @@ pub fn session(editor: WeakEntity<Editor>, cx: &mut App) -> SessionSupport {
     }
 }
 
+fn kernelspec_for_editor(
+    store: &Entity<ReplStore>,
+    editor: &WeakEntity<Editor>,
+    worktree_id: WorktreeId,
+    language: Arc<Language>,
+    cx: &App,
+) -> Option<KernelSpecification> {
+    let store = store.read(cx);
+    pinned_kernel_path(editor, cx)
+        .and_then(|path| store.pinned_kernelspec(&path, worktree_id))
+        .or_else(|| store.active_kernelspec(worktree_id, Some(language), cx))
+}
+
+/// Returns the path a kernel pin is stored under for the editor's file.
+fn pinned_kernel_path(editor: &WeakEntity<Editor>, cx: &App) -> Option<PathBuf> {
+    let editor = editor.upgrade()?;
+    let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+    let file = buffer.read(cx).file()?;
+    Some(match file.as_local() {
+        Some(file) => file.abs_path(cx),
+        None => file.full_path(cx),
+    })
+}
+
+pub fn is_kernel_pinned(editor: WeakEntity<Editor>, cx: &App) -> bool {
+    pinned_kernel_path(&editor, cx)
+        .is_some_and(|path| ReplStore::global(cx).read(cx).is_kernel_pinned(&path))
+}
+
+/// Pins the editor's current kernel to its file, or unpins it if it is already pinned.
+///
+/// While pinned, the file keeps running code on that kernel even when a
+/// different kernel is selected for its worktree from another editor. Pins
+/// are persisted, so they also apply when the file is reopened.
+pub fn toggle_kernel_pin(editor: WeakEntity<Editor>, cx: &mut App) {
+    let store = ReplStore::global(cx);
+    let Some(path) = pinned_kernel_path(&editor, cx) else {
+        return;
+    };
+
+    if store.read(cx).is_kernel_pinned(&path) {
+        store.update(cx, |store, cx| {
+            store.unpin_kernelspec(&path, cx);
+            cx.notify();
+        });
+        return;
+    }
+
+    let kernel_specification = match session(editor, cx) {
+        SessionSupport::ActiveSession(session) => session.read(cx).kernel_specification.clone(),
+        SessionSupport::Inactive(kernel_specification) => kernel_specification,
+        SessionSupport::RequiresSetup(_) | SessionSupport::Unsupported => return,
+    };
+
+    store.update(cx, |store, cx| {
+        store.pin_kernelspec(path, &kernel_specification, cx);
+        cx.notify();
+    });
+}
+
 pub fn clear_outputs(editor: WeakEntity<Editor>, cx: &mut App) {
     let store = ReplStore::global(cx);
     let entity_id = editor.entity_id();
@@ -263,6 +583,135 @@ pub fn shutdown(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
     });
 }
 
+/// Shuts down the kernels of all sessions, across editors, the same way as [`shutdown`].
+pub fn shutdown_all(window: &mut Window, cx: &mut App) {
+    let sessions = ReplStore::global(cx)
+        .read(cx)
+        .sessions()
+        .cloned()
+        .collect::<Vec<_>>();
+    for session in sessions {
+        session.update(cx, |session, cx| {
+            if !matches!(session.kernel, Kernel::ShuttingDown | Kernel::Shutdown) {
+                session.shutdown(window, cx);
+                cx.notify();
+            }
+        });
+    }
+}
+
+/// Whether any session has a kernel that isn't shut down or shutting down.
+pub fn has_running_kernels(cx: &App) -> bool {
+    ReplStore::global(cx).read(cx).sessions().any(|session| {
+        !matches!(
+            session.read(cx).kernel,
+            Kernel::ShuttingDown | Kernel::Shutdown
+        )
+    })
+}
+
+/// Whether any session's kernel is shutting down.
+pub fn is_shutting_down_kernels(cx: &App) -> bool {
+    ReplStore::global(cx)
+        .read(cx)
+        .sessions()
+        .any(|session| matches!(session.read(cx).kernel, Kernel::ShuttingDown))
+}
+
+/// Whether the editor has a REPL session.
+pub fn has_active_session(editor: WeakEntity<Editor>, cx: &App) -> bool {
+    ReplStore::global(cx)
+        .read(cx)
+        .get_session(editor.entity_id())
+        .is_some()
+}
+
+/// Whether the editor has a session with outputs to export.
+pub fn session_has_outputs(editor: WeakEntity<Editor>, cx: &App) -> bool {
+    ReplStore::global(cx)
+        .read(cx)
+        .get_session(editor.entity_id())
+        .is_some_and(|session| session.read(cx).has_outputs(cx))
+}
+
+/// Whether the editor's kernel failed to launch, so its startup log can be viewed.
+pub fn kernel_launch_errored(editor: WeakEntity<Editor>, cx: &App) -> bool {
+    ReplStore::global(cx)
+        .read(cx)
+        .get_session(editor.entity_id())
+        .is_some_and(|session| matches!(session.read(cx).kernel, Kernel::ErroredLaunch(_)))
+}
+
+/// Opens what the editor's kernel process wrote while starting in a read-only buffer.
+pub fn open_startup_log(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+    let Some(session) = ReplStore::global(cx)
+        .read(cx)
+        .get_session(editor.entity_id())
+        .cloned()
+    else {
+        return;
+    };
+    let Some(workspace) = editor
+        .upgrade()
+        .and_then(|editor| editor.read(cx).workspace())
+    else {
+        return;
+    };
+
+    let session = session.read(cx);
+    let mut log = session.startup_log();
+    if log.is_empty() {
+        log = "The kernel didn't write any output.\n".to_string();
+    }
+    let title = format!("Startup Log: {}", session.kernel_specification.name());
+
+    workspace.update(cx, |workspace, cx| {
+        let project = workspace.project().clone();
+        let buffer = project.update(cx, |project, cx| {
+            project.create_local_buffer(&log, None, cx)
+        });
+        let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx).with_title(title.clone()));
+        let log_editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(buffer, Some(project), window, cx);
+            editor.set_read_only(true);
+            editor.set_breadcrumb_header(title);
+            editor
+        });
+        workspace.add_item_to_active_pane(Box::new(log_editor), None, true, window, cx);
+    });
+}
+
+/// Prompts for a path and saves the editor's session, its executed code and outputs, there
+/// as a Jupyter notebook.
+pub fn export_session(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+    let store = ReplStore::global(cx);
+    let Some(session) = store.read(cx).get_session(editor.entity_id()).cloned() else {
+        return;
+    };
+    let Some(notebook) = session.read(cx).to_notebook(cx) else {
+        return;
+    };
+    let fs = store.read(cx).fs().clone();
+    let directory = editor
+        .upgrade()
+        .and_then(|editor| editor.read(cx).working_directory(cx))
+        .unwrap_or_else(|| util::paths::home_dir().clone());
+    let path = cx.prompt_for_new_path(&directory);
+
+    window
+        .spawn(cx, async move |_| {
+            let Some(mut path) = path.await?? else {
+                return Ok(());
+            };
+            if path.extension().is_none() {
+                path.set_extension("ipynb");
+            }
+            let contents = serde_json::to_string_pretty(&notebook)?;
+            fs.atomic_write(path, contents).await
+        })
+        .detach_and_notify_err(window, cx);
+}
+
 pub fn restart(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
     let Some(editor) = editor.upgrade() else {
         return;
@@ -284,6 +733,58 @@ pub fn restart(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
     });
 }
 
+/// Restarts the editor's kernel, then runs all of its cells once the kernel is connected
+/// again. The run is aborted if the kernel fails to start.
+pub fn restart_and_run_all(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+    let Some(session) = ReplStore::global(cx)
+        .read(cx)
+        .get_session(editor.entity_id())
+        .cloned()
+    else {
+        return;
+    };
+
+    session.update(cx, |session, cx| {
+        session.restart(window, cx);
+        cx.notify();
+    });
+
+    window
+        .spawn(cx, async move |cx| {
+            wait_for_kernel_restart(&session, cx).await?;
+            cx.update(|window, cx| run_all(editor, window, cx))?
+        })
+        .detach_and_notify_err(window, cx);
+}
+
+async fn wait_for_kernel_restart(
+    session: &Entity<Session>,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let (changed_tx, mut changed_rx) = mpsc::unbounded();
+    let _subscription = cx.update(|_, cx| {
+        cx.observe(session, move |_, _| {
+            changed_tx.unbounded_send(()).ok();
+        })
+    })?;
+
+    loop {
+        let connected = session.read_with(cx, |session, _| match &session.kernel {
+            Kernel::ErroredLaunch(error) => Err(anyhow!("Kernel failed to restart: {error}")),
+            Kernel::ShuttingDown | Kernel::Shutdown => {
+                Err(anyhow!("Kernel shut down before restarting"))
+            }
+            kernel => Ok(kernel.status().is_connected()),
+        })??;
+        if connected {
+            return Ok(());
+        }
+        if changed_rx.next().await.is_none() {
+            return Err(anyhow!("Kernel session was closed before restarting"));
+        }
+    }
+}
+
 pub fn setup_editor_session_actions(editor: &mut Editor, editor_handle: WeakEntity<Editor>) {
     editor
         .register_action({
@@ -336,6 +837,45 @@ pub fn setup_editor_session_actions(editor: &mut Editor, editor_handle: WeakEnti
             }
         })
         .detach();
+
+    editor
+        .register_action({
+            let editor_handle = editor_handle.clone();
+            move |_: &RestartAndRunAll, window, cx| {
+                if !JupyterSettings::enabled(cx) {
+                    return;
+                }
+
+                crate::restart_and_run_all(editor_handle.clone(), window, cx);
+            }
+        })
+        .detach();
+
+    editor
+        .register_action({
+            let editor_handle = editor_handle.clone();
+            move |_: &RunAll, window, cx| {
+                if !JupyterSettings::enabled(cx) {
+                    return;
+                }
+
+                crate::run_all_cells(editor_handle.clone(), window, cx);
+            }
+        })
+        .detach();
+
+    editor
+        .register_action({
+            let editor_handle = editor_handle.clone();
+            move |_: &ToggleKernelPin, _, cx| {
+                if !JupyterSettings::enabled(cx) {
+                    return;
+                }
+
+                crate::toggle_kernel_pin(editor_handle.clone(), cx);
+            }
+        })
+        .detach();
 }
 
 fn cell_range(buffer: &BufferSnapshot, start_row: u32, end_row: u32) -> Range<Point> {
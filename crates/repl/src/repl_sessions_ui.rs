@@ -3,7 +3,7 @@ use gpui::{
     AnyElement, App, Entity, EventEmitter, FocusHandle, Focusable, Subscription, actions,
     prelude::*,
 };
-use project::ProjectItem as _;
+use project::{ProjectItem as _, WorktreeId};
 use ui::{ButtonLike, ElevationIndex, KeyBinding, prelude::*};
 use util::ResultExt as _;
 use workspace::WorkspaceId;
@@ -11,6 +11,7 @@ use workspace::item::ItemEvent;
 use workspace::{Workspace, item::Item};
 
 use crate::jupyter_settings::JupyterSettings;
+use crate::repl_editor::worktree_id_for_editor;
 use crate::repl_store::ReplStore;
 
 actions!(
@@ -22,7 +23,11 @@ actions!(
         Sessions,
         Interrupt,
         Shutdown,
+        ShutdownAll,
         Restart,
+        RestartAndRunAll,
+        RunAll,
+        ToggleKernelPin,
         RefreshKernelspecs
     ]
 );
@@ -31,24 +36,11 @@ pub fn init(cx: &mut App) {
     cx.observe_new(
         |workspace: &mut Workspace, _window, _cx: &mut Context<Workspace>| {
             workspace.register_action(|workspace, _: &Sessions, window, cx| {
-                let existing = workspace
-                    .active_pane()
-                    .read(cx)
-                    .items()
-                    .find_map(|item| item.downcast::<ReplSessionsPage>());
-
-                if let Some(existing) = existing {
-                    workspace.activate_item(&existing, true, true, window, cx);
-                } else {
-                    let repl_sessions_page = ReplSessionsPage::new(window, cx);
-                    workspace.add_item_to_active_pane(
-                        Box::new(repl_sessions_page),
-                        None,
-                        true,
-                        window,
-                        cx,
-                    )
-                }
+                open_sessions(workspace, None, window, cx);
+            });
+
+            workspace.register_action(|_workspace, _: &ShutdownAll, window, cx| {
+                crate::shutdown_all(window, cx);
             });
 
             workspace.register_action(|_workspace, _: &RefreshKernelspecs, _, cx| {
@@ -111,6 +103,12 @@ pub fn init(cx: &mut App) {
                     }
                 }
 
+                // Reattaching updates the editor, so wait until it's no longer being updated.
+                cx.defer({
+                    let editor_handle = editor_handle.clone();
+                    move |cx| crate::reconnect_session(editor_handle, cx)
+                });
+
                 editor
                     .register_action({
                         let editor_handle = editor_handle.clone();
@@ -142,8 +140,37 @@ pub fn init(cx: &mut App) {
     .detach();
 }
 
+/// Opens the REPL sessions page, reusing an open one.
+///
+/// With a `worktree_id`, the page only lists the sessions of that worktree until the user
+/// chooses to show all of them.
+pub fn open_sessions(
+    workspace: &mut Workspace,
+    worktree_id: Option<WorktreeId>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let existing = workspace
+        .active_pane()
+        .read(cx)
+        .items()
+        .find_map(|item| item.downcast::<ReplSessionsPage>());
+
+    if let Some(existing) = existing {
+        existing.update(cx, |page, cx| page.set_worktree_filter(worktree_id, cx));
+        workspace.activate_item(&existing, true, true, window, cx);
+    } else {
+        let repl_sessions_page = ReplSessionsPage::new(window, cx);
+        repl_sessions_page.update(cx, |page, cx| page.set_worktree_filter(worktree_id, cx));
+        workspace.add_item_to_active_pane(Box::new(repl_sessions_page), None, true, window, cx)
+    }
+}
+
 pub struct ReplSessionsPage {
     focus_handle: FocusHandle,
+    /// The worktree whose sessions are listed, or `None` to list every session.
+    worktree_filter: Option<WorktreeId>,
+    show_all: bool,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -161,10 +188,38 @@ impl ReplSessionsPage {
 
             Self {
                 focus_handle,
+                worktree_filter: None,
+                show_all: false,
                 _subscriptions: subscriptions,
             }
         })
     }
+
+    pub fn set_worktree_filter(&mut self, worktree_id: Option<WorktreeId>, cx: &mut Context<Self>) {
+        self.worktree_filter = worktree_id;
+        self.show_all = false;
+        cx.notify();
+    }
+
+    fn render_filter_toggle(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        self.worktree_filter?;
+        let label = if self.show_all {
+            "Only Show This Worktree's Sessions"
+        } else {
+            "Show All Sessions"
+        };
+        Some(
+            h_flex().child(
+                Button::new("toggle-session-filter", label)
+                    .style(ButtonStyle::Subtle)
+                    .label_size(LabelSize::Small)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.show_all = !this.show_all;
+                        cx.notify();
+                    })),
+            ),
+        )
+    }
 }
 
 impl EventEmitter<ItemEvent> for ReplSessionsPage {}
@@ -217,6 +272,17 @@ impl Render for ReplSessionsPage {
                 store.sessions().cloned().collect::<Vec<_>>(),
             )
         });
+        let total_sessions = sessions.len();
+        let sessions = match self.worktree_filter.filter(|_| !self.show_all) {
+            Some(worktree_id) => sessions
+                .into_iter()
+                .filter(|session| {
+                    let editor = session.read(cx).editor();
+                    worktree_id_for_editor(editor, cx) == Some(worktree_id)
+                })
+                .collect(),
+            None => sessions,
+        };
 
         // When there are no kernel specifications, show a link to the Zed docs explaining how to
         // install kernels. It can be assumed they don't have a running kernel if we have no
@@ -245,15 +311,24 @@ impl Render for ReplSessionsPage {
         // When there are no sessions, show the command to run code in an editor
         if sessions.is_empty() {
             let instructions = "To run code in a Jupyter kernel, select some code and use the 'repl::Run' command.";
+            let title = if total_sessions > 0 {
+                "No Jupyter Kernel Sessions in This Worktree"
+            } else {
+                "No Jupyter Kernel Sessions"
+            };
 
-            return ReplSessionsContainer::new("No Jupyter Kernel Sessions").child(
-                v_flex()
-                    .child(Label::new(instructions))
-                    .children(KeyBinding::for_action(&Run, window, cx)),
-            );
+            return ReplSessionsContainer::new(title)
+                .children(self.render_filter_toggle(cx))
+                .child(
+                    v_flex()
+                        .child(Label::new(instructions))
+                        .children(KeyBinding::for_action(&Run, window, cx)),
+                );
         }
 
-        ReplSessionsContainer::new("Jupyter Kernel Sessions").children(sessions)
+        ReplSessionsContainer::new("Jupyter Kernel Sessions")
+            .children(self.render_filter_toggle(cx))
+            .children(sessions)
     }
 }
 
@@ -1,13 +1,16 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Result;
 use collections::HashMap;
 use command_palette_hooks::CommandPaletteFilter;
+use db::kvp::KEY_VALUE_STORE;
 use gpui::{App, Context, Entity, EntityId, Global, Subscription, Task, prelude::*};
 use jupyter_websocket_client::RemoteServer;
 use language::Language;
-use project::{Fs, Project, WorktreeId};
+use project::{Fs, Project, ProjectPath, WorktreeId};
 use settings::{Settings, SettingsStore};
+use util::ResultExt as _;
 
 use crate::kernels::{
     list_remote_kernelspecs, local_kernel_specifications, python_env_kernel_specifications,
@@ -24,12 +27,14 @@ pub struct ReplStore {
     sessions: HashMap<EntityId, Entity<Session>>,
     kernel_specifications: Vec<KernelSpecification>,
     selected_kernel_for_worktree: HashMap<WorktreeId, KernelSpecification>,
+    pinned_kernels: HashMap<PathBuf, String>,
     kernel_specifications_for_worktree: HashMap<WorktreeId, Vec<KernelSpecification>>,
     _subscriptions: Vec<Subscription>,
 }
 
 impl ReplStore {
     const NAMESPACE: &'static str = "repl";
+    const PINNED_KERNELS_KEY: &'static str = "repl_pinned_kernels";
 
     pub(crate) fn init(fs: Arc<dyn Fs>, cx: &mut App) {
         let store = cx.new(move |cx| Self::new(fs, cx));
@@ -58,8 +63,10 @@ impl ReplStore {
             _subscriptions: subscriptions,
             kernel_specifications_for_worktree: HashMap::default(),
             selected_kernel_for_worktree: HashMap::default(),
+            pinned_kernels: HashMap::default(),
         };
         this.on_enabled_changed(cx);
+        this.load_pinned_kernels(cx);
         this
     }
 
@@ -221,27 +228,98 @@ impl ReplStore {
         }
     }
 
+    /// Returns the kernel pinned to the file at the given path, if any.
+    ///
+    /// A pinned kernel takes precedence over the kernel selected for the
+    /// file's worktree, so the file keeps using it regardless of which
+    /// kernel other editors select. Pins are stored by kernel name, so a pinned
+    /// kernel is only found once its specification has been discovered.
+    pub fn pinned_kernelspec(
+        &self,
+        path: &Path,
+        worktree_id: WorktreeId,
+    ) -> Option<KernelSpecification> {
+        let kernel_name = self.pinned_kernels.get(path)?;
+        self.kernel_specifications_for_worktree(worktree_id)
+            .find(|kernel_specification| kernel_specification.name().as_ref() == kernel_name)
+            .cloned()
+    }
+
+    pub fn is_kernel_pinned(&self, path: &Path) -> bool {
+        self.pinned_kernels.contains_key(path)
+    }
+
+    pub fn pin_kernelspec(
+        &mut self,
+        path: PathBuf,
+        kernelspec: &KernelSpecification,
+        cx: &mut Context<Self>,
+    ) {
+        self.pinned_kernels
+            .insert(path, kernelspec.name().to_string());
+        self.save_pinned_kernels(cx);
+    }
+
+    pub fn unpin_kernelspec(&mut self, path: &Path, cx: &mut Context<Self>) {
+        if self.pinned_kernels.remove(path).is_some() {
+            self.save_pinned_kernels(cx);
+        }
+    }
+
+    fn load_pinned_kernels(&self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            let pinned_kernels = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(Self::PINNED_KERNELS_KEY) })
+                .await
+                .log_err()
+                .flatten()
+                .and_then(|pinned_kernels| {
+                    serde_json::from_str::<HashMap<PathBuf, String>>(&pinned_kernels).log_err()
+                })
+                .unwrap_or_default();
+            this.update(cx, |this, cx| {
+                // Files pinned while loading keep their new pins, and have overwritten the
+                // stored ones, so those are saved again.
+                let pinned_while_loading = !this.pinned_kernels.is_empty();
+                for (path, kernel_name) in pinned_kernels {
+                    this.pinned_kernels.entry(path).or_insert(kernel_name);
+                }
+                if pinned_while_loading {
+                    this.save_pinned_kernels(cx);
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Persists the pinned kernels, so files stay pinned across restarts.
+    fn save_pinned_kernels(&self, cx: &App) {
+        let Some(pinned_kernels) = serde_json::to_string(&self.pinned_kernels).log_err() else {
+            return;
+        };
+        db::write_and_log(cx, move || {
+            KEY_VALUE_STORE.write_kvp(Self::PINNED_KERNELS_KEY.to_string(), pinned_kernels)
+        });
+    }
+
     fn kernelspec_legacy_by_lang_only(
         &self,
         language_at_cursor: Arc<Language>,
         cx: &App,
     ) -> Option<KernelSpecification> {
         let settings = JupyterSettings::get_global(cx);
-        let selected_kernel = settings
-            .kernel_selections
-            .get(language_at_cursor.code_fence_block_name().as_ref());
+        let selected_kernel =
+            settings.kernel_selection(language_at_cursor.code_fence_block_name().as_ref());
 
         let found_by_name = self
             .kernel_specifications
             .iter()
-            .find(|runtime_specification| {
-                if let (Some(selected), KernelSpecification::Jupyter(runtime_specification)) =
-                    (selected_kernel, runtime_specification)
-                {
-                    // Top priority is the selected kernel
-                    return runtime_specification.name.to_lowercase() == selected.to_lowercase();
-                }
-                false
+            .find(|kernel_specification| {
+                // Top priority is the selected kernel
+                selected_kernel.is_some_and(|selected| {
+                    kernel_specification.name().to_lowercase() == selected.to_lowercase()
+                })
             })
             .cloned();
 
@@ -279,4 +357,21 @@ impl ReplStore {
     pub fn remove_session(&mut self, entity_id: EntityId) {
         self.sessions.remove(&entity_id);
     }
+
+    /// Finds a session whose editor was closed that ran code from `project_path` in a
+    /// kernel started from `kernel_specification`.
+    pub fn detached_session(
+        &self,
+        project_path: &ProjectPath,
+        kernel_specification: &KernelSpecification,
+        cx: &App,
+    ) -> Option<(EntityId, Entity<Session>)> {
+        self.sessions.iter().find_map(|(editor_id, session)| {
+            let session_ref = session.read(cx);
+            (session_ref.editor().upgrade().is_none()
+                && session_ref.project_path() == Some(project_path)
+                && &session_ref.kernel_specification == kernel_specification)
+                .then(|| (*editor_id, session.clone()))
+        })
+    }
 }
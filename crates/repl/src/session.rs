@@ -20,12 +20,18 @@ use gpui::{
     Context, Entity, EventEmitter, Render, Subscription, Task, WeakEntity, Window, div, prelude::*,
 };
 use language::Point;
-use project::Fs;
+use project::{Fs, ProjectItem as _, ProjectPath};
 use runtimelib::{
     ExecuteRequest, ExecutionState, InterruptRequest, JupyterMessage, JupyterMessageContent,
     ShutdownRequest,
 };
-use std::{env::temp_dir, ops::Range, sync::Arc, time::Duration};
+use std::{
+    collections::VecDeque,
+    env::temp_dir,
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use theme::ActiveTheme;
 use ui::{IconButtonShape, Tooltip, prelude::*};
 use util::ResultExt as _;
@@ -36,9 +42,64 @@ pub struct Session {
     pub kernel: Kernel,
     blocks: HashMap<String, EditorBlock>,
     pub kernel_specification: KernelSpecification,
+    /// When the kernel last became busy, if it still is.
+    execution_started_at: Option<Instant>,
+    /// Notifies every second while the kernel is busy, to refresh the elapsed time.
+    execution_ticker: Option<Task<()>>,
+    /// What the kernel process has written since it was started, to diagnose failed launches.
+    startup_log: StartupLog,
+    /// The file the session runs code from, to reattach to when the file is reopened.
+    project_path: Option<ProjectPath>,
     _buffer_subscription: Subscription,
 }
 
+/// The longest [`StartupLog`] kept, in bytes. Older lines are dropped past this.
+const MAX_STARTUP_LOG_LEN: usize = 64 * 1024;
+
+#[derive(Default)]
+struct StartupLog {
+    lines: VecDeque<String>,
+    len: usize,
+    truncated: bool,
+}
+
+/// Appended to a line cut short because it alone exceeded [`MAX_STARTUP_LOG_LEN`].
+const TRUNCATED_LINE_SUFFIX: &str = " [line truncated]";
+
+impl StartupLog {
+    fn push_line(&mut self, mut line: String) {
+        if line.len() + 1 > MAX_STARTUP_LOG_LEN {
+            let mut end = MAX_STARTUP_LOG_LEN - 1 - TRUNCATED_LINE_SUFFIX.len();
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            line.truncate(end);
+            line.push_str(TRUNCATED_LINE_SUFFIX);
+        }
+        self.len += line.len() + 1;
+        self.lines.push_back(line);
+        while self.len > MAX_STARTUP_LOG_LEN {
+            let Some(line) = self.lines.pop_front() else {
+                break;
+            };
+            self.len -= line.len() + 1;
+            self.truncated = true;
+        }
+    }
+
+    fn text(&self) -> String {
+        let mut text = String::with_capacity(self.len);
+        if self.truncated {
+            text.push_str("[earlier output truncated]\n");
+        }
+        for line in &self.lines {
+            text.push_str(line);
+            text.push('\n');
+        }
+        text
+    }
+}
+
 struct EditorBlock {
     code_range: Range<Anchor>,
     invalidation_anchor: Anchor,
@@ -207,6 +268,9 @@ impl Session {
             }
             None => Subscription::new(|| {}),
         };
+        let project_path = editor
+            .upgrade()
+            .and_then(|editor| editor_project_path(&editor, cx));
 
         let editor_handle = editor.clone();
 
@@ -222,6 +286,10 @@ impl Session {
             kernel: Kernel::StartingKernel(Task::ready(()).shared()),
             blocks: HashMap::default(),
             kernel_specification,
+            execution_started_at: None,
+            execution_ticker: None,
+            startup_log: StartupLog::default(),
+            project_path,
             _buffer_subscription: subscription,
         };
 
@@ -246,6 +314,7 @@ impl Session {
         );
 
         let session_view = cx.entity().clone();
+        self.startup_log = StartupLog::default();
 
         let kernel = match self.kernel_specification.clone() {
             KernelSpecification::Jupyter(kernel_specification)
@@ -292,6 +361,43 @@ impl Session {
         cx.notify();
     }
 
+    /// The editor this session runs code from.
+    pub fn editor(&self) -> WeakEntity<Editor> {
+        self.editor.clone()
+    }
+
+    pub fn project_path(&self) -> Option<&ProjectPath> {
+        self.project_path.as_ref()
+    }
+
+    /// Moves a session whose editor was closed over to a new editor for the same file,
+    /// keeping its kernel running. Outputs shown in the closed editor aren't restored.
+    pub fn reattach(&mut self, editor: Entity<Editor>, cx: &mut Context<Self>) {
+        let buffer = editor.read(cx).buffer().clone();
+        self._buffer_subscription = cx.subscribe(&buffer, Self::on_buffer_event);
+        self.blocks.clear();
+        self.editor = editor.downgrade();
+
+        let editor_handle = self.editor.clone();
+        editor.update(cx, |editor, _cx| {
+            setup_editor_session_actions(editor, editor_handle);
+        });
+        cx.notify();
+    }
+
+    /// Records a line of output from the kernel process, until the kernel is running.
+    pub fn append_startup_log(&mut self, line: String) {
+        if !matches!(self.kernel, Kernel::RunningKernel(_)) {
+            self.startup_log.push_line(line);
+        }
+    }
+
+    /// What the kernel process has written since it was started, with the oldest output
+    /// dropped if it was very long.
+    pub fn startup_log(&self) -> String {
+        self.startup_log.text()
+    }
+
     pub fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
         self.kernel(Kernel::ErroredLaunch(error_message.clone()), cx);
 
@@ -372,13 +478,11 @@ impl Session {
         move_down: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
-    ) {
-        let Some(editor) = self.editor.upgrade() else {
-            return;
-        };
+    ) -> Option<Entity<ExecutionView>> {
+        let editor = self.editor.upgrade()?;
 
         if code.is_empty() {
-            return;
+            return None;
         }
 
         let execute_request = ExecuteRequest {
@@ -442,7 +546,7 @@ impl Session {
         let Ok(editor_block) =
             EditorBlock::new(self.editor.clone(), anchor_range, status, on_close, cx)
         else {
-            return;
+            return None;
         };
 
         let new_cursor_pos = if let Some(next_cursor) = next_cell {
@@ -451,6 +555,7 @@ impl Session {
             editor_block.invalidation_anchor
         };
 
+        let execution_view = editor_block.execution_view.clone();
         self.blocks
             .insert(message.header.msg_id.clone(), editor_block);
 
@@ -487,6 +592,8 @@ impl Session {
                 );
             });
         }
+
+        Some(execution_view)
     }
 
     pub fn route(&mut self, message: &JupyterMessage, window: &mut Window, cx: &mut Context<Self>) {
@@ -498,6 +605,7 @@ impl Session {
         match &message.content {
             JupyterMessageContent::Status(status) => {
                 self.kernel.set_execution_state(&status.execution_state);
+                self.track_execution_time(&status.execution_state, cx);
 
                 telemetry::event!(
                     "Kernel Status Changed",
@@ -534,6 +642,96 @@ impl Session {
         }
     }
 
+    fn track_execution_time(&mut self, state: &ExecutionState, cx: &mut Context<Self>) {
+        match state {
+            ExecutionState::Busy => {
+                if self.execution_started_at.is_some() {
+                    return;
+                }
+                self.execution_started_at = Some(Instant::now());
+                self.execution_ticker = Some(cx.spawn(async move |this, cx| {
+                    loop {
+                        cx.background_executor().timer(Duration::from_secs(1)).await;
+                        if this.update(cx, |_, cx| cx.notify()).is_err() {
+                            break;
+                        }
+                    }
+                }));
+            }
+            ExecutionState::Idle => self.stop_tracking_execution_time(),
+        }
+    }
+
+    fn stop_tracking_execution_time(&mut self) {
+        self.execution_started_at = None;
+        self.execution_ticker = None;
+    }
+
+    /// How long the kernel has been busy running the current execution.
+    pub fn execution_elapsed(&self) -> Option<Duration> {
+        self.execution_started_at
+            .map(|started_at| started_at.elapsed())
+    }
+
+    /// Whether any of the executions in the editor has produced output.
+    pub fn has_outputs(&self, cx: &App) -> bool {
+        self.blocks
+            .values()
+            .any(|block| !block.execution_view.read(cx).outputs.is_empty())
+    }
+
+    /// Serializes the executed code and its outputs as an nbformat v4 notebook, with one
+    /// code cell per execution, in the order they appear in the editor.
+    pub fn to_notebook(&self, cx: &App) -> Option<serde_json::Value> {
+        let editor = self.editor.upgrade()?;
+        let buffer = editor.read(cx).buffer().read(cx).snapshot(cx);
+
+        let mut blocks = self.blocks.values().collect::<Vec<_>>();
+        blocks.sort_by(|a, b| a.code_range.start.cmp(&b.code_range.start, &buffer));
+
+        let cells = blocks
+            .into_iter()
+            .enumerate()
+            .map(|(ix, block)| {
+                let source = buffer
+                    .text_for_range(block.code_range.clone())
+                    .collect::<String>();
+                let outputs = block
+                    .execution_view
+                    .read(cx)
+                    .outputs
+                    .iter()
+                    .filter_map(|output| output.to_notebook_output(cx))
+                    .collect::<Vec<_>>();
+                serde_json::json!({
+                    "cell_type": "code",
+                    "execution_count": ix + 1,
+                    "metadata": {},
+                    "source": source.split_inclusive('\n').collect::<Vec<_>>(),
+                    "outputs": outputs,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let name = self.kernel_specification.name();
+        let language = self.kernel_specification.language();
+        Some(serde_json::json!({
+            "cells": cells,
+            "metadata": {
+                "kernelspec": {
+                    "name": name,
+                    "display_name": name,
+                    "language": language,
+                },
+                "language_info": {
+                    "name": language,
+                },
+            },
+            "nbformat": 4,
+            "nbformat_minor": 4,
+        }))
+    }
+
     pub fn interrupt(&mut self, cx: &mut Context<Self>) {
         match &mut self.kernel {
             Kernel::RunningKernel(_kernel) => {
@@ -551,6 +749,8 @@ impl Session {
             cx.emit(SessionEvent::Shutdown(self.editor.clone()));
         }
 
+        self.stop_tracking_execution_time();
+
         let kernel_status = KernelStatus::from(&kernel).to_string();
         let kernel_language = self.kernel_specification.language();
 
@@ -562,10 +762,12 @@ impl Session {
         );
 
         self.kernel = kernel;
+        cx.notify();
     }
 
     pub fn shutdown(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let kernel = std::mem::replace(&mut self.kernel, Kernel::ShuttingDown);
+        self.stop_tracking_execution_time();
 
         match kernel {
             Kernel::RunningKernel(mut kernel) => {
@@ -600,6 +802,7 @@ impl Session {
 
     pub fn restart(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let kernel = std::mem::replace(&mut self.kernel, Kernel::Restarting);
+        self.stop_tracking_execution_time();
 
         match kernel {
             Kernel::Restarting => {
@@ -641,6 +844,16 @@ impl Session {
     }
 }
 
+fn editor_project_path(editor: &Entity<Editor>, cx: &App) -> Option<ProjectPath> {
+    editor
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .as_singleton()?
+        .read(cx)
+        .project_path(cx)
+}
+
 pub enum SessionEvent {
     Shutdown(WeakEntity<Editor>),
 }
@@ -695,3 +908,21 @@ impl Render for Session {
             .buttons(interrupt_button)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_log_truncation() {
+        let mut log = StartupLog::default();
+        log.push_line("starting".into());
+        assert_eq!(log.text(), "starting\n");
+
+        log.push_line("é".repeat(MAX_STARTUP_LOG_LEN));
+        let text = log.text();
+        assert!(text.starts_with("[earlier output truncated]\néé"));
+        assert!(text.ends_with(&format!("{TRUNCATED_LINE_SUFFIX}\n")));
+        assert!(log.len <= MAX_STARTUP_LOG_LEN);
+    }
+}
@@ -68,6 +68,8 @@ pub struct SpawnInTerminal {
     pub show_command: bool,
     /// Whether to show the rerun button in the terminal tab.
     pub show_rerun: bool,
+    /// Whether to run the task in an interactive shell, sourcing the user's rc files.
+    pub interactive: bool,
 }
 
 /// A final form of the [`TaskTemplate`], that got resolved with a particular [`TaskContext`] and now is ready to spawn the actual task.
@@ -363,6 +365,7 @@ enum WindowsShellType {
 pub struct ShellBuilder {
     program: String,
     args: Vec<String>,
+    interactive: bool,
 }
 
 impl ShellBuilder {
@@ -379,7 +382,18 @@ impl ShellBuilder {
             Shell::Program(shell) => (shell.clone(), Vec::new()),
             Shell::WithArguments { program, args, .. } => (program.clone(), args.clone()),
         };
-        Self { program, args }
+        Self {
+            program,
+            args,
+            interactive: true,
+        }
+    }
+
+    /// Sets whether the task's shell should be started in interactive mode.
+    /// Defaults to `true`.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
     }
 }
 
@@ -387,7 +401,11 @@ impl ShellBuilder {
 impl ShellBuilder {
     /// Returns the label to show in the terminal tab
     pub fn command_label(&self, command_label: &str) -> String {
-        format!("{} -i -c '{}'", self.program, command_label)
+        if self.interactive {
+            format!("{} -i -c '{}'", self.program, command_label)
+        } else {
+            format!("{} -c '{}'", self.program, command_label)
+        }
     }
 
     /// Returns the program and arguments to run this task in a shell.
@@ -399,8 +417,10 @@ impl ShellBuilder {
                 command.push_str(&arg);
                 command
             });
-        self.args
-            .extend(["-i".to_owned(), "-c".to_owned(), combined_command]);
+        if self.interactive {
+            self.args.push("-i".to_owned());
+        }
+        self.args.extend(["-c".to_owned(), combined_command]);
 
         (self.program, self.args)
     }
@@ -414,17 +434,15 @@ impl ShellBuilder {
 impl ShellBuilder {
     /// Returns the label to show in the terminal tab
     pub fn command_label(&self, command_label: &str) -> String {
-        match self.windows_shell_type() {
-            WindowsShellType::Powershell => {
-                format!("{} -C '{}'", self.program, command_label)
-            }
-            WindowsShellType::Cmd => {
-                format!("{} /C '{}'", self.program, command_label)
-            }
-            WindowsShellType::Other => {
-                format!("{} -i -c '{}'", self.program, command_label)
-            }
-        }
+        let flags = match (self.windows_shell_type(), self.interactive) {
+            (WindowsShellType::Powershell, true) => "-C",
+            (WindowsShellType::Powershell, false) => "-NoProfile -C",
+            (WindowsShellType::Cmd, true) => "/C",
+            (WindowsShellType::Cmd, false) => "/D /C",
+            (WindowsShellType::Other, true) => "-i -c",
+            (WindowsShellType::Other, false) => "-c",
+        };
+        format!("{} {} '{}'", self.program, flags, command_label)
     }
 
     /// Returns the program and arguments to run this task in a shell.
@@ -437,14 +455,19 @@ impl ShellBuilder {
                 command
             });
 
-        match self.windows_shell_type() {
-            WindowsShellType::Powershell => self.args.extend(["-C".to_owned(), combined_command]),
-            WindowsShellType::Cmd => self.args.extend(["/C".to_owned(), combined_command]),
-            WindowsShellType::Other => {
-                self.args
-                    .extend(["-i".to_owned(), "-c".to_owned(), combined_command])
+        // In non-interactive mode, skip the user's profile (PowerShell), AutoRun commands (cmd)
+        // and rc files (other shells).
+        match (self.windows_shell_type(), self.interactive) {
+            (WindowsShellType::Powershell, true) => self.args.push("-C".to_owned()),
+            (WindowsShellType::Powershell, false) => {
+                self.args.extend(["-NoProfile".to_owned(), "-C".to_owned()])
             }
+            (WindowsShellType::Cmd, true) => self.args.push("/C".to_owned()),
+            (WindowsShellType::Cmd, false) => self.args.extend(["/D".to_owned(), "/C".to_owned()]),
+            (WindowsShellType::Other, true) => self.args.extend(["-i".to_owned(), "-c".to_owned()]),
+            (WindowsShellType::Other, false) => self.args.push("-c".to_owned()),
         }
+        self.args.push(combined_command);
 
         (self.program, self.args)
     }
@@ -518,3 +541,55 @@ impl ShellBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_shell_builder_interactive() {
+        let builder = ShellBuilder::new(true, &Shell::Program("bash".to_string()));
+        assert_eq!(builder.command_label("echo hi"), "bash -i -c 'echo hi'");
+        assert_eq!(
+            builder.build("echo".to_string(), &vec!["hi".to_string()]),
+            (
+                "bash".to_string(),
+                vec!["-i".to_string(), "-c".to_string(), "echo hi".to_string()]
+            )
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_shell_builder_non_interactive() {
+        let builder =
+            ShellBuilder::new(true, &Shell::Program("bash".to_string())).interactive(false);
+        assert_eq!(builder.command_label("echo hi"), "bash -c 'echo hi'");
+        assert_eq!(
+            builder.build("echo".to_string(), &vec!["hi".to_string()]),
+            (
+                "bash".to_string(),
+                vec!["-c".to_string(), "echo hi".to_string()]
+            )
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_shell_builder_keeps_shell_arguments() {
+        let shell = Shell::WithArguments {
+            program: "bash".to_string(),
+            args: vec!["--login".to_string()],
+            title_override: None,
+        };
+        let (program, args) = ShellBuilder::new(true, &shell)
+            .interactive(false)
+            .build("make".to_string(), &Vec::new());
+        assert_eq!(program, "bash");
+        assert_eq!(
+            args,
+            vec!["--login".to_string(), "-c".to_string(), "make".to_string()]
+        );
+    }
+}
@@ -73,6 +73,10 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Whether to run the command in an interactive shell, which sources the user's rc files.
+    /// Defaults to `true`.
+    #[serde(default)]
+    pub interactive: Option<bool>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -307,6 +311,7 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                interactive: self.interactive.unwrap_or(true),
             }),
         })
     }
@@ -8,7 +8,9 @@ use std::path::PathBuf;
 #[cfg(target_os = "windows")]
 use windows::Win32::{Foundation::HANDLE, System::Threading::GetProcessId};
 
-use sysinfo::{Pid, Process, ProcessRefreshKind, RefreshKind, System, UpdateKind};
+use sysinfo::{
+    Pid, Process, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System, UpdateKind,
+};
 
 pub struct ProcessIdGetter {
     handle: i32,
@@ -79,12 +81,24 @@ pub struct ProcessInfo {
     pub argv: Vec<String>,
 }
 
+/// How much CPU and memory a process uses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProcessUsage {
+    /// The CPU usage since the previous sample, as a percentage of one core, or `None`
+    /// for the first sample of a process.
+    pub cpu_percent: Option<f32>,
+    /// The resident set size, in bytes.
+    pub memory: u64,
+}
+
 /// Fetches Zed-relevant Pseudo-Terminal (PTY) process information
 pub struct PtyProcessInfo {
     system: System,
     refresh_kind: ProcessRefreshKind,
     pid_getter: ProcessIdGetter,
+    usage_sampled_pid: Option<Pid>,
     pub current: Option<ProcessInfo>,
+    pub usage: Option<ProcessUsage>,
 }
 
 impl PtyProcessInfo {
@@ -100,7 +114,9 @@ impl PtyProcessInfo {
             system,
             refresh_kind: process_refresh_kind,
             pid_getter: ProcessIdGetter::new(pty),
+            usage_sampled_pid: None,
             current: None,
+            usage: None,
         }
     }
 
@@ -110,10 +126,10 @@ impl PtyProcessInfo {
 
     fn refresh(&mut self) -> Option<&Process> {
         let pid = self.pid_getter.pid()?;
-        if self.system.refresh_processes_specifics(
-            sysinfo::ProcessesToUpdate::Some(&[pid]),
-            self.refresh_kind,
-        ) == 1
+        if self
+            .system
+            .refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), self.refresh_kind)
+            == 1
         {
             self.system.process(pid)
         } else {
@@ -155,4 +171,33 @@ impl PtyProcessInfo {
     pub fn pid(&self) -> Option<Pid> {
         self.pid_getter.pid()
     }
+
+    /// Samples the CPU and memory usage of the foreground process, or returns `None` on
+    /// platforms where process stats aren't available. The CPU usage is measured since
+    /// the previous sample, so the first sample of each process reports none.
+    pub fn sample_usage(&mut self) -> Option<ProcessUsage> {
+        self.usage = self.load_usage();
+        self.usage
+    }
+
+    fn load_usage(&mut self) -> Option<ProcessUsage> {
+        if !sysinfo::IS_SUPPORTED_SYSTEM {
+            return None;
+        }
+        let pid = self.pid_getter.pid()?;
+        let refresh_kind = ProcessRefreshKind::new().with_cpu().with_memory();
+        if self
+            .system
+            .refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), refresh_kind)
+            != 1
+        {
+            return None;
+        }
+        let process = self.system.process(pid)?;
+        let has_previous_sample = self.usage_sampled_pid.replace(pid) == Some(pid);
+        Some(ProcessUsage {
+            cpu_percent: has_previous_sample.then(|| process.cpu_usage()),
+            memory: process.memory(),
+        })
+    }
 }
@@ -1,6 +1,7 @@
 pub mod mappings;
 
 pub use alacritty_terminal;
+pub use pty_info::ProcessUsage;
 
 mod pty_info;
 pub mod terminal_settings;
@@ -81,6 +82,8 @@ actions!(
         ScrollToTop,
         ScrollToBottom,
         ToggleViMode,
+        ToggleShowInvisibles,
+        RestartTerminal,
     ]
 );
 
@@ -96,6 +99,10 @@ const DEBUG_TERMINAL_WIDTH: Pixels = px(500.);
 const DEBUG_TERMINAL_HEIGHT: Pixels = px(30.);
 const DEBUG_CELL_WIDTH: Pixels = px(5.);
 const DEBUG_LINE_HEIGHT: Pixels = px(5.);
+/// Variables the shell maintains itself, which are not kept in a terminal's spawn
+/// environment: the current and previous working directories, the shell nesting level
+/// and the last executed command.
+const SHELL_MANAGED_ENV_VARS: &[&str] = &["PWD", "OLDPWD", "SHLVL", "_"];
 
 ///Upward flowing events, for changing the title and such
 #[derive(Clone, Debug)]
@@ -346,6 +353,7 @@ impl TerminalBuilder {
         task: Option<TaskState>,
         shell: Shell,
         mut env: HashMap<String, String>,
+        mut spawn_env: HashMap<String, String>,
         cursor_shape: CursorShape,
         alternate_scroll: AlternateScroll,
         max_scroll_history_lines: Option<usize>,
@@ -372,6 +380,8 @@ impl TerminalBuilder {
             release_channel::AppVersion::global(cx).to_string(),
         );
 
+        spawn_env.retain(|name, _| !SHELL_MANAGED_ENV_VARS.contains(&name.as_str()));
+
         let mut terminal_title_override = None;
 
         let pty_options = {
@@ -411,6 +421,12 @@ impl TerminalBuilder {
                 env: env.into_iter().collect(),
             }
         };
+        // The working directory of an SSH terminal's local process isn't the remote one.
+        let spawn_directory = if is_ssh_terminal {
+            None
+        } else {
+            pty_options.working_directory.clone()
+        };
 
         // Setup Alacritty's env, which modifies the current process's environment
         alacritty_terminal::tty::setup_env();
@@ -504,7 +520,11 @@ impl TerminalBuilder {
             vi_mode_enabled: false,
             debug_terminal,
             is_ssh_terminal,
+            spawn_directory,
             python_venv_directory,
+            spawn_env,
+            #[cfg(any(test, feature = "test-support"))]
+            pasted_text: Vec::new(),
         };
 
         Ok(TerminalBuilder {
@@ -662,6 +682,12 @@ pub struct Terminal {
     vi_mode_enabled: bool,
     debug_terminal: bool,
     is_ssh_terminal: bool,
+    /// The directory the shell was started in.
+    spawn_directory: Option<PathBuf>,
+    /// The extra environment variables the terminal was spawned with.
+    spawn_env: HashMap<String, String>,
+    #[cfg(any(test, feature = "test-support"))]
+    pasted_text: Vec<String>,
 }
 
 pub struct TaskState {
@@ -686,10 +712,18 @@ pub enum TaskStatus {
     /// The task is started and running currently.
     Running,
     /// After the start, the task stopped running and reported its error code back.
-    Completed { success: bool },
+    Completed { success: bool, exit_code: i32 },
 }
 
 impl TaskStatus {
+    /// The exit code the task reported, if it has completed.
+    pub fn exit_code(&self) -> Option<i32> {
+        match self {
+            Self::Completed { exit_code, .. } => Some(*exit_code),
+            Self::Unknown | Self::Running => None,
+        }
+    }
+
     fn register_terminal_exit(&mut self) {
         if self == &Self::Running {
             *self = Self::Unknown;
@@ -699,6 +733,7 @@ impl TaskStatus {
     fn register_task_exit(&mut self, error_code: i32) {
         *self = TaskStatus::Completed {
             success: error_code == 0,
+            exit_code: error_code,
         };
     }
 }
@@ -747,6 +782,7 @@ impl Terminal {
 
                 if self.pty_info.has_changed() {
                     cx.emit(Event::TitleChanged);
+                    cx.emit(Event::BreadcrumbsChanged);
                 }
             }
             AlacTermEvent::ColorRequest(index, format) => {
@@ -1375,6 +1411,9 @@ impl Terminal {
 
     ///Paste text into the terminal
     pub fn paste(&mut self, text: &str) {
+        #[cfg(any(test, feature = "test-support"))]
+        self.pasted_text.push(text.to_string());
+
         let paste_text = if self.last_content.mode.contains(TermMode::BRACKETED_PASTE) {
             format!("{}{}{}", "\x1b[200~", text.replace('\x1b', ""), "\x1b[201~")
         } else {
@@ -1384,6 +1423,12 @@ impl Terminal {
         self.input(paste_text);
     }
 
+    /// The text pasted into the terminal so far.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn pasted_text(&self) -> &[String] {
+        &self.pasted_text
+    }
+
     pub fn sync(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let term = self.term.clone();
         let mut terminal = term.lock_unfair();
@@ -1765,6 +1810,12 @@ impl Terminal {
         })
     }
 
+    /// The extra environment variables this terminal was spawned with, on top of the
+    /// inherited environment and the `env` setting, without the ones the shell manages.
+    pub fn spawn_env(&self) -> &HashMap<String, String> {
+        &self.spawn_env
+    }
+
     pub fn working_directory(&self) -> Option<PathBuf> {
         if self.is_ssh_terminal {
             // We can't yet reliably detect the working directory of a shell on the
@@ -1776,6 +1827,13 @@ impl Terminal {
         }
     }
 
+    /// Returns the shell's current working directory, or the directory it was started in when
+    /// the current one can't be detected.
+    pub fn current_directory(&self) -> Option<PathBuf> {
+        self.working_directory()
+            .or_else(|| self.spawn_directory.clone())
+    }
+
     /// Returns the working directory of the process that's connected to the PTY.
     /// That means it returns the working directory of the local shell or program
     /// that's running inside the terminal.
@@ -1789,6 +1847,24 @@ impl Terminal {
             .map(|process| process.cwd.clone())
     }
 
+    /// The name of the terminal's foreground process, if known.
+    pub fn foreground_process_name(&self) -> Option<&str> {
+        self.pty_info
+            .current
+            .as_ref()
+            .map(|process| process.name.as_str())
+    }
+
+    /// Samples the CPU and memory usage of the terminal's foreground process.
+    pub fn sample_process_usage(&mut self) -> Option<ProcessUsage> {
+        self.pty_info.sample_usage()
+    }
+
+    /// The most recent usage sample of the terminal's foreground process.
+    pub fn process_usage(&self) -> Option<ProcessUsage> {
+        self.pty_info.usage
+    }
+
     pub fn title(&self, truncate: bool) -> String {
         const MAX_CHARS: usize = 25;
         match &self.task {
@@ -35,6 +35,7 @@ pub struct TerminalSettings {
     pub font_weight: Option<FontWeight>,
     pub line_height: TerminalLineHeight,
     pub env: HashMap<String, String>,
+    pub profiles: HashMap<String, TerminalProfile>,
     pub cursor_shape: Option<CursorShape>,
     pub blinking: TerminalBlink,
     pub alternate_scroll: AlternateScroll,
@@ -42,14 +43,29 @@ pub struct TerminalSettings {
     pub copy_on_select: bool,
     pub button: bool,
     pub dock: TerminalDockPosition,
+    pub open_on_startup: bool,
+    pub spawn_on_activate: bool,
     pub default_width: Pixels,
     pub default_height: Pixels,
     pub detect_venv: VenvSettings,
     pub max_scroll_history_lines: Option<usize>,
+    pub close_successful_task_after_ms: Option<u64>,
     pub toolbar: Toolbar,
     pub scrollbar: ScrollbarSettings,
 }
 
+/// A named set of environment variables that can be applied when opening a new terminal.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct TerminalProfile {
+    /// Key-value pairs added to the environment of terminals opened with this profile.
+    ///
+    /// These take precedence over the variables from the `env` setting.
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct ScrollbarSettings {
     /// When to show the scrollbar in the terminal.
@@ -167,6 +183,12 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: {}
     pub env: Option<HashMap<String, String>>,
+    /// Named profiles that can be picked when opening a new terminal,
+    /// each adding its own environment variables on top of `env`.
+    /// Tasks are not affected by profiles: their own environment is applied as is.
+    ///
+    /// Default: {}
+    pub profiles: Option<HashMap<String, TerminalProfile>>,
     /// Default cursor shape for the terminal.
     /// Can be "bar", "block", "underline", or "hollow".
     ///
@@ -197,6 +219,18 @@ pub struct TerminalSettingsContent {
     /// Default: true
     pub button: Option<bool>,
     pub dock: Option<TerminalDockPosition>,
+    /// Whether to open the terminal panel when a workspace is opened for the
+    /// first time. Workspaces restored from a previous session keep the
+    /// terminal panel's saved visibility instead.
+    ///
+    /// Default: false
+    pub open_on_startup: Option<bool>,
+    /// Whether to start a new terminal when the terminal panel is opened
+    /// without any terminals in it. When disabled, the empty panel offers
+    /// a button to start one instead.
+    ///
+    /// Default: true
+    pub spawn_on_activate: Option<bool>,
     /// Default width when the terminal is docked to the left or right.
     ///
     /// Default: 640
@@ -219,6 +253,13 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: 10_000
     pub max_scroll_history_lines: Option<usize>,
+    /// The delay, in milliseconds, after which the terminal of a task that completed
+    /// successfully is closed. Terminals of failed tasks are kept open and revealed
+    /// instead, and a terminal is never closed while it is focused.
+    /// Set this to null to keep task terminals open regardless of their outcome.
+    ///
+    /// Default: null
+    pub close_successful_task_after_ms: Option<u64>,
     /// Toolbar related settings
     pub toolbar: Option<ToolbarContent>,
     /// Scrollbar-related settings
@@ -272,12 +313,16 @@ pub enum TerminalLineHeight {
 
 impl TerminalLineHeight {
     pub fn value(&self) -> AbsoluteLength {
-        let value = match self {
+        px(self.multiplier()).into()
+    }
+
+    /// The line height as a multiple of the font size.
+    pub fn multiplier(&self) -> f32 {
+        match self {
             TerminalLineHeight::Comfortable => 1.618,
             TerminalLineHeight::Standard => 1.3,
             TerminalLineHeight::Custom(line_height) => f32::max(*line_height, 1.),
-        };
-        px(value).into()
+        }
     }
 }
 
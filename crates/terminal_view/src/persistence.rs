@@ -1,18 +1,20 @@
 use anyhow::Result;
 use async_recursion::async_recursion;
-use collections::HashSet;
+use collections::{HashMap, HashSet};
 use futures::{StreamExt as _, stream::FuturesUnordered};
 use gpui::{AppContext as _, AsyncWindowContext, Axis, Entity, Task, WeakEntity};
 use project::{Project, terminals::TerminalKind};
 use serde::{Deserialize, Serialize};
+use settings::Settings as _;
 use std::path::{Path, PathBuf};
+use terminal::terminal_settings::TerminalSettings;
 use ui::{App, Context, Pixels, Window};
 use util::ResultExt as _;
 
 use db::{define_connection, query, sqlez::statement::Statement, sqlez_macros::sql};
 use workspace::{
     ItemHandle, ItemId, Member, Pane, PaneAxis, PaneGroup, SerializableItem as _, Workspace,
-    WorkspaceDb, WorkspaceId,
+    WorkspaceDb, WorkspaceId, dock::Panel as _,
 };
 
 use crate::{
@@ -95,9 +97,13 @@ pub(crate) fn deserialize_terminal_panel(
                 let mut panel = TerminalPanel::new(workspace, window, cx);
                 panel.height = serialized_panel.height.map(|h| h.round());
                 panel.width = serialized_panel.width.map(|w| w.round());
+                panel.restored = true;
                 panel
             })
         })?;
+        // The zoom belongs to the active pane; if that pane could not be restored,
+        // the panel comes back un-zoomed.
+        let mut restore_zoom = serialized_panel.zoomed;
         match &serialized_panel.items {
             SerializedItems::NoSplits(item_ids) => {
                 let items = deserialize_terminal_views(
@@ -126,14 +132,22 @@ pub(crate) fn deserialize_terminal_panel(
                 )
                 .await;
                 if let Some((center_group, active_pane)) = center_pane {
+                    restore_zoom &= active_pane.is_some();
                     terminal_panel.update(cx, |terminal_panel, _| {
                         terminal_panel.center = PaneGroup::with_root(center_group);
                         terminal_panel.active_pane =
                             active_pane.unwrap_or_else(|| terminal_panel.center.first_pane());
                     })?;
+                } else {
+                    restore_zoom = false;
                 }
             }
         }
+        if restore_zoom {
+            terminal_panel.update_in(cx, |terminal_panel, window, cx| {
+                terminal_panel.set_zoomed(true, window, cx);
+            })?;
+        }
 
         Ok(terminal_panel)
     })
@@ -177,7 +191,8 @@ async fn deserialize_pane_group(
         } => {
             let mut current_active_pane = None;
             let mut members = Vec::new();
-            for child in children {
+            let mut restored_children = Vec::new();
+            for (ix, child) in children.iter().enumerate() {
                 if let Some((new_member, active_pane)) = deserialize_pane_group(
                     workspace.clone(),
                     project.clone(),
@@ -189,6 +204,7 @@ async fn deserialize_pane_group(
                 .await
                 {
                     members.push(new_member);
+                    restored_children.push(ix);
                     current_active_pane = current_active_pane.or(active_pane);
                 }
             }
@@ -202,7 +218,11 @@ async fn deserialize_pane_group(
             }
 
             Some((
-                Member::Axis(PaneAxis::load(axis.0, members, flexes.clone())),
+                Member::Axis(PaneAxis::load(
+                    axis.0,
+                    members,
+                    restored_flexes_for(flexes.as_deref(), children.len(), &restored_children),
+                )),
                 current_active_pane,
             ))
         }
@@ -233,15 +253,18 @@ async fn deserialize_pane_group(
             let terminal = pane
                 .update_in(cx, |pane, window, cx| {
                     populate_pane_items(pane, new_items, active_item, window, cx);
-                    // Avoid blank panes in splits
-                    if pane.items_len() == 0 {
+                    // Avoid blank panes in splits, unless the user wants an empty panel
+                    // to stay empty.
+                    if pane.items_len() == 0 && TerminalSettings::get_global(cx).spawn_on_activate {
                         let working_directory = workspace
                             .update(cx, |workspace, cx| default_working_directory(workspace, cx))
                             .ok()
                             .flatten();
-                        let kind = TerminalKind::Shell(
-                            working_directory.as_deref().map(Path::to_path_buf),
-                        );
+                        let kind = TerminalKind::Shell {
+                            working_directory: working_directory.as_deref().map(Path::to_path_buf),
+                            profile: None,
+                            env: HashMap::default(),
+                        };
                         let window = window.window_handle();
                         let terminal = project
                             .update(cx, |project, cx| project.create_terminal(kind, window, cx));
@@ -274,6 +297,29 @@ async fn deserialize_pane_group(
     }
 }
 
+/// Picks the split ratios of the children that were successfully restored.
+///
+/// Data serialized before split ratios were recorded, or with a mismatched number of ratios,
+/// falls back to equal splits.
+fn restored_flexes_for(
+    flexes: Option<&[f32]>,
+    serialized_children: usize,
+    restored_children: &[usize],
+) -> Option<Vec<f32>> {
+    let flexes = flexes.filter(|flexes| flexes.len() == serialized_children)?;
+    let restored = restored_children
+        .iter()
+        .map(|&ix| flexes[ix])
+        .collect::<Vec<_>>();
+    if restored.iter().any(|flex| !flex.is_finite() || *flex <= 0.) {
+        return None;
+    }
+    // Pane axes expect the flexes to sum up to the number of members.
+    let total = restored.iter().sum::<f32>();
+    let scale = restored.len() as f32 / total;
+    Some(restored.into_iter().map(|flex| flex * scale).collect())
+}
+
 async fn deserialize_terminal_views(
     workspace_id: WorkspaceId,
     project: Entity<Project>,
@@ -313,6 +359,9 @@ pub(crate) struct SerializedTerminalPanel {
     pub active_item_id: Option<u64>,
     pub width: Option<Pixels>,
     pub height: Option<Pixels>,
+    /// Whether the active pane was zoomed.
+    #[serde(default)]
+    pub zoomed: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -407,6 +456,9 @@ define_connection! {
             ALTER TABLE terminals ADD COLUMN working_directory_path TEXT;
             UPDATE terminals SET working_directory_path = CAST(working_directory AS TEXT);
         ),
+        sql! (
+            ALTER TABLE terminals ADD COLUMN show_invisibles INTEGER;
+        ),
     ];
 }
 
@@ -449,6 +501,27 @@ impl TerminalDb {
         .await
     }
 
+    query! {
+        pub async fn save_show_invisibles(
+            item_id: ItemId,
+            workspace_id: WorkspaceId,
+            show_invisibles: bool
+        ) -> Result<()> {
+            INSERT INTO terminals(item_id, workspace_id, show_invisibles)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT DO UPDATE SET
+                show_invisibles = ?3
+        }
+    }
+
+    query! {
+        pub fn get_show_invisibles(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<bool>> {
+            SELECT show_invisibles
+            FROM terminals
+            WHERE item_id = ? AND workspace_id = ?
+        }
+    }
+
     query! {
         pub fn get_working_directory(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<PathBuf>> {
             SELECT working_directory
@@ -483,3 +556,192 @@ impl TerminalDb {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext, WindowHandle};
+    use settings::SettingsStore;
+    use workspace::AppState;
+
+    fn pane(children: Vec<u64>, active: bool) -> SerializedPaneGroup {
+        SerializedPaneGroup::Pane(SerializedPane {
+            active,
+            active_item: children.first().copied(),
+            children,
+        })
+    }
+
+    #[gpui::test]
+    async fn test_split_ratios_round_trip(cx: &mut TestAppContext) {
+        let (project, window) = init_test(cx).await;
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+
+        let panel = SerializedTerminalPanel {
+            items: SerializedItems::WithSplits(SerializedPaneGroup::Group {
+                axis: SerializedAxis(Axis::Horizontal),
+                flexes: Some(vec![0.5, 1.0, 1.5]),
+                children: vec![
+                    pane(vec![1], false),
+                    pane(vec![2], true),
+                    pane(vec![3], false),
+                ],
+            }),
+            active_item_id: None,
+            width: None,
+            height: None,
+            zoomed: false,
+        };
+
+        let restored_panel = round_trip(panel, &workspace, &project, cx).await;
+        let serialized = restored_panel.update(cx, |terminal_panel, cx| {
+            serialize_pane_group(&terminal_panel.center, &terminal_panel.active_pane, cx)
+        });
+        let SerializedPaneGroup::Group {
+            axis,
+            flexes,
+            children,
+        } = serialized
+        else {
+            panic!("expected a split pane group");
+        };
+        assert_eq!(axis.0, Axis::Horizontal);
+        assert_eq!(children.len(), 3);
+        assert_eq!(flexes, Some(vec![0.5, 1.0, 1.5]));
+        assert!(
+            matches!(&children[1], SerializedPaneGroup::Pane(pane) if pane.active),
+            "the active pane should be restored"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_empty_panel_round_trip(cx: &mut TestAppContext) {
+        let (project, window) = init_test(cx).await;
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+
+        let terminal_panel = workspace.update_in(cx, |workspace, window, cx| {
+            cx.new(|cx| TerminalPanel::new(workspace, window, cx))
+        });
+        let panel = terminal_panel.update(cx, |terminal_panel, cx| SerializedTerminalPanel {
+            items: SerializedItems::WithSplits(serialize_pane_group(
+                &terminal_panel.center,
+                &terminal_panel.active_pane,
+                cx,
+            )),
+            active_item_id: None,
+            width: None,
+            height: None,
+            zoomed: false,
+        });
+
+        let restored_panel = round_trip(panel, &workspace, &project, cx).await;
+        restored_panel.read_with(cx, |terminal_panel, cx| {
+            let panes = terminal_panel.center.panes();
+            assert_eq!(panes.len(), 1);
+            assert_eq!(panes[0], &terminal_panel.active_pane);
+            assert_eq!(
+                panes[0].read(cx).items_len(),
+                0,
+                "an empty panel should stay empty when restored"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_zoomed_round_trip(cx: &mut TestAppContext) {
+        let (project, window) = init_test(cx).await;
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+
+        for zoomed in [true, false] {
+            let panel = SerializedTerminalPanel {
+                items: SerializedItems::WithSplits(pane(vec![1], true)),
+                active_item_id: None,
+                width: None,
+                height: None,
+                zoomed,
+            };
+            let restored_panel = round_trip(panel, &workspace, &project, cx).await;
+            restored_panel.read_with(cx, |terminal_panel, cx| {
+                assert_eq!(terminal_panel.active_pane.read(cx).is_zoomed(), zoomed);
+            });
+        }
+
+        let legacy = r#"{"items":[1,2],"active_item_id":1,"width":null,"height":null}"#;
+        let legacy: SerializedTerminalPanel = serde_json::from_str(legacy).unwrap();
+        assert!(!legacy.zoomed);
+    }
+
+    /// Sets up a workspace in which empty terminal panes stay empty, as the terminals of
+    /// the panels restored in tests aren't in the database.
+    async fn init_test(cx: &mut TestAppContext) -> (Entity<Project>, WindowHandle<Workspace>) {
+        let params = cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            AppState::test(cx)
+        });
+        cx.update(|cx| {
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings::<TerminalSettings>(cx, |settings| {
+                    settings.spawn_on_activate = Some(false);
+                });
+            });
+        });
+
+        let project = Project::test(params.fs.clone(), [], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        (project, window)
+    }
+
+    /// Restores the panel through its JSON form, as when it's loaded from the database.
+    async fn round_trip(
+        panel: SerializedTerminalPanel,
+        workspace: &Entity<Workspace>,
+        project: &Entity<Project>,
+        cx: &mut VisualTestContext,
+    ) -> Entity<TerminalPanel> {
+        let json = serde_json::to_string(&panel).unwrap();
+        let restored: SerializedTerminalPanel = serde_json::from_str(&json).unwrap();
+        let restored_panel = cx
+            .update(|window, cx| {
+                deserialize_terminal_panel(
+                    workspace.downgrade(),
+                    project.clone(),
+                    WorkspaceId::default(),
+                    restored,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        restored_panel
+    }
+
+    #[test]
+    fn test_split_ratios_fallbacks() {
+        let legacy = r#"{"Group":{"axis":"vertical","children":[{"Pane":{"active":true,"children":[1],"active_item":1}},{"Pane":{"active":false,"children":[2],"active_item":null}}]}}"#;
+        let SerializedPaneGroup::Group { flexes, .. } =
+            serde_json::from_str::<SerializedPaneGroup>(legacy).unwrap()
+        else {
+            panic!("expected a split pane group");
+        };
+        assert_eq!(flexes, None);
+        assert_eq!(restored_flexes_for(flexes.as_deref(), 2, &[0, 1]), None);
+
+        assert_eq!(restored_flexes_for(Some(&[1.0, 2.0]), 3, &[0, 1, 2]), None);
+        assert_eq!(
+            restored_flexes_for(Some(&[0.5, 1.0, 1.5]), 3, &[0, 2]),
+            Some(vec![0.5, 1.5])
+        );
+    }
+}
@@ -201,6 +201,7 @@ impl TerminalElement {
         // terminal_theme: &TerminalStyle,
         text_system: &WindowTextSystem,
         hyperlink: Option<(HighlightStyle, &RangeInclusive<AlacPoint>)>,
+        show_invisibles: bool,
         window: &Window,
         cx: &App,
     ) -> (Vec<LayoutCell>, Vec<LayoutRect>) {
@@ -213,7 +214,18 @@ impl TerminalElement {
 
         let linegroups = grid.into_iter().chunk_by(|i| i.point.line);
         for (line_index, (_, line)) in linegroups.into_iter().enumerate() {
-            for cell in line {
+            let line = line.collect::<Vec<_>>();
+            // The grid doesn't distinguish trailing spaces from unwritten cells,
+            // so invisibles are only shown up to the last character of the line.
+            let content_end = line.iter().rposition(|cell| cell.c != ' ');
+            let mut in_tab = false;
+            for (cell_index, cell) in line.iter().enumerate() {
+                // Tabs are stored in their first cell, followed by plain spaces up to the next tab stop.
+                in_tab = match cell.c {
+                    '\t' => true,
+                    ' ' => in_tab && cell.point.column.0 % TAB_WIDTH != 0,
+                    _ => false,
+                };
                 let mut fg = cell.fg;
                 let mut bg = cell.bg;
                 if cell.flags.contains(Flags::INVERSE) {
@@ -278,10 +290,21 @@ impl TerminalElement {
 
                 //Layout current cell text
                 {
-                    if !is_blank(&cell) {
+                    let tab_padding = in_tab && cell.c == ' ';
+                    let invisible = (show_invisibles && !tab_padding)
+                        .then(|| {
+                            invisible_glyph(cell, content_end.is_some_and(|end| cell_index < end))
+                        })
+                        .flatten();
+                    if let Some(glyph) = invisible {
+                        cells.push(LayoutCell::new(
+                            AlacPoint::new(line_index as i32, cell.point.column.0 as i32),
+                            Self::shape_invisible(glyph, text_style, text_system, window, cx),
+                        ));
+                    } else if !is_blank(cell) {
                         let cell_text = cell.c.to_string();
                         let cell_style =
-                            TerminalElement::cell_style(&cell, fg, theme, text_style, hyperlink);
+                            TerminalElement::cell_style(cell, fg, theme, text_style, hyperlink);
 
                         let layout_cell = text_system
                             .shape_line(
@@ -302,10 +325,47 @@ impl TerminalElement {
             if cur_rect.is_some() {
                 rects.push(cur_rect.take().unwrap());
             }
+
+            if show_invisibles {
+                let line_end = content_end.and_then(|end| line.get(end + 1));
+                let wraps = line
+                    .last()
+                    .is_some_and(|cell| cell.flags.contains(Flags::WRAPLINE));
+                if let Some(cell) = line_end.filter(|_| !wraps) {
+                    cells.push(LayoutCell::new(
+                        AlacPoint::new(line_index as i32, cell.point.column.0 as i32),
+                        Self::shape_invisible(LINE_END_GLYPH, text_style, text_system, window, cx),
+                    ));
+                }
+            }
         }
         (cells, rects)
     }
 
+    fn shape_invisible(
+        glyph: char,
+        text_style: &TextStyle,
+        text_system: &WindowTextSystem,
+        window: &Window,
+        cx: &App,
+    ) -> ShapedLine {
+        let run = TextRun {
+            len: glyph.len_utf8(),
+            color: cx.theme().colors().editor_invisible,
+            background_color: None,
+            font: text_style.font(),
+            underline: None,
+            strikethrough: None,
+        };
+        text_system
+            .shape_line(
+                glyph.to_string().into(),
+                text_style.font_size.to_pixels(window.rem_size()),
+                &[run],
+            )
+            .unwrap()
+    }
+
     /// Computes the cursor position and expected block width, may return a zero width if x_for_index returns
     /// the same position for sequential indexes. Use em_width instead
     fn shape_cursor(
@@ -752,6 +812,7 @@ impl Element for TerminalElement {
                     last_hovered_word
                         .as_ref()
                         .map(|last_hovered_word| (link_style, &last_hovered_word.word_match)),
+                    self.terminal_view.read(cx).show_invisibles,
                     window,
                     cx,
                 );
@@ -1068,6 +1129,23 @@ impl InputHandler for TerminalInputHandler {
     }
 }
 
+const TAB_WIDTH: usize = 8;
+const SPACE_GLYPH: char = '·';
+const TAB_GLYPH: char = '→';
+const LINE_END_GLYPH: char = '↵';
+
+/// Returns the glyph drawn in place of a whitespace cell when invisibles are shown.
+/// Spaces are only marked when followed by other characters on the same line.
+fn invisible_glyph(cell: &IndexedCell, before_line_end: bool) -> Option<char> {
+    match cell.c {
+        '\t' => Some(TAB_GLYPH),
+        ' ' if before_line_end && !cell.flags.contains(Flags::WIDE_CHAR_SPACER) => {
+            Some(SPACE_GLYPH)
+        }
+        _ => None,
+    }
+}
+
 pub fn is_blank(cell: &IndexedCell) -> bool {
     if cell.c != ' ' {
         return false;
@@ -1,4 +1,4 @@
-use std::{cmp, ops::ControlFlow, path::PathBuf, sync::Arc, time::Duration};
+use std::{cmp, ops::ControlFlow, path::PathBuf, rc::Rc, sync::Arc, time::Duration};
 
 use crate::{
     TerminalView, default_working_directory,
@@ -11,33 +11,40 @@ use collections::HashMap;
 use db::kvp::KEY_VALUE_STORE;
 use futures::future::join_all;
 use gpui::{
-    Action, AnyView, App, AsyncApp, AsyncWindowContext, Context, Corner, Entity, EventEmitter,
-    ExternalPaths, FocusHandle, Focusable, IntoElement, ParentElement, Pixels, Render, Styled,
-    Task, WeakEntity, Window, actions,
+    Action, AnyElement, AnyView, App, AsyncApp, AsyncWindowContext, Bounds, Context, Corner,
+    DragMoveEvent, Entity, EntityId, EventEmitter, ExternalPaths, FocusHandle, Focusable, Global,
+    IntoElement, MouseButton, MouseUpEvent, ParentElement, Pixels, Point, Render, Styled, Task,
+    WeakEntity, Window, actions, impl_actions,
 };
 use itertools::Itertools;
 use project::{Fs, Project, ProjectEntryId, terminals::TerminalKind};
+use schemars::JsonSchema;
 use search::{BufferSearchBar, buffer_search::DivRegistrar};
+use serde::Deserialize;
 use settings::Settings;
 use task::{RevealStrategy, RevealTarget, ShellBuilder, SpawnInTerminal, TaskId};
 use terminal::{
-    Terminal,
+    TaskStatus, Terminal,
+    alacritty_terminal::term::TermMode,
     terminal_settings::{TerminalDockPosition, TerminalSettings},
 };
+use theme::ThemeSettings;
 use ui::{
-    ButtonCommon, Clickable, ContextMenu, FluentBuilder, PopoverMenu, Toggleable, Tooltip,
+    ButtonCommon, Clickable, ContextMenu, FluentBuilder, PopoverMenu, Tab, Toggleable, Tooltip,
     prelude::*,
 };
 use util::{ResultExt, TryFutureExt};
 use workspace::{
     ActivateNextPane, ActivatePane, ActivatePaneDown, ActivatePaneLeft, ActivatePaneRight,
-    ActivatePaneUp, ActivatePreviousPane, DraggedSelection, DraggedTab, ItemId, MoveItemToPane,
-    MoveItemToPaneInDirection, NewTerminal, Pane, PaneGroup, SplitDirection, SplitDown, SplitLeft,
-    SplitRight, SplitUp, SwapPaneDown, SwapPaneLeft, SwapPaneRight, SwapPaneUp, ToggleZoom,
-    Workspace,
-    dock::{DockPosition, Panel, PanelEvent, PanelHandle},
+    ActivatePaneUp, ActivatePreviousPane, DraggedSelection, DraggedTab, DraggedText, ItemId,
+    MoveItemToPane, MoveItemToPaneInDirection, NewTerminal, Pane, PaneGroup, SplitDirection,
+    SplitDown, SplitLeft, SplitRight, SplitUp, SwapPaneDown, SwapPaneLeft, SwapPaneRight,
+    SwapPaneUp, ToggleZoom, Workspace,
+    dock::{DockPosition, Panel, PanelBadge, PanelEvent, PanelHandle},
     item::SerializableItem,
-    move_active_item, move_item, pane,
+    move_active_item, move_item,
+    notifications::{NotificationId, Toast},
+    pane,
     ui::IconName,
 };
 
@@ -46,23 +53,66 @@ use zed_actions::assistant::InlineAssist;
 
 const TERMINAL_PANEL_KEY: &str = "TerminalPanel";
 
-actions!(terminal_panel, [ToggleFocus]);
+/// The number of terminal lines that stay visible when the panel is resized down.
+const MIN_VISIBLE_LINES: f32 = 2.;
+const MIN_PANEL_WIDTH: Pixels = px(120.);
+/// How long the panel's badge shows whether the last finished task succeeded.
+const TASK_OUTCOME_FLASH_DURATION: Duration = Duration::from_secs(3);
+
+actions!(
+    terminal_panel,
+    [
+        ToggleFocus,
+        CollapseTerminalToSinglePane,
+        ActivateNextItem,
+        ActivatePrevItem,
+        DuplicateTerminal,
+        RerunLastTask
+    ]
+);
+
+/// Swaps the terminals of the active pane with the ones of the pane in the given direction,
+/// leaving the split layout intact.
+#[derive(Clone, Deserialize, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SwapItemsWithPaneInDirection {
+    pub direction: SplitDirection,
+}
+
+/// Opens a new terminal with the environment variables of the given terminal profile.
+#[derive(Clone, Deserialize, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NewTerminalWithProfile {
+    pub profile: String,
+}
+
+impl_actions!(
+    terminal_panel,
+    [NewTerminalWithProfile, SwapItemsWithPaneInDirection]
+);
 
 pub fn init(cx: &mut App) {
     cx.observe_new(
         |workspace: &mut Workspace, _window, _: &mut Context<Workspace>| {
             workspace.register_action(TerminalPanel::new_terminal);
+            workspace.register_action(TerminalPanel::new_terminal_with_profile);
             workspace.register_action(TerminalPanel::open_terminal);
             workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
                 if is_enabled_in_workspace(workspace, cx) {
                     workspace.toggle_panel_focus::<TerminalPanel>(window, cx);
                 }
             });
+            workspace.register_action(TerminalPanel::rerun_last_task);
         },
     )
     .detach();
 }
 
+/// Renders an extra button in the tab bar of a terminal pane. The pane's active item is the
+/// terminal the button acts on.
+pub type TerminalTabBarButton =
+    Rc<dyn Fn(&mut Pane, &mut Window, &mut Context<Pane>) -> Option<AnyElement>>;
+
 pub struct TerminalPanel {
     pub(crate) active_pane: Entity<Pane>,
     pub(crate) center: PaneGroup,
@@ -75,9 +125,31 @@ pub struct TerminalPanel {
     deferred_tasks: HashMap<TaskId, Task<()>>,
     assistant_enabled: bool,
     assistant_tab_bar_button: Option<AnyView>,
+    custom_tab_bar_buttons: Vec<(SharedString, TerminalTabBarButton)>,
     active: bool,
+    /// Whether the panel was restored from a previous session, in which case
+    /// the dock's saved state decides whether it starts open.
+    pub(crate) restored: bool,
+    /// The most recent task handled by [`TerminalPanel::spawn_task`], rerun by [`RerunLastTask`].
+    last_spawn: Option<SpawnInTerminal>,
+    /// Whether the most recently finished task succeeded, along with the task that
+    /// clears it from the badge.
+    task_outcome: Option<(bool, Task<()>)>,
+    /// The terminal tab being dragged outside of the window, which is moved into a new
+    /// window if it gets dropped there.
+    tab_dragged_out: Option<DraggedTab>,
+    /// Whether the panel took in terminals dragged out of another window, in which case
+    /// it starts open to show them.
+    adopted_detached_terminals: bool,
 }
 
+/// Terminals dragged out of a window, waiting for the terminal panel of the new
+/// workspace they were moved to, keyed by that workspace's entity ID.
+#[derive(Default)]
+struct DetachedTerminals(HashMap<EntityId, Vec<Entity<Terminal>>>);
+
+impl Global for DetachedTerminals {}
+
 impl TerminalPanel {
     pub fn new(workspace: &Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
         let project = workspace.project();
@@ -95,7 +167,13 @@ impl TerminalPanel {
             deferred_tasks: HashMap::default(),
             assistant_enabled: false,
             assistant_tab_bar_button: None,
+            custom_tab_bar_buttons: Vec::new(),
             active: false,
+            restored: false,
+            last_spawn: None,
+            task_outcome: None,
+            tab_dragged_out: None,
+            adopted_detached_terminals: false,
         };
         terminal_panel.apply_tab_bar_buttons(&terminal_panel.active_pane, cx);
         terminal_panel
@@ -117,6 +195,43 @@ impl TerminalPanel {
         } else {
             self.assistant_tab_bar_button = None;
         }
+        self.apply_tab_bar_buttons_to_all_panes(cx);
+    }
+
+    /// Adds a button to the tab bar of every terminal pane, rendered after the built-in
+    /// ones. Registering a button with an existing id replaces it.
+    pub fn register_tab_bar_button<F>(
+        &mut self,
+        id: impl Into<SharedString>,
+        render: F,
+        cx: &mut Context<Self>,
+    ) where
+        F: 'static + Fn(&mut Pane, &mut Window, &mut Context<Pane>) -> Option<AnyElement>,
+    {
+        let id = id.into();
+        let render: TerminalTabBarButton = Rc::new(render);
+        match self
+            .custom_tab_bar_buttons
+            .iter_mut()
+            .find(|(button_id, _)| *button_id == id)
+        {
+            Some((_, button)) => *button = render,
+            None => self.custom_tab_bar_buttons.push((id, render)),
+        }
+        self.apply_tab_bar_buttons_to_all_panes(cx);
+    }
+
+    /// Removes a button added with [`Self::register_tab_bar_button`].
+    pub fn unregister_tab_bar_button(&mut self, id: &str, cx: &mut Context<Self>) {
+        let buttons_count = self.custom_tab_bar_buttons.len();
+        self.custom_tab_bar_buttons
+            .retain(|(button_id, _)| button_id.as_ref() != id);
+        if self.custom_tab_bar_buttons.len() != buttons_count {
+            self.apply_tab_bar_buttons_to_all_panes(cx);
+        }
+    }
+
+    fn apply_tab_bar_buttons_to_all_panes(&self, cx: &mut Context<Self>) {
         for pane in self.center.panes() {
             self.apply_tab_bar_buttons(pane, cx);
         }
@@ -124,6 +239,11 @@ impl TerminalPanel {
 
     fn apply_tab_bar_buttons(&self, terminal_pane: &Entity<Pane>, cx: &mut Context<Self>) {
         let assistant_tab_bar_button = self.assistant_tab_bar_button.clone();
+        let custom_tab_bar_buttons = self
+            .custom_tab_bar_buttons
+            .iter()
+            .map(|(_, render)| render.clone())
+            .collect::<Vec<_>>();
         terminal_pane.update(cx, |pane, cx| {
             pane.set_render_tab_bar_buttons(cx, move |pane, window, cx| {
                 let split_context = pane
@@ -134,6 +254,10 @@ impl TerminalPanel {
                     return (None, None);
                 }
                 let focus_handle = pane.focus_handle(cx);
+                let custom_buttons = custom_tab_bar_buttons
+                    .iter()
+                    .filter_map(|render| render(pane, window, cx))
+                    .collect::<Vec<_>>();
                 let right_children = h_flex()
                     .gap(DynamicSpacing::Base02.rems(cx))
                     .child(
@@ -146,12 +270,25 @@ impl TerminalPanel {
                             .with_handle(pane.new_item_context_menu_handle.clone())
                             .menu(move |window, cx| {
                                 let focus_handle = focus_handle.clone();
+                                let profiles = TerminalSettings::get_global(cx)
+                                    .profiles
+                                    .keys()
+                                    .cloned()
+                                    .sorted()
+                                    .collect::<Vec<_>>();
                                 let menu = ContextMenu::build(window, cx, |menu, _, _| {
-                                    menu.context(focus_handle.clone())
-                                        .action(
-                                            "New Terminal",
-                                            workspace::NewTerminal.boxed_clone(),
-                                        )
+                                    let menu = menu.context(focus_handle.clone()).action(
+                                        "New Terminal",
+                                        workspace::NewTerminal.boxed_clone(),
+                                    );
+                                    profiles
+                                        .into_iter()
+                                        .fold(menu, |menu, profile| {
+                                            menu.action(
+                                                format!("New Terminal ▸ {profile}"),
+                                                NewTerminalWithProfile { profile }.boxed_clone(),
+                                            )
+                                        })
                                         // We want the focus to go back to terminal panel once task modal is dismissed,
                                         // hence we focus that first. Otherwise, we'd end up without a focused element, as
                                         // context menu will be gone the moment we spawn the modal.
@@ -209,6 +346,7 @@ impl TerminalPanel {
                                 )
                             })
                     })
+                    .children(custom_buttons)
                     .into_any_element()
                     .into();
                 (None, right_children)
@@ -278,6 +416,20 @@ impl TerminalPanel {
             })?
         };
 
+        workspace.update_in(&mut cx, |_, window, cx| {
+            let workspace_id = cx.entity_id();
+            let detached_terminals = cx
+                .default_global::<DetachedTerminals>()
+                .0
+                .remove(&workspace_id)
+                .unwrap_or_default();
+            for terminal in detached_terminals {
+                terminal_panel.update(cx, |terminal_panel, cx| {
+                    terminal_panel.add_detached_terminal(terminal, window, cx)
+                });
+            }
+        })?;
+
         if let Some(workspace) = workspace.upgrade() {
             terminal_panel
                 .update_in(&mut cx, |_, window, cx| {
@@ -371,6 +523,7 @@ impl TerminalPanel {
                 }
                 cx.emit(PanelEvent::ZoomIn);
                 cx.notify();
+                self.serialize(cx);
             }
             pane::Event::ZoomOut => {
                 for pane in self.center.panes() {
@@ -380,6 +533,7 @@ impl TerminalPanel {
                 }
                 cx.emit(PanelEvent::ZoomOut);
                 cx.notify();
+                self.serialize(cx);
             }
             pane::Event::AddItem { item } => {
                 if let Some(workspace) = self.workspace.upgrade() {
@@ -412,26 +566,86 @@ impl TerminalPanel {
         cx: &mut Context<Self>,
     ) -> Option<Entity<Pane>> {
         let workspace = self.workspace.upgrade()?;
-        let workspace = workspace.read(cx);
-        let database_id = workspace.database_id();
-        let weak_workspace = self.workspace.clone();
-        let project = workspace.project().clone();
-        let (working_directory, python_venv_directory) = self
+        let project = workspace.read(cx).project().clone();
+        let (working_directory, python_venv_directory, env) = self
+            .active_terminal_spawn_state(workspace.read(cx), cx)
+            .unwrap_or_default();
+        let terminal_view =
+            self.new_terminal_view(working_directory, python_venv_directory, env, window, cx)?;
+        let pane = new_terminal_pane(
+            self.workspace.clone(),
+            project,
+            self.active_pane.read(cx).is_zoomed(),
+            window,
+            cx,
+        );
+        self.apply_tab_bar_buttons(&pane, cx);
+        pane.update(cx, |pane, cx| {
+            pane.add_item(terminal_view, true, true, None, window, cx);
+        });
+
+        Some(pane)
+    }
+
+    /// Opens a copy of the active terminal, keeping its working directory, Python
+    /// virtual environment and extra environment variables, as a new tab of the active pane.
+    fn duplicate_active_terminal(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let Some((working_directory, python_venv_directory, env)) =
+            self.active_terminal_spawn_state(workspace.read(cx), cx)
+        else {
+            return;
+        };
+        let Some(terminal_view) =
+            self.new_terminal_view(working_directory, python_venv_directory, env, window, cx)
+        else {
+            return;
+        };
+        self.active_pane.update(cx, |pane, cx| {
+            pane.add_item(terminal_view, true, true, None, window, cx);
+        });
+    }
+
+    /// Returns the working directory, Python virtual environment and extra environment
+    /// variables of the active pane's terminal, or `None` if the active item is not a terminal.
+    fn active_terminal_spawn_state(
+        &self,
+        workspace: &Workspace,
+        cx: &App,
+    ) -> Option<(Option<PathBuf>, Option<PathBuf>, HashMap<String, String>)> {
+        let terminal_view = self
             .active_pane
             .read(cx)
-            .active_item()
-            .and_then(|item| item.downcast::<TerminalView>())
-            .map(|terminal_view| {
-                let terminal = terminal_view.read(cx).terminal().read(cx);
-                (
-                    terminal
-                        .working_directory()
-                        .or_else(|| default_working_directory(workspace, cx)),
-                    terminal.python_venv_directory.clone(),
-                )
-            })
-            .unwrap_or((None, None));
-        let kind = TerminalKind::Shell(working_directory);
+            .active_item()?
+            .downcast::<TerminalView>()?;
+        let terminal = terminal_view.read(cx).terminal().read(cx);
+        Some((
+            terminal
+                .working_directory()
+                .or_else(|| default_working_directory(workspace, cx)),
+            terminal.python_venv_directory.clone(),
+            terminal.spawn_env().clone(),
+        ))
+    }
+
+    fn new_terminal_view(
+        &self,
+        working_directory: Option<PathBuf>,
+        python_venv_directory: Option<PathBuf>,
+        env: HashMap<String, String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Box<Entity<TerminalView>>> {
+        let workspace = self.workspace.upgrade()?;
+        let database_id = workspace.read(cx).database_id();
+        let project = workspace.read(cx).project().clone();
+        let kind = TerminalKind::Shell {
+            working_directory,
+            profile: None,
+            env,
+        };
         let window_handle = window.window_handle();
         let terminal = project
             .update(cx, |project, cx| {
@@ -439,29 +653,16 @@ impl TerminalPanel {
             })
             .ok()?;
 
-        let terminal_view = Box::new(cx.new(|cx| {
+        Some(Box::new(cx.new(|cx| {
             TerminalView::new(
-                terminal.clone(),
-                weak_workspace.clone(),
+                terminal,
+                self.workspace.clone(),
                 database_id,
                 project.downgrade(),
                 window,
                 cx,
             )
-        }));
-        let pane = new_terminal_pane(
-            weak_workspace,
-            project,
-            self.active_pane.read(cx).is_zoomed(),
-            window,
-            cx,
-        );
-        self.apply_tab_bar_buttons(&pane, cx);
-        pane.update(cx, |pane, cx| {
-            pane.add_item(terminal_view, true, true, None, window, cx);
-        });
-
-        Some(pane)
+        })))
     }
 
     pub fn open_terminal(
@@ -477,7 +678,11 @@ impl TerminalPanel {
         terminal_panel
             .update(cx, |panel, cx| {
                 panel.add_terminal(
-                    TerminalKind::Shell(Some(action.working_directory.clone())),
+                    TerminalKind::Shell {
+                        working_directory: Some(action.working_directory.clone()),
+                        profile: None,
+                        env: HashMap::default(),
+                    },
                     RevealStrategy::Always,
                     window,
                     cx,
@@ -486,7 +691,34 @@ impl TerminalPanel {
             .detach_and_log_err(cx);
     }
 
+    fn rerun_last_task(
+        workspace: &mut Workspace,
+        _: &RerunLastTask,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(terminal_panel) = workspace.panel::<Self>(cx) else {
+            return;
+        };
+        let Some(last_spawn) = terminal_panel.read(cx).last_spawn.clone() else {
+            struct NoTaskToRerun;
+
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<NoTaskToRerun>(),
+                    "No task has been run yet",
+                ),
+                cx,
+            );
+            return;
+        };
+        terminal_panel.update(cx, |terminal_panel, cx| {
+            terminal_panel.spawn_task(&last_spawn, window, cx);
+        });
+    }
+
     fn spawn_task(&mut self, task: &SpawnInTerminal, window: &mut Window, cx: &mut Context<Self>) {
+        self.last_spawn = Some(task.clone());
         let Ok(is_local) = self
             .workspace
             .update(cx, |workspace, cx| workspace.project().read(cx).is_local())
@@ -494,7 +726,7 @@ impl TerminalPanel {
             return;
         };
 
-        let builder = ShellBuilder::new(is_local, &task.shell);
+        let builder = ShellBuilder::new(is_local, &task.shell).interactive(task.interactive);
         let command_label = builder.command_label(&task.command_label);
         let (command, args) = builder.build(task.command.clone(), &task.args);
 
@@ -591,7 +823,34 @@ impl TerminalPanel {
             return;
         };
 
-        let kind = TerminalKind::Shell(default_working_directory(workspace, cx));
+        let kind = TerminalKind::Shell {
+            working_directory: default_working_directory(workspace, cx),
+            profile: None,
+            env: HashMap::default(),
+        };
+
+        terminal_panel
+            .update(cx, |this, cx| {
+                this.add_terminal(kind, RevealStrategy::Always, window, cx)
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn new_terminal_with_profile(
+        workspace: &mut Workspace,
+        action: &NewTerminalWithProfile,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(terminal_panel) = workspace.panel::<Self>(cx) else {
+            return;
+        };
+
+        let kind = TerminalKind::Shell {
+            working_directory: default_working_directory(workspace, cx),
+            profile: Some(action.profile.clone()),
+            env: HashMap::default(),
+        };
 
         terminal_panel
             .update(cx, |this, cx| {
@@ -642,6 +901,24 @@ impl TerminalPanel {
             .collect()
     }
 
+    /// Activates the given terminal if it is in one of the panel's panes, without focusing it.
+    /// Returns whether the terminal was found.
+    pub(crate) fn reveal_terminal_view(
+        &self,
+        terminal_view: &Entity<TerminalView>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> bool {
+        let Some((pane, item_index)) = self.center.panes().into_iter().find_map(|pane| {
+            let item_index = pane.read(cx).index_for_item(terminal_view)?;
+            Some((pane.clone(), item_index))
+        }) else {
+            return false;
+        };
+        self.activate_terminal_view(&pane, item_index, false, window, cx);
+        true
+    }
+
     fn activate_terminal_view(
         &self,
         pane: &Entity<Pane>,
@@ -747,12 +1024,197 @@ impl TerminalPanel {
             })?;
             terminal_panel.update(cx, |this, cx| {
                 this.pending_terminals_to_add = this.pending_terminals_to_add.saturating_sub(1);
+                if let Ok(terminal) = &result {
+                    this.watch_task(terminal, cx);
+                }
                 this.serialize(cx)
             })?;
             result
         })
     }
 
+    /// Keeps the panel's badge in sync with the task running in `terminal`, if any,
+    /// briefly showing whether it succeeded once it completes.
+    fn watch_task(&mut self, terminal: &Entity<Terminal>, cx: &mut Context<Self>) {
+        let Some(task) = terminal.read(cx).task() else {
+            return;
+        };
+        if task.status != TaskStatus::Running {
+            return;
+        }
+        let completed = terminal.read(cx).wait_for_completed_task(cx);
+        let terminal = terminal.downgrade();
+        cx.notify();
+        cx.spawn(async move |terminal_panel, cx| {
+            completed.await;
+            let status = terminal
+                .read_with(cx, |terminal, _| terminal.task().map(|task| task.status))
+                .ok()
+                .flatten();
+            terminal_panel
+                .update(cx, |terminal_panel, cx| match status {
+                    Some(TaskStatus::Completed { success, .. }) => {
+                        terminal_panel.flash_task_outcome(success, cx)
+                    }
+                    _ => cx.notify(),
+                })
+                .ok();
+        })
+        .detach();
+    }
+
+    fn flash_task_outcome(&mut self, success: bool, cx: &mut Context<Self>) {
+        let clear = cx.spawn(async move |terminal_panel, cx| {
+            cx.background_executor()
+                .timer(TASK_OUTCOME_FLASH_DURATION)
+                .await;
+            terminal_panel
+                .update(cx, |terminal_panel, cx| {
+                    terminal_panel.task_outcome = None;
+                    cx.notify();
+                })
+                .ok();
+        });
+        self.task_outcome = Some((success, clear));
+        cx.notify();
+    }
+
+    fn running_tasks(&self, cx: &App) -> usize {
+        self.center
+            .panes()
+            .into_iter()
+            .flat_map(|pane| pane.read(cx).items())
+            .filter_map(|item| item.act_as::<TerminalView>(cx))
+            .filter(|terminal_view| {
+                terminal_view
+                    .read(cx)
+                    .terminal()
+                    .read(cx)
+                    .task()
+                    .is_some_and(|task| task.status == TaskStatus::Running)
+            })
+            .count()
+    }
+
+    fn handle_tab_drag_move(
+        &mut self,
+        event: &DragMoveEvent<DraggedTab>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let tab = event.drag(cx);
+        let window_bounds = Bounds::new(Point::default(), window.viewport_size());
+        let is_dragged_out = tab.item.downcast::<TerminalView>().is_some()
+            && self.center.panes().contains(&&tab.pane)
+            && !window_bounds.contains(&event.event.position);
+        self.tab_dragged_out = is_dragged_out.then(|| tab.clone());
+    }
+
+    fn handle_mouse_up_out(
+        &mut self,
+        event: &MouseUpEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(tab) = self.tab_dragged_out.take() else {
+            return;
+        };
+        let window_bounds = Bounds::new(Point::default(), window.viewport_size());
+        if cx.has_active_drag() && !window_bounds.contains(&event.position) {
+            self.detach_terminal_into_new_window(&tab, window, cx);
+        }
+    }
+
+    /// Moves the terminal of a tab dropped outside of the window into the terminal panel
+    /// of a new window on the same project. The terminal keeps running, so its working
+    /// directory and task carry over, and it stays one of the project's terminals, whether
+    /// the project is local or remote.
+    fn detach_terminal_into_new_window(
+        &mut self,
+        tab: &DraggedTab,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(terminal_view) = tab.item.downcast::<TerminalView>() else {
+            return;
+        };
+        let Ok((app_state, project)) = self.workspace.read_with(cx, |workspace, _| {
+            (workspace.app_state().clone(), workspace.project().clone())
+        }) else {
+            return;
+        };
+        let terminal = terminal_view.read(cx).terminal().clone();
+        tab.pane.update(cx, |pane, cx| {
+            pane.remove_item(terminal_view.item_id(), false, true, window, cx)
+        });
+        let options = (app_state.build_window_options)(None, cx);
+        let Some(new_window) = cx
+            .open_window(options, |window, cx| {
+                cx.new(|cx| Workspace::new(None, project, app_state, window, cx))
+            })
+            .log_err()
+        else {
+            return;
+        };
+        new_window
+            .update(cx, |workspace, window, cx| {
+                cx.activate(true);
+                window.activate_window();
+                Self::adopt_detached_terminal(workspace, terminal, window, cx);
+            })
+            .log_err();
+    }
+
+    /// Adds a terminal dragged out of another window to the workspace's terminal panel,
+    /// or to the panel it will load if it hasn't yet.
+    fn adopt_detached_terminal(
+        workspace: &mut Workspace,
+        terminal: Entity<Terminal>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if let Some(terminal_panel) = workspace.panel::<Self>(cx) {
+            terminal_panel.update(cx, |terminal_panel, cx| {
+                terminal_panel.add_detached_terminal(terminal, window, cx)
+            });
+            workspace.focus_panel::<Self>(window, cx);
+        } else {
+            let workspace_id = cx.entity_id();
+            cx.default_global::<DetachedTerminals>()
+                .0
+                .entry(workspace_id)
+                .or_default()
+                .push(terminal);
+        }
+    }
+
+    fn add_detached_terminal(
+        &mut self,
+        terminal: Entity<Terminal>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let terminal_view = Box::new(cx.new(|cx| {
+            TerminalView::new(
+                terminal.clone(),
+                self.workspace.clone(),
+                workspace.read(cx).database_id(),
+                workspace.read(cx).project().downgrade(),
+                window,
+                cx,
+            )
+        }));
+        self.active_pane.update(cx, |pane, cx| {
+            pane.add_item(terminal_view, true, true, None, window, cx)
+        });
+        self.adopted_detached_terminals = true;
+        self.watch_task(&terminal, cx);
+        self.serialize(cx);
+    }
+
     fn serialize(&mut self, cx: &mut Context<Self>) {
         let height = self.height;
         let width = self.width;
@@ -771,13 +1233,14 @@ impl TerminalPanel {
                 .timer(Duration::from_millis(50))
                 .await;
             let terminal_panel = terminal_panel.upgrade()?;
-            let items = terminal_panel
+            let (items, zoomed) = terminal_panel
                 .update(cx, |terminal_panel, cx| {
-                    SerializedItems::WithSplits(serialize_pane_group(
+                    let items = SerializedItems::WithSplits(serialize_pane_group(
                         &terminal_panel.center,
                         &terminal_panel.active_pane,
                         cx,
-                    ))
+                    ));
+                    (items, terminal_panel.active_pane.read(cx).is_zoomed())
                 })
                 .ok()?;
             cx.background_spawn(
@@ -790,6 +1253,7 @@ impl TerminalPanel {
                                 active_item_id: None,
                                 height,
                                 width,
+                                zoomed,
                             })?,
                         )
                         .await?;
@@ -833,7 +1297,12 @@ impl TerminalPanel {
                 .log_err()?;
             terminal_to_replace
                 .update_in(cx, |terminal_to_replace, window, cx| {
-                    terminal_to_replace.set_terminal(new_terminal, window, cx);
+                    terminal_to_replace.set_terminal(new_terminal.clone(), window, cx);
+                })
+                .ok()?;
+            terminal_panel
+                .update(cx, |terminal_panel, cx| {
+                    terminal_panel.watch_task(&new_terminal, cx)
                 })
                 .ok()?;
 
@@ -914,7 +1383,46 @@ impl TerminalPanel {
     }
 
     fn has_no_terminals(&self, cx: &App) -> bool {
-        self.active_pane.read(cx).items_len() == 0 && self.pending_terminals_to_add == 0
+        self.center
+            .panes()
+            .into_iter()
+            .all(|pane| pane.read(cx).items_len() == 0)
+            && self.pending_terminals_to_add == 0
+    }
+
+    /// Activates the item `offset` tabs away from the active one in the active pane,
+    /// wrapping around at either end.
+    fn cycle_active_pane_items(&mut self, offset: isize, window: &mut Window, cx: &mut App) {
+        self.active_pane.update(cx, |pane, cx| {
+            let items_len = pane.items_len();
+            if items_len <= 1 {
+                return;
+            }
+            let index = (pane.active_item_index() as isize + offset).rem_euclid(items_len as isize);
+            pane.activate_item(index as usize, true, true, window, cx);
+        });
+    }
+
+    /// Shown instead of the panes when the panel has no terminals and
+    /// `terminal.spawn_on_activate` is off.
+    fn render_empty_state(&self, cx: &App) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .track_focus(&self.active_pane.focus_handle(cx))
+            .justify_center()
+            .items_center()
+            .gap_2()
+            .child(Label::new("No terminals open").color(Color::Muted))
+            .child(
+                Button::new("new-terminal", "New Terminal")
+                    .icon(IconName::Terminal)
+                    .icon_position(IconPosition::Start)
+                    .icon_size(IconSize::Small)
+                    .icon_color(Color::Muted)
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(NewTerminal.boxed_clone(), cx)
+                    }),
+            )
     }
 
     pub fn assistant_enabled(&self) -> bool {
@@ -957,6 +1465,112 @@ impl TerminalPanel {
             cx.notify();
         }
     }
+
+    fn swap_items_with_pane_in_direction(
+        &mut self,
+        direction: SplitDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(to) = self
+            .center
+            .find_pane_in_direction(&self.active_pane, direction, cx)
+            .cloned()
+        else {
+            return;
+        };
+        let from = self.active_pane.clone();
+        let pane_contents = |pane: &Entity<Pane>, cx: &App| {
+            let pane = pane.read(cx);
+            (
+                pane.items().cloned().collect::<Vec<_>>(),
+                pane.active_item_index(),
+            )
+        };
+        let (from_items, from_active_index) = pane_contents(&from, cx);
+        let (to_items, to_active_index) = pane_contents(&to, cx);
+
+        // Items are taken out of both panes before being moved over, and the panes are kept
+        // around while empty, so that the split layout stays the same.
+        for (pane, items) in [(&from, &from_items), (&to, &to_items)] {
+            pane.update(cx, |pane, cx| {
+                for item in items {
+                    pane.remove_item(item.item_id(), false, false, window, cx);
+                }
+            });
+        }
+        for (pane, items, active_index) in [
+            (&from, to_items, to_active_index),
+            (&to, from_items, from_active_index),
+        ] {
+            pane.update(cx, |pane, cx| {
+                for item in items {
+                    let index = pane.items_len();
+                    pane.add_item(item, false, false, Some(index), window, cx);
+                }
+                if active_index < pane.items_len() {
+                    pane.activate_item(active_index, false, false, window, cx);
+                }
+            });
+        }
+
+        window.focus(&from.focus_handle(cx));
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    /// Moves the terminals of every pane into the active one and removes the other panes.
+    fn collapse_to_single_pane(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let active_pane = self.active_pane.clone();
+        let other_panes = self
+            .center
+            .panes()
+            .into_iter()
+            .filter(|pane| **pane != active_pane)
+            .cloned()
+            .collect::<Vec<_>>();
+        if other_panes.is_empty() {
+            return;
+        }
+
+        let active_item = active_pane.read(cx).active_item();
+        for pane in &other_panes {
+            let items = pane.read(cx).items().cloned().collect::<Vec<_>>();
+            // The emptied pane isn't closed, as its `pane::Event::Remove` would close the whole
+            // panel once this was the last pane left in `self.center`.
+            pane.update(cx, |pane, cx| {
+                for item in &items {
+                    pane.remove_item(item.item_id(), false, false, window, cx);
+                }
+            });
+            active_pane.update(cx, |active_pane, cx| {
+                for item in items {
+                    let index = active_pane.items_len();
+                    active_pane.add_item(item, false, false, Some(index), window, cx);
+                }
+            });
+            self.center.remove(pane).log_err();
+        }
+
+        let was_zoomed = active_pane.update(cx, |pane, cx| {
+            if let Some(index) = active_item
+                .as_ref()
+                .and_then(|item| pane.index_for_item(item.as_ref()))
+            {
+                pane.activate_item(index, true, true, window, cx);
+            }
+            let was_zoomed = pane.is_zoomed();
+            pane.set_zoomed(false, cx);
+            was_zoomed
+        });
+        if was_zoomed {
+            cx.emit(PanelEvent::ZoomOut);
+        }
+
+        window.focus(&active_pane.focus_handle(cx));
+        self.serialize(cx);
+        cx.notify();
+    }
 }
 
 fn is_enabled_in_workspace(workspace: &Workspace, cx: &App) -> bool {
@@ -1126,6 +1740,8 @@ pub fn new_terminal_pane(
                 {
                     add_paths_to_terminal(pane, &[entry_path], window, cx);
                 }
+            } else if let Some(dragged_text) = dropped_item.downcast_ref::<DraggedText>() {
+                add_text_to_terminal(pane, &dragged_text.text, window, cx);
             } else if is_local {
                 if let Some(paths) = dropped_item.downcast_ref::<ExternalPaths>() {
                     add_paths_to_terminal(pane, paths.paths(), window, cx);
@@ -1182,6 +1798,34 @@ fn add_paths_to_terminal(
     }
 }
 
+fn add_text_to_terminal(pane: &mut Pane, text: &str, window: &mut Window, cx: &mut Context<Pane>) {
+    if let Some(terminal_view) = pane
+        .active_item()
+        .and_then(|item| item.downcast::<TerminalView>())
+    {
+        window.focus(&terminal_view.focus_handle(cx));
+        terminal_view.update(cx, |terminal_view, cx| {
+            terminal_view.terminal().update(cx, |terminal, _| {
+                let bracketed_paste = terminal
+                    .last_content()
+                    .mode
+                    .contains(TermMode::BRACKETED_PASTE);
+                if let Some(text) = dropped_text_to_paste(text, bracketed_paste) {
+                    terminal.paste(text);
+                }
+            });
+        });
+    }
+}
+
+/// Prepares dropped text so that pasting it never runs a command on its own: trailing
+/// line breaks are dropped, and text spanning several lines is only pasted when the
+/// shell uses bracketed paste, as each line break would run a command otherwise.
+fn dropped_text_to_paste(text: &str, bracketed_paste: bool) -> Option<&str> {
+    let text = text.trim_end_matches(['\r', '\n']);
+    (bracketed_paste || !text.contains(['\r', '\n'])).then_some(text)
+}
+
 impl EventEmitter<PanelEvent> for TerminalPanel {}
 
 impl Render for TerminalPanel {
@@ -1199,18 +1843,26 @@ impl Render for TerminalPanel {
         );
         BufferSearchBar::register(&mut registrar);
         let registrar = registrar.into_div();
+        let show_empty_state =
+            self.has_no_terminals(cx) && !TerminalSettings::get_global(cx).spawn_on_activate;
         self.workspace
             .update(cx, |workspace, cx| {
-                registrar.size_full().child(self.center.render(
-                    workspace.project(),
-                    &HashMap::default(),
-                    None,
-                    &self.active_pane,
-                    workspace.zoomed_item(),
-                    workspace.app_state(),
-                    window,
-                    cx,
-                ))
+                if show_empty_state {
+                    registrar
+                        .size_full()
+                        .child(self.render_empty_state(cx).into_any_element())
+                } else {
+                    registrar.size_full().child(self.center.render(
+                        workspace.project(),
+                        &HashMap::default(),
+                        None,
+                        &self.active_pane,
+                        workspace.zoomed_item(),
+                        workspace.app_state(),
+                        window,
+                        cx,
+                    ))
+                }
             })
             .ok()
             .map(|div| {
@@ -1284,6 +1936,21 @@ impl Render for TerminalPanel {
                         }
                     }),
                 )
+                .on_action(
+                    cx.listener(|terminal_panel, _: &DuplicateTerminal, window, cx| {
+                        terminal_panel.duplicate_active_terminal(window, cx);
+                    }),
+                )
+                .on_action(
+                    cx.listener(|terminal_panel, _: &ActivateNextItem, window, cx| {
+                        terminal_panel.cycle_active_pane_items(1, window, cx);
+                    }),
+                )
+                .on_action(
+                    cx.listener(|terminal_panel, _: &ActivatePrevItem, window, cx| {
+                        terminal_panel.cycle_active_pane_items(-1, window, cx);
+                    }),
+                )
                 .on_action(cx.listener(|terminal_panel, _: &SwapPaneLeft, _, cx| {
                     terminal_panel.swap_pane_in_direction(SplitDirection::Left, cx);
                 }))
@@ -1296,6 +1963,20 @@ impl Render for TerminalPanel {
                 .on_action(cx.listener(|terminal_panel, _: &SwapPaneDown, _, cx| {
                     terminal_panel.swap_pane_in_direction(SplitDirection::Down, cx);
                 }))
+                .on_action(cx.listener(
+                    |terminal_panel, action: &SwapItemsWithPaneInDirection, window, cx| {
+                        terminal_panel.swap_items_with_pane_in_direction(
+                            action.direction,
+                            window,
+                            cx,
+                        );
+                    },
+                ))
+                .on_action(cx.listener(
+                    |terminal_panel, _: &CollapseTerminalToSinglePane, window, cx| {
+                        terminal_panel.collapse_to_single_pane(window, cx);
+                    },
+                ))
                 .on_action(
                     cx.listener(|terminal_panel, action: &MoveItemToPane, window, cx| {
                         let Some(&target_pane) =
@@ -1331,6 +2012,8 @@ impl Render for TerminalPanel {
                         };
                     },
                 ))
+                .on_drag_move(cx.listener(Self::handle_tab_drag_move))
+                .on_mouse_up_out(MouseButton::Left, cx.listener(Self::handle_mouse_up_out))
             })
             .unwrap_or_else(|| div())
     }
@@ -1385,6 +2068,21 @@ impl Panel for TerminalPanel {
         }
     }
 
+    fn min_size(&self, cx: &App) -> Pixels {
+        let settings = TerminalSettings::get_global(cx);
+        match settings.dock {
+            TerminalDockPosition::Left | TerminalDockPosition::Right => MIN_PANEL_WIDTH,
+            TerminalDockPosition::Bottom => {
+                let font_size = settings.font_size.map_or_else(
+                    || ThemeSettings::get_global(cx).buffer_font_size(cx),
+                    |size| theme::adjusted_font_size(size, cx),
+                );
+                let line_height = font_size * settings.line_height.multiplier();
+                Tab::container_height(cx) + line_height * MIN_VISIBLE_LINES
+            }
+        }
+    }
+
     fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>) {
         match self.position(window, cx) {
             DockPosition::Left | DockPosition::Right => self.width = size,
@@ -1400,6 +2098,11 @@ impl Panel for TerminalPanel {
         self.active_pane.read(cx).is_zoomed()
     }
 
+    fn starts_open(&self, _window: &Window, cx: &App) -> bool {
+        (!self.restored && TerminalSettings::get_global(cx).open_on_startup)
+            || self.adopted_detached_terminals
+    }
+
     fn set_zoomed(&mut self, zoomed: bool, _: &mut Window, cx: &mut Context<Self>) {
         for pane in self.center.panes() {
             pane.update(cx, |pane, cx| {
@@ -1415,10 +2118,18 @@ impl Panel for TerminalPanel {
         if !active || old_active == active || !self.has_no_terminals(cx) {
             return;
         }
+        if !TerminalSettings::get_global(cx).spawn_on_activate {
+            return;
+        }
         cx.defer_in(window, |this, window, cx| {
-            let Ok(kind) = this.workspace.update(cx, |workspace, cx| {
-                TerminalKind::Shell(default_working_directory(workspace, cx))
-            }) else {
+            let Ok(kind) = this
+                .workspace
+                .update(cx, |workspace, cx| TerminalKind::Shell {
+                    working_directory: default_working_directory(workspace, cx),
+                    profile: None,
+                    env: HashMap::default(),
+                })
+            else {
                 return;
             };
 
@@ -1427,7 +2138,26 @@ impl Panel for TerminalPanel {
         })
     }
 
-    fn icon_label(&self, _window: &Window, cx: &App) -> Option<String> {
+    fn on_close(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        // Persist the layout as the panel gets hidden, so it is up to date even if
+        // the app quits before any further change schedules a serialization.
+        self.serialize(cx);
+    }
+
+    fn badge(&self, _window: &Window, cx: &App) -> Option<PanelBadge> {
+        let running_tasks = self.running_tasks(cx);
+        if running_tasks > 0 {
+            return Some(PanelBadge::count(running_tasks, Color::Accent));
+        }
+        if let Some((success, _)) = &self.task_outcome {
+            let color = if *success {
+                Color::Success
+            } else {
+                Color::Error
+            };
+            return Some(PanelBadge::dot(color));
+        }
+
         let count = self
             .center
             .panes()
@@ -1437,7 +2167,7 @@ impl Panel for TerminalPanel {
         if count == 0 {
             None
         } else {
-            Some(count.to_string())
+            Some(PanelBadge::count(count, Color::Muted))
         }
     }
 
@@ -1495,3 +2225,284 @@ impl Render for InlineAssistTabBarButton {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{Modifiers, TestAppContext, VisualTestContext, point, px};
+    use settings::SettingsStore;
+    use workspace::AppState;
+
+    #[gpui::test]
+    async fn test_collapse_to_single_pane(cx: &mut TestAppContext) {
+        let params = cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            AppState::test(cx)
+        });
+        cx.update(|cx| {
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+
+        let project = Project::test(params.fs.clone(), [], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+
+        let terminal_panel = workspace.update_in(cx, |workspace, window, cx| {
+            let terminal_panel = cx.new(|cx| TerminalPanel::new(workspace, window, cx));
+            workspace.add_panel(terminal_panel.clone(), window, cx);
+            workspace.open_panel::<TerminalPanel>(window, cx);
+            terminal_panel
+        });
+        terminal_panel
+            .update_in(cx, |terminal_panel, window, cx| {
+                terminal_panel.add_terminal(
+                    TerminalKind::Shell {
+                        working_directory: None,
+                        profile: None,
+                        env: HashMap::default(),
+                    },
+                    RevealStrategy::NoFocus,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        for _ in 0..2 {
+            terminal_panel.update(cx, |terminal_panel, cx| {
+                terminal_panel.active_pane.update(cx, |pane, cx| {
+                    pane.split(SplitDirection::Right, cx);
+                });
+            });
+            cx.run_until_parked();
+        }
+        terminal_panel.read_with(cx, |terminal_panel, _| {
+            assert_eq!(terminal_panel.center.panes().len(), 3);
+        });
+
+        terminal_panel.update_in(cx, |terminal_panel, window, cx| {
+            terminal_panel.collapse_to_single_pane(window, cx);
+        });
+        cx.run_until_parked();
+
+        terminal_panel.read_with(cx, |terminal_panel, cx| {
+            let panes = terminal_panel.center.panes();
+            assert_eq!(panes.len(), 1);
+            assert_eq!(panes[0].read(cx).items_len(), 3);
+        });
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(
+                workspace.bottom_dock().read(cx).is_open(),
+                "collapsing the panes should keep the terminal dock open"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_detach_terminal_into_new_window(cx: &mut TestAppContext) {
+        let params = cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            AppState::test(cx)
+        });
+        cx.update(|cx| {
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+
+        let project = Project::test(params.fs.clone(), [], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+
+        let terminal_panel = workspace.update_in(cx, |workspace, window, cx| {
+            let terminal_panel = cx.new(|cx| TerminalPanel::new(workspace, window, cx));
+            workspace.add_panel(terminal_panel.clone(), window, cx);
+            workspace.open_panel::<TerminalPanel>(window, cx);
+            terminal_panel
+        });
+        terminal_panel
+            .update_in(cx, |terminal_panel, window, cx| {
+                terminal_panel.add_terminal(
+                    TerminalKind::Shell {
+                        working_directory: None,
+                        profile: None,
+                        env: HashMap::default(),
+                    },
+                    RevealStrategy::NoFocus,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let (pane, terminal_view) = terminal_panel.read_with(cx, |terminal_panel, cx| {
+            let pane = terminal_panel.active_pane.clone();
+            let terminal_view = pane
+                .read(cx)
+                .active_item()
+                .and_then(|item| item.downcast::<TerminalView>())
+                .unwrap();
+            (pane, terminal_view)
+        });
+        let terminal =
+            terminal_view.read_with(cx, |terminal_view, _| terminal_view.terminal().clone());
+        terminal_panel.update_in(cx, |terminal_panel, window, cx| {
+            let tab = DraggedTab {
+                pane: pane.clone(),
+                item: Box::new(terminal_view.clone()),
+                ix: 0,
+                detail: 0,
+                is_active: true,
+            };
+            terminal_panel.detach_terminal_into_new_window(&tab, window, cx);
+        });
+        cx.run_until_parked();
+
+        assert_eq!(pane.read_with(cx, |pane, _| pane.items_len()), 0);
+        let new_workspace = cx.update(|window, cx| {
+            let old_window_id = window.window_handle().window_id();
+            cx.windows()
+                .into_iter()
+                .filter(|window| window.window_id() != old_window_id)
+                .find_map(|window| window.downcast::<Workspace>())
+                .unwrap()
+                .root(cx)
+                .unwrap()
+        });
+        new_workspace.read_with(cx, |new_workspace, _| {
+            assert_eq!(
+                new_workspace.project(),
+                &project,
+                "the new window should be on the terminal's project"
+            );
+        });
+        let detached_terminals = cx.update(|_, cx| {
+            cx.global::<DetachedTerminals>()
+                .0
+                .get(&new_workspace.entity_id())
+                .cloned()
+                .unwrap_or_default()
+        });
+        assert_eq!(detached_terminals, [terminal.clone()]);
+        project.read_with(cx, |project, _| {
+            assert!(
+                project
+                    .local_terminal_handles()
+                    .iter()
+                    .any(|handle| handle.upgrade().as_ref() == Some(&terminal)),
+                "the terminal should still belong to the project"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_dropping_text_on_terminal_pane(cx: &mut TestAppContext) {
+        let params = cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            AppState::test(cx)
+        });
+        cx.update(|cx| {
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+
+        let project = Project::test(params.fs.clone(), [], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+
+        let terminal_panel = workspace.update_in(cx, |workspace, window, cx| {
+            let terminal_panel = cx.new(|cx| TerminalPanel::new(workspace, window, cx));
+            workspace.add_panel(terminal_panel.clone(), window, cx);
+            workspace.open_panel::<TerminalPanel>(window, cx);
+            terminal_panel
+        });
+        terminal_panel
+            .update_in(cx, |terminal_panel, window, cx| {
+                terminal_panel.add_terminal(
+                    TerminalKind::Shell {
+                        working_directory: None,
+                        profile: None,
+                        env: HashMap::default(),
+                    },
+                    RevealStrategy::NoFocus,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let terminal_view = terminal_panel.read_with(cx, |terminal_panel, cx| {
+            terminal_panel
+                .active_pane
+                .read(cx)
+                .active_item()
+                .and_then(|item| item.downcast::<TerminalView>())
+                .unwrap()
+        });
+        let is_terminal_focused = |cx: &mut VisualTestContext| {
+            cx.update(|window, cx| terminal_view.focus_handle(cx).is_focused(window))
+        };
+        assert!(!is_terminal_focused(cx));
+
+        // Drop the text below the terminal pane's tab bar, onto the terminal itself.
+        let tab_bounds = cx.debug_bounds("TAB-0").unwrap();
+        let drop_position = tab_bounds.bottom_left() + point(px(10.), px(40.));
+        cx.update(|_, cx| {
+            let dragged_text = DraggedText {
+                text: "echo dropped\n".into(),
+            };
+            let drag_view = cx.new(|_| dragged_text.clone());
+            cx.start_drag(dragged_text, drag_view, point(px(0.), px(0.)));
+        });
+        cx.simulate_mouse_move(drop_position, MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_up(drop_position, MouseButton::Left, Modifiers::none());
+
+        assert!(!cx.update(|_, cx| cx.has_active_drag()));
+        assert!(
+            is_terminal_focused(cx),
+            "dropping text should focus the terminal it was pasted into"
+        );
+        terminal_view.read_with(cx, |terminal_view, cx| {
+            assert_eq!(
+                terminal_view.terminal().read(cx).pasted_text(),
+                ["echo dropped"],
+                "dropped text should be pasted without its trailing newline"
+            );
+        });
+    }
+
+    #[test]
+    fn test_dropped_text_to_paste() {
+        assert_eq!(dropped_text_to_paste("ls -la\n", true), Some("ls -la"));
+        assert_eq!(dropped_text_to_paste("ls -la\r\n", false), Some("ls -la"));
+        assert_eq!(
+            dropped_text_to_paste("cd src\nls\n\n", true),
+            Some("cd src\nls")
+        );
+        assert_eq!(dropped_text_to_paste("cd src\r\nls\n", false), None);
+    }
+}
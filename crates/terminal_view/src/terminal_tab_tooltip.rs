@@ -1,9 +1,13 @@
 use gpui::{IntoElement, Render};
+use terminal::ProcessUsage;
 use ui::{Divider, prelude::*, tooltip_container};
 
 pub struct TerminalTooltip {
     title: SharedString,
     pid: u32,
+    exit_code: Option<i32>,
+    process_name: Option<SharedString>,
+    process_usage: Option<ProcessUsage>,
 }
 
 impl TerminalTooltip {
@@ -11,8 +15,40 @@ impl TerminalTooltip {
         Self {
             title: title.into(),
             pid,
+            exit_code: None,
+            process_name: None,
+            process_usage: None,
         }
     }
+
+    /// Shows the exit code of the terminal's finished task.
+    pub fn exit_code(mut self, exit_code: Option<i32>) -> Self {
+        self.exit_code = exit_code;
+        self
+    }
+
+    /// Shows the name of the foreground process, along with its CPU and memory usage
+    /// when they are known.
+    pub fn process(mut self, name: Option<SharedString>, usage: Option<ProcessUsage>) -> Self {
+        self.process_name = name;
+        self.process_usage = usage;
+        self
+    }
+}
+
+fn format_memory(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024. && unit + 1 < UNITS.len() {
+        size /= 1024.;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[unit])
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
 }
 
 impl Render for TerminalTooltip {
@@ -29,8 +65,52 @@ impl Render for TerminalTooltip {
                             Label::new(format!("Process ID (PID): {}", self.pid))
                                 .color(Color::Muted)
                                 .size(LabelSize::Small),
-                        ),
+                        )
+                        .when_some(self.process_name.clone(), |this, name| {
+                            this.child(
+                                Label::new(format!("Process: {name}"))
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small),
+                            )
+                        })
+                        .when_some(self.process_usage, |this, usage| {
+                            let memory = format_memory(usage.memory);
+                            this.child(
+                                Label::new(match usage.cpu_percent {
+                                    Some(cpu_percent) => {
+                                        format!("CPU: {cpu_percent:.1}%, Memory: {memory}")
+                                    }
+                                    None => format!("Memory: {memory}"),
+                                })
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                            )
+                        })
+                        .when_some(self.exit_code, |this, exit_code| {
+                            this.child(
+                                Label::new(format!("Exit code: {exit_code}"))
+                                    .color(if exit_code == 0 {
+                                        Color::Success
+                                    } else {
+                                        Color::Error
+                                    })
+                                    .size(LabelSize::Small),
+                            )
+                        }),
                 )
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_memory() {
+        assert_eq!(format_memory(512), "512 B");
+        assert_eq!(format_memory(1536), "1.5 KiB");
+        assert_eq!(format_memory(300 * 1024 * 1024), "300.0 MiB");
+        assert_eq!(format_memory(5 * 1024 * 1024 * 1024 * 1024), "5120.0 GiB");
+    }
+}
@@ -4,20 +4,22 @@ pub mod terminal_panel;
 pub mod terminal_scrollbar;
 pub mod terminal_tab_tooltip;
 
+use collections::HashMap;
 use editor::{Editor, EditorSettings, actions::SelectAll, scroll::ScrollbarAutoHide};
 use gpui::{
-    AnyElement, App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyContext,
-    KeyDownEvent, Keystroke, MouseButton, MouseDownEvent, Pixels, Render, ScrollWheelEvent,
-    Stateful, Styled, Subscription, Task, WeakEntity, anchored, deferred, div, impl_actions,
+    Action, Animation, AnimationExt, AnyElement, App, DismissEvent, Entity, EventEmitter,
+    FocusHandle, Focusable, KeyContext, KeyDownEvent, Keystroke, MouseButton, MouseDownEvent,
+    Pixels, Render, ScrollWheelEvent, Stateful, Styled, Subscription, Task, Transformation,
+    WeakEntity, anchored, deferred, div, impl_actions, percentage,
 };
 use itertools::Itertools;
 use persistence::TERMINAL_DB;
 use project::{Entry, Metadata, Project, search::SearchQuery, terminals::TerminalKind};
 use schemars::JsonSchema;
 use terminal::{
-    Clear, Copy, Event, MaybeNavigationTarget, Paste, ScrollLineDown, ScrollLineUp, ScrollPageDown,
-    ScrollPageUp, ScrollToBottom, ScrollToTop, ShowCharacterPalette, TaskState, TaskStatus,
-    Terminal, TerminalBounds, ToggleViMode,
+    Clear, Copy, Event, MaybeNavigationTarget, Paste, RestartTerminal, ScrollLineDown,
+    ScrollLineUp, ScrollPageDown, ScrollPageUp, ScrollToBottom, ScrollToTop, ShowCharacterPalette,
+    TaskState, TaskStatus, Terminal, TerminalBounds, ToggleShowInvisibles, ToggleViMode,
     alacritty_terminal::{
         index::Point,
         term::{TermMode, search::RegexSearch},
@@ -62,6 +64,8 @@ const REGEX_SPECIAL_CHARS: &[char] = &[
 ];
 
 const CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(500);
+/// How often the foreground process's CPU and memory usage is sampled for the tab tooltip.
+const PROCESS_USAGE_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
 
 const GIT_DIFF_PATH_PREFIXES: &[&str] = &["a", "b"];
 
@@ -123,8 +127,15 @@ pub struct TerminalView {
     scroll_handle: TerminalScrollHandle,
     show_scrollbar: bool,
     hide_scrollbar_task: Option<Task<()>>,
+    show_invisibles: bool,
+    auto_close_task: Option<Task<()>>,
+    /// Whether the view has been rendered since it was last deactivated. The process
+    /// usage is only sampled while it is.
+    is_shown: bool,
+    _process_usage_sampler: Task<()>,
     _subscriptions: Vec<Subscription>,
     _terminal_subscriptions: Vec<Subscription>,
+    _task_completion: Task<()>,
 }
 
 impl EventEmitter<Event> for TerminalView {}
@@ -148,7 +159,11 @@ impl TerminalView {
         let working_directory = default_working_directory(workspace, cx);
         TerminalPanel::add_center_terminal(
             workspace,
-            TerminalKind::Shell(working_directory),
+            TerminalKind::Shell {
+                working_directory,
+                profile: None,
+                env: HashMap::default(),
+            },
             window,
             cx,
         )
@@ -166,6 +181,8 @@ impl TerminalView {
         let workspace_handle = workspace.clone();
         let terminal_subscriptions =
             subscribe_for_terminal_events(&terminal, workspace, window, cx);
+        let task_completion = watch_task_completion(&terminal, cx);
+        let process_usage_sampler = sample_process_usage(window, cx);
 
         let focus_handle = cx.focus_handle();
         let focus_in = cx.on_focus_in(&focus_handle, window, |terminal_view, window, cx| {
@@ -205,12 +222,17 @@ impl TerminalView {
             scroll_handle,
             show_scrollbar: !Self::should_autohide_scrollbar(cx),
             hide_scrollbar_task: None,
+            show_invisibles: false,
+            auto_close_task: None,
+            is_shown: false,
+            _process_usage_sampler: process_usage_sampler,
             _subscriptions: vec![
                 focus_in,
                 focus_out,
                 cx.observe_global::<SettingsStore>(Self::settings_changed),
             ],
             _terminal_subscriptions: terminal_subscriptions,
+            _task_completion: task_completion,
         }
     }
 
@@ -463,6 +485,29 @@ impl TerminalView {
         cx.notify();
     }
 
+    /// Whether spaces, tabs and line endings are drawn as visible glyphs.
+    /// This only affects rendering, the terminal content is left untouched.
+    pub fn show_invisibles(&self) -> bool {
+        self.show_invisibles
+    }
+
+    pub fn set_show_invisibles(&mut self, show_invisibles: bool, cx: &mut Context<Self>) {
+        if self.show_invisibles != show_invisibles {
+            self.show_invisibles = show_invisibles;
+            cx.emit(ItemEvent::UpdateTab);
+            cx.notify();
+        }
+    }
+
+    fn toggle_show_invisibles(
+        &mut self,
+        _: &ToggleShowInvisibles,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_show_invisibles(!self.show_invisibles, cx);
+    }
+
     pub fn should_show_cursor(&self, focused: bool, cx: &mut Context<Self>) -> bool {
         //Don't blink the cursor when not focused, blinking is disabled, or paused
         if !focused
@@ -673,7 +718,79 @@ impl TerminalView {
     ) {
         self._terminal_subscriptions =
             subscribe_for_terminal_events(&terminal, self.workspace.clone(), window, cx);
+        self._task_completion = watch_task_completion(&terminal, cx);
         self.terminal = terminal;
+        cx.emit(ItemEvent::UpdateTab);
+    }
+
+    /// Replaces the terminal with a new one running the same shell or task, in place.
+    fn restart_terminal(
+        &mut self,
+        _: &RestartTerminal,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        let Some(kind) = project.read(cx).terminal_kind(&self.terminal).cloned() else {
+            return;
+        };
+        let window_handle = window.window_handle();
+        let new_terminal = project.update(cx, |project, cx| {
+            project.create_terminal(kind, window_handle, cx)
+        });
+        cx.spawn_in(window, async move |terminal_view, cx| {
+            let new_terminal = new_terminal.await?;
+            terminal_view.update_in(cx, |terminal_view, window, cx| {
+                terminal_view.set_terminal(new_terminal, window, cx);
+                terminal_view.has_bell = false;
+                terminal_view.auto_close_task = None;
+                cx.emit(ItemEvent::UpdateTab);
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Closes the terminal after the given delay, unless it is focused by then.
+    fn close_after(&mut self, delay: Duration, window: &mut Window, cx: &mut Context<Self>) {
+        self.auto_close_task = Some(cx.spawn_in(window, async move |terminal_view, cx| {
+            Timer::after(delay).await;
+            terminal_view
+                .update_in(cx, |terminal_view, window, cx| {
+                    if !terminal_view.focus_handle.contains_focused(window, cx) {
+                        cx.emit(ItemEvent::CloseItem);
+                    }
+                })
+                .ok();
+        }));
+    }
+
+    /// Brings the terminal into view without focusing it, in the center or in the terminal panel.
+    fn reveal(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let terminal_view = cx.entity();
+        let workspace = self.workspace.clone();
+        window.defer(cx, move |window, cx| {
+            workspace
+                .update(cx, |workspace, cx| {
+                    if workspace.activate_item(&terminal_view, true, false, window, cx) {
+                        return;
+                    }
+                    let revealed =
+                        workspace
+                            .panel::<TerminalPanel>(cx)
+                            .is_some_and(|terminal_panel| {
+                                terminal_panel.update(cx, |terminal_panel, cx| {
+                                    terminal_panel.reveal_terminal_view(&terminal_view, window, cx)
+                                })
+                            });
+                    if revealed {
+                        workspace.open_panel::<TerminalPanel>(window, cx);
+                    }
+                })
+                .ok();
+        });
     }
 
     // Hack: Using editor in terminal causes cyclic dependency i.e. editor -> terminal -> project -> editor.
@@ -834,6 +951,41 @@ impl TerminalView {
     }
 }
 
+/// Refreshes the tab once the terminal's task completes, so that its status icon
+/// reflects the exit code.
+fn watch_task_completion(terminal: &Entity<Terminal>, cx: &mut Context<TerminalView>) -> Task<()> {
+    let completion = terminal.read(cx).wait_for_completed_task(cx);
+    cx.spawn(async move |terminal_view, cx| {
+        completion.await;
+        terminal_view
+            .update(cx, |_, cx| {
+                cx.emit(ItemEvent::UpdateTab);
+                cx.notify();
+            })
+            .ok();
+    })
+}
+
+fn sample_process_usage(window: &mut Window, cx: &mut Context<TerminalView>) -> Task<()> {
+    cx.spawn_in(window, async move |terminal_view, cx| {
+        loop {
+            cx.background_executor()
+                .timer(PROCESS_USAGE_SAMPLE_INTERVAL)
+                .await;
+            let updated = terminal_view.update_in(cx, |terminal_view, window, cx| {
+                if terminal_view.is_shown && window.is_window_active() {
+                    terminal_view.terminal.update(cx, |terminal, _| {
+                        terminal.sample_process_usage();
+                    });
+                }
+            });
+            if updated.is_err() {
+                break;
+            }
+        }
+    })
+}
+
 fn subscribe_for_terminal_events(
     terminal: &Entity<Terminal>,
     workspace: WeakEntity<Workspace>,
@@ -990,6 +1142,15 @@ fn subscribe_for_terminal_events(
                         })
                         .log_err();
                 }
+                // Failed tasks are kept open, so that their output can be inspected.
+                if let Some(delay) = TerminalSettings::get_global(cx).close_successful_task_after_ms
+                {
+                    if *success {
+                        this.close_after(Duration::from_millis(delay), window, cx);
+                    } else {
+                        this.reveal(window, cx);
+                    }
+                }
             }
         },
     );
@@ -1248,6 +1409,7 @@ impl TerminalView {
 
 impl Render for TerminalView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.is_shown = true;
         let terminal_handle = self.terminal.clone();
         let terminal_view_handle = cx.entity().clone();
 
@@ -1271,6 +1433,8 @@ impl Render for TerminalView {
             .on_action(cx.listener(TerminalView::scroll_to_top))
             .on_action(cx.listener(TerminalView::scroll_to_bottom))
             .on_action(cx.listener(TerminalView::toggle_vi_mode))
+            .on_action(cx.listener(TerminalView::toggle_show_invisibles))
+            .on_action(cx.listener(TerminalView::restart_terminal))
             .on_action(cx.listener(TerminalView::show_character_palette))
             .on_action(cx.listener(TerminalView::select_all))
             .on_key_down(cx.listener(Self::key_down))
@@ -1324,46 +1488,97 @@ impl Render for TerminalView {
 impl Item for TerminalView {
     type Event = ItemEvent;
 
+    fn tab_context_menu_actions(&self, _: &App) -> Vec<(SharedString, Box<dyn Action>)> {
+        let label = if self.show_invisibles {
+            "Hide Invisibles"
+        } else {
+            "Show Invisibles"
+        };
+        vec![(label.into(), Box::new(ToggleShowInvisibles))]
+    }
+
     fn tab_tooltip_content(&self, cx: &App) -> Option<TabTooltipContent> {
         let terminal = self.terminal().read(cx);
         let title = terminal.title(false);
         let pid = terminal.pty_info.pid_getter().fallback_pid();
+        let exit_code = terminal.task().and_then(|task| task.status.exit_code());
+        let process_name = terminal.foreground_process_name().map(SharedString::from);
+        let terminal = self.terminal.clone();
 
         Some(TabTooltipContent::Custom(Box::new(move |_window, cx| {
-            cx.new(|_| TerminalTooltip::new(title.clone(), pid)).into()
+            let process_usage = terminal.read(cx).process_usage();
+            cx.new(|_| {
+                TerminalTooltip::new(title.clone(), pid)
+                    .exit_code(exit_code)
+                    .process(process_name.clone(), process_usage)
+            })
+            .into()
         })))
     }
 
+    fn deactivated(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
+        self.is_shown = false;
+    }
+
+    fn workspace_deactivated(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
+        self.is_shown = false;
+    }
+
     fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
         let terminal = self.terminal().read(cx);
         let title = terminal.title(true);
 
-        let (icon, icon_color, rerun_button) = match terminal.task() {
+        let (icon, rerun_button) = match terminal.task() {
             Some(terminal_task) => match &terminal_task.status {
                 TaskStatus::Running => (
-                    IconName::Play,
-                    Color::Disabled,
+                    Icon::new(IconName::ArrowCircle)
+                        .color(Color::Muted)
+                        .with_animation(
+                            "task-running",
+                            Animation::new(Duration::from_secs(2)).repeat(),
+                            |icon, delta| icon.transform(Transformation::rotate(percentage(delta))),
+                        )
+                        .into_any_element(),
                     TerminalView::rerun_button(&terminal_task),
                 ),
                 TaskStatus::Unknown => (
-                    IconName::Warning,
-                    Color::Warning,
+                    Icon::new(IconName::Warning)
+                        .color(Color::Warning)
+                        .into_any_element(),
                     TerminalView::rerun_button(&terminal_task),
                 ),
-                TaskStatus::Completed { success } => {
+                TaskStatus::Completed { success, .. } => {
                     let rerun_button = TerminalView::rerun_button(&terminal_task);
 
                     if *success {
-                        (IconName::Check, Color::Success, rerun_button)
+                        (
+                            Icon::new(IconName::Check)
+                                .color(Color::Success)
+                                .into_any_element(),
+                            rerun_button,
+                        )
                     } else {
-                        (IconName::XCircle, Color::Error, rerun_button)
+                        (
+                            Icon::new(IconName::XCircle)
+                                .color(Color::Error)
+                                .into_any_element(),
+                            rerun_button,
+                        )
                     }
                 }
             },
-            None if self.terminal.read(cx).debug_terminal() => {
-                (IconName::Debug, Color::Muted, None)
-            }
-            None => (IconName::Terminal, Color::Muted, None),
+            None if self.terminal.read(cx).debug_terminal() => (
+                Icon::new(IconName::Debug)
+                    .color(Color::Muted)
+                    .into_any_element(),
+                None,
+            ),
+            None => (
+                Icon::new(IconName::Terminal)
+                    .color(Color::Muted)
+                    .into_any_element(),
+                None,
+            ),
         };
 
         h_flex()
@@ -1377,7 +1592,7 @@ impl Item for TerminalView {
                             .when(rerun_button.is_some(), |this| {
                                 this.hover(|style| style.invisible().w_0())
                             })
-                            .child(Icon::new(icon).color(icon_color)),
+                            .child(icon),
                     )
                     .when_some(rerun_button, |this, rerun_button| {
                         this.child(
@@ -1412,7 +1627,11 @@ impl Item for TerminalView {
                     .or_else(|| Some(project.active_project_directory(cx)?.to_path_buf()));
                 let python_venv_directory = terminal.python_venv_directory.clone();
                 project.create_terminal_with_venv(
-                    TerminalKind::Shell(working_directory),
+                    TerminalKind::Shell {
+                        working_directory,
+                        profile: None,
+                        env: HashMap::default(),
+                    },
                     python_venv_directory,
                     window_handle,
                     cx,
@@ -1457,7 +1676,11 @@ impl Item for TerminalView {
     }
 
     fn breadcrumb_location(&self, cx: &App) -> ToolbarItemLocation {
-        if self.show_breadcrumbs && !self.terminal().read(cx).breadcrumb_text.trim().is_empty() {
+        let terminal = self.terminal().read(cx);
+        if self.show_breadcrumbs
+            && (terminal.current_directory().is_some()
+                || !terminal.breadcrumb_text.trim().is_empty())
+        {
             ToolbarItemLocation::PrimaryLeft
         } else {
             ToolbarItemLocation::Hidden
@@ -1465,11 +1688,12 @@ impl Item for TerminalView {
     }
 
     fn breadcrumbs(&self, _: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
-        Some(vec![BreadcrumbText {
-            text: self.terminal().read(cx).breadcrumb_text.clone(),
-            highlights: None,
-            font: None,
-        }])
+        let terminal = self.terminal().read(cx);
+        Some(terminal_breadcrumbs(
+            terminal.current_directory().as_deref(),
+            &terminal.breadcrumb_text,
+            &self.project,
+        ))
     }
 
     fn added_to_workspace(
@@ -1527,15 +1751,19 @@ impl SerializableItem for TerminalView {
             return None;
         }
 
-        if let Some((cwd, workspace_id)) = terminal.working_directory().zip(self.workspace_id) {
-            Some(cx.background_spawn(async move {
+        let workspace_id = self.workspace_id?;
+        let cwd = terminal.working_directory();
+        let show_invisibles = self.show_invisibles;
+        Some(cx.background_spawn(async move {
+            if let Some(cwd) = cwd {
                 TERMINAL_DB
                     .save_working_directory(item_id, workspace_id, cwd)
-                    .await
-            }))
-        } else {
-            None
-        }
+                    .await?;
+            }
+            TERMINAL_DB
+                .save_show_invisibles(item_id, workspace_id, show_invisibles)
+                .await
+        }))
     }
 
     fn should_serialize(&self, event: &Self::Event) -> bool {
@@ -1574,19 +1802,34 @@ impl SerializableItem for TerminalView {
 
             let terminal = project
                 .update(cx, |project, cx| {
-                    project.create_terminal(TerminalKind::Shell(cwd), window_handle, cx)
+                    project.create_terminal(
+                        TerminalKind::Shell {
+                            working_directory: cwd,
+                            profile: None,
+                            env: HashMap::default(),
+                        },
+                        window_handle,
+                        cx,
+                    )
                 })?
                 .await?;
+            let show_invisibles = TERMINAL_DB
+                .get_show_invisibles(item_id, workspace_id)
+                .log_err()
+                .flatten()
+                .unwrap_or(false);
             cx.update(|window, cx| {
                 cx.new(|cx| {
-                    TerminalView::new(
+                    let mut view = TerminalView::new(
                         terminal,
                         workspace,
                         Some(workspace_id),
                         project.downgrade(),
                         window,
                         cx,
-                    )
+                    );
+                    view.show_invisibles = show_invisibles;
+                    view
                 })
             })
         })
@@ -1750,6 +1993,79 @@ impl SearchableItem for TerminalView {
     }
 }
 
+/// Returns the breadcrumbs of the terminal's working directory, followed by the title set by
+/// the shell or the program running in it, if any.
+fn terminal_breadcrumbs(
+    directory: Option<&Path>,
+    title: &str,
+    project: &WeakEntity<Project>,
+) -> Vec<BreadcrumbText> {
+    let mut breadcrumbs = directory
+        .map(|directory| directory_breadcrumbs(directory, project))
+        .unwrap_or_default();
+    if breadcrumbs.is_empty() || !title.trim().is_empty() {
+        breadcrumbs.push(BreadcrumbText {
+            text: title.to_string(),
+            highlights: None,
+            font: None,
+            on_click: None,
+        });
+    }
+    breadcrumbs
+}
+
+/// Returns a breadcrumb for each component of `directory`, abbreviating the home directory
+/// to `~`. Clicking a breadcrumb reveals that directory in the project panel.
+fn directory_breadcrumbs(directory: &Path, project: &WeakEntity<Project>) -> Vec<BreadcrumbText> {
+    let home_dir = util::paths::home_dir();
+    let mut segments = Vec::new();
+    let mut path = PathBuf::new();
+    let relative_path = match directory.strip_prefix(home_dir) {
+        Ok(relative_path) => {
+            path = home_dir.clone();
+            segments.push(("~".to_string(), path.clone()));
+            relative_path
+        }
+        Err(_) => directory,
+    };
+    for component in relative_path.components() {
+        path.push(component);
+        segments.push((
+            component.as_os_str().to_string_lossy().into_owned(),
+            path.clone(),
+        ));
+    }
+
+    segments
+        .into_iter()
+        .map(|(text, path)| {
+            let project = project.clone();
+            BreadcrumbText {
+                text,
+                highlights: None,
+                font: None,
+                on_click: Some(Rc::new(move |_: &mut Window, cx: &mut App| {
+                    reveal_in_project_panel(&path, &project, cx)
+                })),
+            }
+        })
+        .collect()
+}
+
+fn reveal_in_project_panel(path: &Path, project: &WeakEntity<Project>, cx: &mut App) {
+    let Some(project) = project.upgrade() else {
+        return;
+    };
+    project.update(cx, |project, cx| {
+        let entry = project
+            .find_project_path(path, cx)
+            .and_then(|project_path| project.entry_for_path(&project_path, cx));
+        if let Some(entry) = entry {
+            cx.emit(project::Event::RevealInProjectPanel(entry.id));
+        }
+    });
+}
+
 ///Gets the working directory for the given workspace, respecting the user's settings.
 /// None implies "~" on whichever machine we end up on.
 pub(crate) fn default_working_directory(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
@@ -1784,7 +2100,7 @@ mod tests {
     use super::*;
     use gpui::TestAppContext;
     use project::{Entry, Project, ProjectPath, Worktree};
-    use std::path::Path;
+    use std::{cell::RefCell, path::Path};
     use workspace::AppState;
 
     // Working directory calculation tests
@@ -1893,6 +2209,54 @@ mod tests {
         });
     }
 
+    // Clicking a directory breadcrumb reveals it in the project panel, if it's in the project
+    #[gpui::test]
+    async fn directory_breadcrumbs_reveal_in_project_panel(cx: &mut TestAppContext) {
+        let (project, _workspace) = init_test(cx).await;
+        let (_wt, entry) = create_folder_wt(project.clone(), "/root1/", cx).await;
+
+        let revealed = Rc::new(RefCell::new(Vec::new()));
+        let _subscription = cx.update(|cx| {
+            let revealed = revealed.clone();
+            cx.subscribe(&project, move |_, event, _| {
+                if let project::Event::RevealInProjectPanel(entry_id) = event {
+                    revealed.borrow_mut().push(*entry_id);
+                }
+            })
+        });
+
+        let breadcrumbs = directory_breadcrumbs(Path::new("/root1"), &project.downgrade());
+        assert_eq!(
+            breadcrumbs
+                .iter()
+                .map(|segment| segment.text.as_str())
+                .collect::<Vec<_>>(),
+            ["/", "root1"]
+        );
+
+        let breadcrumb_texts = |directory: Option<&Path>, title: &str| {
+            terminal_breadcrumbs(directory, title, &project.downgrade())
+                .into_iter()
+                .map(|segment| segment.text)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            breadcrumb_texts(Some(Path::new("/root1")), "vim"),
+            ["/", "root1", "vim"]
+        );
+        assert_eq!(
+            breadcrumb_texts(Some(Path::new("/root1")), ""),
+            ["/", "root1"]
+        );
+        assert_eq!(breadcrumb_texts(None, "vim"), ["vim"]);
+
+        cx.update(|cx| {
+            reveal_in_project_panel(Path::new("/root1"), &project.downgrade(), cx);
+            reveal_in_project_panel(Path::new("/elsewhere"), &project.downgrade(), cx);
+        });
+        assert_eq!(*revealed.borrow(), [entry.id]);
+    }
+
     /// Creates a worktree with 1 file: /root.txt
     pub async fn init_test(cx: &mut TestAppContext) -> (Entity<Project>, Entity<Workspace>) {
         let params = cx.update(AppState::test);
@@ -1,13 +1,20 @@
 use gpui::{
     AnyElement, App, Bounds, Div, DivFrameState, Element, ElementId, GlobalElementId, Hitbox,
     InteractiveElement as _, IntoElement, LayoutId, ParentElement, Pixels, StyleRefinement, Styled,
-    Window, div,
+    Window, div, px,
 };
 
+/// The smallest rem size a scaled [WithRemSize] resolves to, so text never collapses.
+const MIN_SCALED_REM_SIZE: Pixels = px(4.);
+
 /// An element that sets a particular rem size for its children.
 pub struct WithRemSize {
     div: Div,
     rem_size: Pixels,
+    /// When set, `rem_size` is resolved from the inherited rem size during layout.
+    scale: Option<f32>,
+    min_rem_size: Option<Pixels>,
+    max_rem_size: Option<Pixels>,
 }
 
 impl WithRemSize {
@@ -17,9 +24,38 @@ impl WithRemSize {
         Self {
             div: div(),
             rem_size: rem_size.into(),
+            scale: None,
+            min_rem_size: None,
+            max_rem_size: None,
         }
     }
 
+    /// Create a new [WithRemSize] element, which sets the rem size of its
+    /// children to `factor` times the rem size it inherits.
+    pub fn scaled(factor: f32) -> Self {
+        Self {
+            div: div(),
+            rem_size: MIN_SCALED_REM_SIZE,
+            scale: Some(factor),
+            min_rem_size: None,
+            max_rem_size: None,
+        }
+    }
+
+    /// Sets the smallest rem size the children will get, after any scaling.
+    pub fn min_rem(mut self, min_rem_size: impl Into<Pixels>) -> Self {
+        self.min_rem_size = Some(min_rem_size.into());
+        self
+    }
+
+    /// Sets the largest rem size the children will get, after any scaling.
+    ///
+    /// Takes precedence over [`Self::min_rem`] if the two conflict.
+    pub fn max_rem(mut self, max_rem_size: impl Into<Pixels>) -> Self {
+        self.max_rem_size = Some(max_rem_size.into());
+        self
+    }
+
     /// Block the mouse from interacting with this element or any of its children
     /// The fluent API equivalent to [`Interactivity::occlude_mouse`]
     ///
@@ -56,6 +92,15 @@ impl Element for WithRemSize {
         window: &mut Window,
         cx: &mut App,
     ) -> (LayoutId, Self::RequestLayoutState) {
+        if let Some(scale) = self.scale {
+            self.rem_size = (window.rem_size() * scale).max(MIN_SCALED_REM_SIZE);
+        }
+        if let Some(min_rem_size) = self.min_rem_size {
+            self.rem_size = self.rem_size.max(min_rem_size);
+        }
+        if let Some(max_rem_size) = self.max_rem_size {
+            self.rem_size = self.rem_size.min(max_rem_size);
+        }
         window.with_rem_size(Some(self.rem_size), |window| {
             self.div.request_layout(id, window, cx)
         })
@@ -97,3 +142,56 @@ impl IntoElement for WithRemSize {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use gpui::{TestAppContext, VisualTestContext, canvas, point, size};
+
+    use super::*;
+
+    fn child_rem_size(element: WithRemSize, cx: &mut VisualTestContext) -> Pixels {
+        let rem_size = Rc::new(Cell::new(px(0.)));
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_, _| {
+            let rem_size = rem_size.clone();
+            element.child(
+                canvas(
+                    move |_, window, _| rem_size.set(window.rem_size()),
+                    |_, _, _, _| {},
+                )
+                .size_full(),
+            )
+        });
+        rem_size.get()
+    }
+
+    #[gpui::test]
+    fn test_rem_size_clamp(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+
+        assert_eq!(
+            child_rem_size(WithRemSize::new(px(40.)).max_rem(px(24.)), cx),
+            px(24.)
+        );
+        assert_eq!(
+            child_rem_size(WithRemSize::new(px(2.)).min_rem(px(8.)), cx),
+            px(8.)
+        );
+        assert_eq!(
+            child_rem_size(
+                WithRemSize::new(px(12.)).min_rem(px(8.)).max_rem(px(24.)),
+                cx
+            ),
+            px(12.)
+        );
+
+        // Scaling applies to the inherited rem size before clamping.
+        let inherited_rem_size = cx.update(|window, _| window.rem_size());
+        assert_eq!(
+            child_rem_size(WithRemSize::scaled(4.).max_rem(inherited_rem_size * 2.), cx),
+            inherited_rem_size * 2.
+        );
+    }
+}
@@ -1449,6 +1449,7 @@ impl ShellExec {
                         show_summary: false,
                         show_command: false,
                         show_rerun: false,
+                        interactive: true,
                     }),
                 });
             });
@@ -1,22 +1,51 @@
 use crate::persistence::model::DockData;
 use crate::{DraggedDock, Event, ModalLayer, Pane};
-use crate::{Workspace, status_bar::StatusItemView};
+use crate::{Workspace, WorkspaceSettings, status_bar::StatusItemView};
 use anyhow::Context as _;
 use client::proto;
 use gpui::{
-    Action, AnyView, App, Axis, Context, Corner, Entity, EntityId, EventEmitter, FocusHandle,
-    Focusable, IntoElement, KeyContext, MouseButton, MouseDownEvent, MouseUpEvent, ParentElement,
-    Render, SharedString, StyleRefinement, Styled, Subscription, WeakEntity, Window, deferred, div,
-    px,
+    Action, Animation, AnimationExt as _, AnyView, App, Axis, Context, Corner, DragMoveEvent,
+    Entity, EntityId, EventEmitter, FocusHandle, Focusable, IntoElement, KeyContext, MouseButton,
+    MouseDownEvent, MouseUpEvent, ParentElement, Render, SharedString, StyleRefinement, Styled,
+    Subscription, Task, WeakEntity, Window, deferred, div, px, relative,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use settings::SettingsStore;
-use std::sync::Arc;
-use ui::{ContextMenu, Divider, DividerColor, IconButton, Tooltip, h_flex};
+use settings::{Settings as _, SettingsStore};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use ui::{ContextMenu, Divider, DividerColor, IconButton, Indicator, Tooltip, h_flex};
 use ui::{prelude::*, right_click_menu};
+use util::ResultExt as _;
 
 pub(crate) const RESIZE_HANDLE_SIZE: Pixels = Pixels(6.);
+const MIN_SPLIT_RATIO: f32 = 0.1;
+
+#[derive(Clone)]
+struct DraggedDockSplit;
+
+impl Render for DraggedDockSplit {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        gpui::Empty
+    }
+}
+
+/// A panel button being dragged to reorder the panels of its dock.
+#[derive(Clone)]
+struct DraggedPanelButton {
+    dock_id: EntityId,
+    panel_id: EntityId,
+    icon: IconName,
+}
+
+impl Render for DraggedPanelButton {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .p_1()
+            .rounded_sm()
+            .bg(cx.theme().colors().element_selected)
+            .child(Icon::new(self.icon).size(IconSize::Small))
+    }
+}
 
 pub enum PanelEvent {
     ZoomIn,
@@ -25,21 +54,87 @@ pub enum PanelEvent {
     Close,
 }
 
+/// Emitted by a [`Dock`] as panels are added, removed, activated or reordered,
+/// so that other UI can follow the dock without observing each of its panels.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DockEvent {
+    /// A panel was added to the dock.
+    PanelAdded {
+        persistent_name: &'static str,
+        panel_id: EntityId,
+    },
+    /// A panel was removed from the dock, e.g. because it moved to another dock.
+    PanelRemoved {
+        persistent_name: &'static str,
+        panel_id: EntityId,
+    },
+    /// A panel became the active panel of the dock.
+    PanelActivated {
+        persistent_name: &'static str,
+        panel_id: EntityId,
+    },
+    /// A panel was moved to another position in the dock, given by `index`.
+    PanelMoved {
+        persistent_name: &'static str,
+        panel_id: EntityId,
+        index: usize,
+    },
+}
+
 pub use proto::PanelId;
 
+/// A small marker shown over a panel's button, e.g. to signal unread notifications.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PanelBadge {
+    /// The number shown next to the dot, if any.
+    pub count: Option<usize>,
+    pub color: Color,
+}
+
+impl PanelBadge {
+    pub fn dot(color: Color) -> Self {
+        Self { count: None, color }
+    }
+
+    pub fn count(count: usize, color: Color) -> Self {
+        Self {
+            count: Some(count),
+            color,
+        }
+    }
+}
+
 pub trait Panel: Focusable + EventEmitter<PanelEvent> + Render + Sized {
     fn persistent_name() -> &'static str;
     fn position(&self, window: &Window, cx: &App) -> DockPosition;
+    /// The dock the panel is added to in a workspace whose docks haven't recorded it yet,
+    /// e.g. on first run. Afterwards, the panel follows its [`Panel::position`].
+    fn default_position(&self, window: &Window, cx: &App) -> DockPosition {
+        self.position(window, cx)
+    }
+    /// Whether [`Panel::position`] was chosen by the user, e.g. in their settings. A chosen
+    /// position takes precedence over both the [`Panel::default_position`] and the dock the
+    /// panel was serialized in.
+    fn has_chosen_position(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
     fn position_is_valid(&self, position: DockPosition) -> bool;
     fn set_position(&mut self, position: DockPosition, window: &mut Window, cx: &mut Context<Self>);
     fn size(&self, window: &Window, cx: &App) -> Pixels;
     fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>);
+    /// The smallest size the panel can be resized to.
+    fn min_size(&self, _cx: &App) -> Pixels {
+        RESIZE_HANDLE_SIZE
+    }
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName>;
     fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<&'static str>;
     fn toggle_action(&self) -> Box<dyn Action>;
     fn icon_label(&self, _window: &Window, _: &App) -> Option<String> {
         None
     }
+    fn badge(&self, _window: &Window, _cx: &App) -> Option<PanelBadge> {
+        None
+    }
     fn is_zoomed(&self, _window: &Window, _cx: &App) -> bool {
         false
     }
@@ -48,6 +143,12 @@ pub trait Panel: Focusable + EventEmitter<PanelEvent> + Render + Sized {
     }
     fn set_zoomed(&mut self, _zoomed: bool, _window: &mut Window, _cx: &mut Context<Self>) {}
     fn set_active(&mut self, _active: bool, _window: &mut Window, _cx: &mut Context<Self>) {}
+    /// Called when the dock hides this panel or removes it, so the panel can
+    /// pause background work or flush state.
+    fn on_close(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {}
+    /// Called when the theme setting changes, so the panel can recompute any colors
+    /// it caches.
+    fn on_workspace_theme_changed(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {}
     fn pane(&self) -> Option<Entity<Pane>> {
         None
     }
@@ -64,19 +165,24 @@ pub trait PanelHandle: Send + Sync {
     fn panel_id(&self) -> EntityId;
     fn persistent_name(&self) -> &'static str;
     fn position(&self, window: &Window, cx: &App) -> DockPosition;
+    fn default_position(&self, window: &Window, cx: &App) -> DockPosition;
     fn position_is_valid(&self, position: DockPosition, cx: &App) -> bool;
     fn set_position(&self, position: DockPosition, window: &mut Window, cx: &mut App);
     fn is_zoomed(&self, window: &Window, cx: &App) -> bool;
     fn set_zoomed(&self, zoomed: bool, window: &mut Window, cx: &mut App);
     fn set_active(&self, active: bool, window: &mut Window, cx: &mut App);
+    fn on_close(&self, window: &mut Window, cx: &mut App);
+    fn on_workspace_theme_changed(&self, window: &mut Window, cx: &mut App);
     fn remote_id(&self) -> Option<proto::PanelId>;
     fn pane(&self, cx: &App) -> Option<Entity<Pane>>;
     fn size(&self, window: &Window, cx: &App) -> Pixels;
     fn set_size(&self, size: Option<Pixels>, window: &mut Window, cx: &mut App);
+    fn min_size(&self, cx: &App) -> Pixels;
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName>;
     fn icon_tooltip(&self, window: &Window, cx: &App) -> Option<&'static str>;
     fn toggle_action(&self, window: &Window, cx: &App) -> Box<dyn Action>;
     fn icon_label(&self, window: &Window, cx: &App) -> Option<String>;
+    fn badge(&self, window: &Window, cx: &App) -> Option<PanelBadge>;
     fn panel_focus_handle(&self, cx: &App) -> FocusHandle;
     fn to_any(&self) -> AnyView;
     fn activation_priority(&self, cx: &App) -> u32;
@@ -114,6 +220,10 @@ where
         self.read(cx).position(window, cx)
     }
 
+    fn default_position(&self, window: &Window, cx: &App) -> DockPosition {
+        self.read(cx).default_position(window, cx)
+    }
+
     fn position_is_valid(&self, position: DockPosition, cx: &App) -> bool {
         self.read(cx).position_is_valid(position)
     }
@@ -134,6 +244,14 @@ where
         self.update(cx, |this, cx| this.set_active(active, window, cx))
     }
 
+    fn on_close(&self, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| this.on_close(window, cx))
+    }
+
+    fn on_workspace_theme_changed(&self, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| this.on_workspace_theme_changed(window, cx))
+    }
+
     fn pane(&self, cx: &App) -> Option<Entity<Pane>> {
         self.read(cx).pane()
     }
@@ -150,6 +268,10 @@ where
         self.update(cx, |this, cx| this.set_size(size, window, cx))
     }
 
+    fn min_size(&self, cx: &App) -> Pixels {
+        self.read(cx).min_size(cx)
+    }
+
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName> {
         self.read(cx).icon(window, cx)
     }
@@ -166,6 +288,10 @@ where
         self.read(cx).icon_label(window, cx)
     }
 
+    fn badge(&self, window: &Window, cx: &App) -> Option<PanelBadge> {
+        self.read(cx).badge(window, cx)
+    }
+
     fn to_any(&self) -> AnyView {
         self.clone().into()
     }
@@ -197,11 +323,24 @@ pub struct Dock {
     workspace: WeakEntity<Workspace>,
     is_open: bool,
     active_panel_index: Option<usize>,
+    /// The panel shown alongside the active one, if the dock is split, and the
+    /// fraction of the dock taken up by the active panel.
+    split_panels: Option<(usize, f32)>,
+    /// The position of each panel the user reordered, by persistent name. Takes
+    /// precedence over the panels' activation priority.
+    panel_order: BTreeMap<String, usize>,
+    /// The duration of the current open or close transition, if the dock is animating
+    /// it, and the task ending it.
+    transition: Option<(Duration, Task<()>)>,
+    /// The number of transitions so far, so that each one restarts the animation.
+    transition_count: usize,
+    /// Whether the dock closes when focus leaves it.
+    auto_hide: bool,
     focus_handle: FocusHandle,
     pub(crate) serialized_dock: Option<DockData>,
     zoom_layer_open: bool,
     modal_layer: Entity<ModalLayer>,
-    _subscriptions: [Subscription; 2],
+    _subscriptions: [Subscription; 3],
 }
 
 impl Focusable for Dock {
@@ -237,6 +376,10 @@ impl DockPosition {
 
 struct PanelEntry {
     panel: Arc<dyn PanelHandle>,
+    /// Pinned panels keep their dock open when it gets toggled.
+    pinned: bool,
+    /// The size this panel was given in this workspace, if it has been resized.
+    size: Option<Pixels>,
     _subscriptions: [Subscription; 3],
 }
 
@@ -244,6 +387,8 @@ pub struct PanelButtons {
     dock: Entity<Dock>,
 }
 
+impl EventEmitter<DockEvent> for Dock {}
+
 impl Dock {
     pub fn new(
         position: DockPosition,
@@ -260,6 +405,37 @@ impl Dock {
                         active_entry.panel.panel_focus_handle(cx).focus(window)
                     }
                 });
+            let focus_out_subscription =
+                cx.on_focus_out(&focus_handle, window, |dock: &mut Dock, _, window, cx| {
+                    if !dock.auto_hide || !dock.is_open {
+                        return;
+                    }
+                    // Only hide when focus moves to the center, so that e.g. modals and the
+                    // dock's own context menus don't close it.
+                    let focused_center = dock.workspace.upgrade().is_some_and(|workspace| {
+                        workspace
+                            .read(cx)
+                            .center
+                            .panes()
+                            .into_iter()
+                            .any(|pane| pane.focus_handle(cx).contains_focused(window, cx))
+                    });
+                    if !focused_center {
+                        return;
+                    }
+                    // Zoomed panels are shown outside of the dock, so they keep it open.
+                    let keep_open = dock
+                        .active_panel_entry()
+                        .is_some_and(|entry| entry.pinned || entry.panel.is_zoomed(window, cx));
+                    if !keep_open {
+                        dock.set_open(false, window, cx);
+                        dock.workspace
+                            .update(cx, |workspace, cx| {
+                                workspace.serialize_workspace(window, cx);
+                            })
+                            .ok();
+                    }
+                });
             let zoom_subscription = cx.subscribe(&workspace, |dock, workspace, e: &Event, cx| {
                 if matches!(e, Event::ZoomChanged) {
                     let is_zoomed = workspace.read(cx).zoomed.is_some();
@@ -271,9 +447,18 @@ impl Dock {
                 workspace: workspace.downgrade(),
                 panel_entries: Default::default(),
                 active_panel_index: None,
+                split_panels: None,
+                panel_order: BTreeMap::new(),
+                transition: None,
+                transition_count: 0,
+                auto_hide: false,
                 is_open: false,
                 focus_handle: focus_handle.clone(),
-                _subscriptions: [focus_subscription, zoom_subscription],
+                _subscriptions: [
+                    focus_subscription,
+                    focus_out_subscription,
+                    zoom_subscription,
+                ],
                 serialized_dock: None,
                 zoom_layer_open: false,
                 modal_layer,
@@ -385,14 +570,263 @@ impl Dock {
     pub fn set_open(&mut self, open: bool, window: &mut Window, cx: &mut Context<Self>) {
         if open != self.is_open {
             self.is_open = open;
-            if let Some(active_panel) = self.active_panel_entry() {
-                active_panel.panel.set_active(open, window, cx);
+            self.start_transition(cx);
+            for entry in [self.active_panel_entry(), self.split_panel_entry()]
+                .into_iter()
+                .flatten()
+            {
+                entry.panel.set_active(open, window, cx);
+                if !open {
+                    entry.panel.on_close(window, cx);
+                }
+            }
+
+            cx.notify();
+        }
+    }
+
+    /// Animates the dock opening or closing, unless the user turned animations off.
+    fn start_transition(&mut self, cx: &mut Context<Self>) {
+        let settings = WorkspaceSettings::get_global(cx);
+        if settings.reduce_motion || settings.dock_animation_duration_ms == 0 {
+            self.transition = None;
+            return;
+        }
+
+        let duration = Duration::from_millis(settings.dock_animation_duration_ms);
+        self.transition_count += 1;
+        let end_transition = cx.spawn(async move |dock, cx| {
+            cx.background_executor().timer(duration).await;
+            dock.update(cx, |dock, cx| {
+                dock.transition = None;
+                cx.notify();
+            })
+            .ok();
+        });
+        self.transition = Some((duration, end_transition));
+    }
+
+    pub fn auto_hide(&self) -> bool {
+        self.auto_hide
+    }
+
+    /// Sets whether the dock closes when focus leaves it, unless its active
+    /// panel is pinned or zoomed.
+    pub fn set_auto_hide(&mut self, auto_hide: bool, cx: &mut Context<Self>) {
+        if self.auto_hide != auto_hide {
+            self.auto_hide = auto_hide;
+            cx.notify();
+        }
+    }
+
+    pub fn is_active_panel_pinned(&self) -> bool {
+        self.active_panel_entry()
+            .map_or(false, |entry| entry.pinned)
+    }
+
+    pub fn is_panel_pinned(&self, panel_id: EntityId) -> bool {
+        self.panel_entries
+            .iter()
+            .any(|entry| entry.pinned && entry.panel.panel_id() == panel_id)
+    }
+
+    pub fn set_panel_pinned(&mut self, panel_id: EntityId, pinned: bool, cx: &mut Context<Self>) {
+        if let Some(entry) = self
+            .panel_entries
+            .iter_mut()
+            .find(|entry| entry.panel.panel_id() == panel_id)
+        {
+            if entry.pinned != pinned {
+                entry.pinned = pinned;
+                cx.notify();
             }
+        }
+    }
+
+    /// The panel shown alongside the active one when the dock is split.
+    pub fn split_panel(&self) -> Option<&Arc<dyn PanelHandle>> {
+        Some(&self.split_panel_entry()?.panel)
+    }
+
+    pub fn split_ratio(&self) -> Option<f32> {
+        self.split_panels.map(|(_, ratio)| ratio)
+    }
+
+    fn split_panel_entry(&self) -> Option<&PanelEntry> {
+        self.split_panels
+            .and_then(|(index, _)| self.panel_entries.get(index))
+    }
 
+    /// Splits the dock so the panel at `panel_ix` is shown alongside the active panel.
+    ///
+    /// Panels are stacked vertically in the side docks and side by side in the bottom dock.
+    pub fn split_with_panel(
+        &mut self,
+        panel_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .active_panel_index
+            .is_none_or(|index| index == panel_ix)
+            || panel_ix >= self.panel_entries.len()
+            || self.split_panels.map(|(index, _)| index) == Some(panel_ix)
+        {
+            return;
+        }
+
+        self.close_split(window, cx);
+        self.split_panels = Some((panel_ix, 0.5));
+        if self.is_open {
+            self.panel_entries[panel_ix]
+                .panel
+                .set_active(true, window, cx);
+        }
+        cx.notify();
+    }
+
+    /// Stops showing a second panel in this dock.
+    pub fn close_split(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry) = self.split_panel_entry() {
+            if self.is_open {
+                entry.panel.set_active(false, window, cx);
+                entry.panel.on_close(window, cx);
+            }
+        }
+        if self.split_panels.take().is_some() {
             cx.notify();
         }
     }
 
+    pub fn set_split_ratio(&mut self, ratio: f32, cx: &mut Context<Self>) {
+        if let Some((_, split_ratio)) = self.split_panels.as_mut() {
+            *split_ratio = ratio.clamp(MIN_SPLIT_RATIO, 1. - MIN_SPLIT_RATIO);
+            cx.notify();
+        }
+    }
+
+    /// The sizes of the panels resized in this dock, by persistent name.
+    pub(crate) fn panel_sizes(&self) -> BTreeMap<String, f32> {
+        self.panel_entries
+            .iter()
+            .filter_map(|entry| {
+                let size = entry.size?;
+                Some((entry.panel.persistent_name().to_string(), f32::from(size)))
+            })
+            .collect()
+    }
+
+    /// The persistent names of the panels the user reordered, in their custom order.
+    pub(crate) fn panel_order(&self) -> Vec<String> {
+        let mut panel_order = self.panel_order.iter().collect::<Vec<_>>();
+        panel_order.sort_by_key(|(_, position)| **position);
+        panel_order
+            .into_iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Swaps the positions of two panels, remembering the resulting order.
+    pub fn swap_panels(&mut self, ix_a: usize, ix_b: usize, cx: &mut Context<Self>) {
+        if ix_a == ix_b || ix_a >= self.panel_entries.len() || ix_b >= self.panel_entries.len() {
+            return;
+        }
+
+        self.panel_entries.swap(ix_a, ix_b);
+        let swap_index = |index: &mut usize| {
+            if *index == ix_a {
+                *index = ix_b;
+            } else if *index == ix_b {
+                *index = ix_a;
+            }
+        };
+        if let Some(active_index) = self.active_panel_index.as_mut() {
+            swap_index(active_index);
+        }
+        if let Some((split_index, _)) = self.split_panels.as_mut() {
+            swap_index(split_index);
+        }
+
+        // Keep the positions of reordered panels that aren't in this dock right now,
+        // e.g. ones that haven't been loaded yet, after the panels that are.
+        let mut panel_order = self
+            .panel_entries
+            .iter()
+            .map(|entry| entry.panel.persistent_name().to_string())
+            .collect::<Vec<_>>();
+        for name in self.panel_order() {
+            if !panel_order.contains(&name) {
+                panel_order.push(name);
+            }
+        }
+        self.set_panel_order(panel_order);
+        for index in [ix_a, ix_b] {
+            let panel = &self.panel_entries[index].panel;
+            cx.emit(DockEvent::PanelMoved {
+                persistent_name: panel.persistent_name(),
+                panel_id: panel.panel_id(),
+                index,
+            });
+        }
+        cx.notify();
+    }
+
+    fn set_panel_order(&mut self, panel_order: Vec<String>) {
+        self.panel_order = panel_order
+            .into_iter()
+            .enumerate()
+            .map(|(position, name)| (name, position))
+            .collect();
+    }
+
+    /// Sorts the panels by their custom position, falling back to their activation priority.
+    fn sort_panel_entries(&mut self, cx: &App) {
+        let active_panel_id = self
+            .active_panel_entry()
+            .map(|entry| entry.panel.panel_id());
+        let split_panel = self
+            .split_panels
+            .zip(self.split_panel_entry())
+            .map(|((_, ratio), entry)| (entry.panel.panel_id(), ratio));
+
+        let panel_order = &self.panel_order;
+        self.panel_entries.sort_by_cached_key(|entry| {
+            panel_sort_key(
+                panel_order,
+                entry.panel.persistent_name(),
+                entry.panel.activation_priority(cx),
+            )
+        });
+
+        let index_of = |panel_id: EntityId| {
+            self.panel_entries
+                .iter()
+                .position(|entry| entry.panel.panel_id() == panel_id)
+        };
+        self.active_panel_index = active_panel_id.and_then(index_of);
+        self.split_panels = split_panel
+            .and_then(|(panel_id, ratio)| index_of(panel_id).map(|index| (index, ratio)));
+    }
+
+    /// Whether the dock's serialized state, if any, records the panel with the given name as
+    /// being in the dock. Only the active panel and the panel sizes are considered, as the
+    /// panel order also keeps the names of panels that have left the dock.
+    pub(crate) fn has_serialized_panel(&self, persistent_name: &str) -> bool {
+        self.serialized_dock.as_ref().is_some_and(|serialized| {
+            serialized.active_panel.as_deref() == Some(persistent_name)
+                || serialized.panel_sizes.contains_key(persistent_name)
+        })
+    }
+
+    /// The persistent names of the pinned panels in this dock.
+    pub(crate) fn pinned_panels(&self) -> Vec<String> {
+        self.panel_entries
+            .iter()
+            .filter(|entry| entry.pinned)
+            .map(|entry| entry.panel.persistent_name().to_string())
+            .collect()
+    }
+
     pub fn set_panel_zoomed(
         &mut self,
         panel: &AnyView,
@@ -438,9 +872,24 @@ impl Dock {
             cx.observe_global_in::<SettingsStore>(window, {
                 let workspace = workspace.clone();
                 let panel = panel.clone();
+                // Only follow changes of the panel's position, so that a panel placed at its
+                // default position stays there until it's moved.
+                let mut last_position = panel.read(cx).position(window, cx);
+                // Themes are compared by identity, as reloading a theme or overriding its
+                // styles keeps its name.
+                let mut last_theme = cx.theme().clone();
 
                 move |this, window, cx| {
+                    if !Arc::ptr_eq(cx.theme(), &last_theme) {
+                        last_theme = cx.theme().clone();
+                        panel.on_workspace_theme_changed(window, cx);
+                    }
+
                     let new_position = panel.read(cx).position(window, cx);
+                    if new_position == last_position {
+                        return;
+                    }
+                    last_position = new_position;
                     if new_position == this.position {
                         return;
                     }
@@ -532,11 +981,18 @@ impl Dock {
             ),
         ];
 
-        let index = match self
-            .panel_entries
-            .binary_search_by_key(&panel.read(cx).activation_priority(), |entry| {
-                entry.panel.activation_priority(cx)
-            }) {
+        let sort_key = panel_sort_key(
+            &self.panel_order,
+            T::persistent_name(),
+            panel.read(cx).activation_priority(),
+        );
+        let index = match self.panel_entries.binary_search_by_key(&sort_key, |entry| {
+            panel_sort_key(
+                &self.panel_order,
+                entry.panel.persistent_name(),
+                entry.panel.activation_priority(cx),
+            )
+        }) {
             Ok(ix) => ix,
             Err(ix) => ix,
         };
@@ -545,13 +1001,24 @@ impl Dock {
                 *active_index += 1;
             }
         }
+        if let Some((split_index, _)) = self.split_panels.as_mut() {
+            if *split_index >= index {
+                *split_index += 1;
+            }
+        }
         self.panel_entries.insert(
             index,
             PanelEntry {
                 panel: Arc::new(panel.clone()),
+                pinned: false,
+                size: None,
                 _subscriptions: subscriptions,
             },
         );
+        cx.emit(DockEvent::PanelAdded {
+            persistent_name: T::persistent_name(),
+            panel_id: Entity::entity_id(&panel),
+        });
 
         self.restore_state(window, cx);
         if panel.read(cx).starts_open(window, cx) {
@@ -565,9 +1032,33 @@ impl Dock {
 
     pub fn restore_state(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
         if let Some(serialized) = self.serialized_dock.clone() {
-            if let Some(active_panel) = serialized.active_panel.filter(|_| serialized.visible) {
+            // Panels get restored whenever one is added, so a custom order from
+            // the current session takes precedence over the serialized one.
+            if self.panel_order.is_empty() && !serialized.panel_order.is_empty() {
+                self.set_panel_order(serialized.panel_order.clone());
+                self.sort_panel_entries(cx);
+            }
+
+            if let Some(active_panel) = serialized.active_panel {
                 if let Some(idx) = self.panel_index_for_persistent_name(active_panel.as_str(), cx) {
-                    self.activate_panel(idx, window, cx);
+                    if serialized.visible {
+                        self.activate_panel(idx, window, cx);
+                    } else if self.active_panel_index.is_none() {
+                        // A hidden dock remembers the panel it shows when opened, and the
+                        // split next to it, without activating the panel yet.
+                        self.active_panel_index = Some(idx);
+                    }
+                }
+            }
+
+            if let Some(split_panel) = serialized.split_panel {
+                if let Some(idx) = self.panel_index_for_persistent_name(split_panel.as_str(), cx) {
+                    if self.split_panels.map(|(index, _)| index) != Some(idx) {
+                        self.split_with_panel(idx, window, cx);
+                        if let Some(ratio) = serialized.split_ratio {
+                            self.set_split_ratio(ratio, cx);
+                        }
+                    }
                 }
             }
 
@@ -576,6 +1067,18 @@ impl Dock {
                     panel.set_zoomed(true, window, cx)
                 }
             }
+            for entry in &mut self.panel_entries {
+                entry.pinned = serialized
+                    .pinned_panels
+                    .iter()
+                    .any(|name| name == entry.panel.persistent_name());
+                if let Some(size) = serialized.panel_sizes.get(entry.panel.persistent_name()) {
+                    let size = px(*size);
+                    entry.size = Some(size);
+                    entry.panel.set_size(Some(size), window, cx);
+                }
+            }
+            self.auto_hide = serialized.auto_hide;
             self.set_open(serialized.visible, window, cx);
             return true;
         }
@@ -593,6 +1096,17 @@ impl Dock {
             .iter()
             .position(|entry| entry.panel.panel_id() == Entity::entity_id(panel))
         {
+            if let Some((split_index, ratio)) = self.split_panels {
+                match panel_ix.cmp(&split_index) {
+                    std::cmp::Ordering::Less => {
+                        self.split_panels = Some((split_index - 1, ratio));
+                    }
+                    std::cmp::Ordering::Equal => {
+                        self.split_panels = None;
+                    }
+                    std::cmp::Ordering::Greater => {}
+                }
+            }
             if let Some(active_panel_index) = self.active_panel_index.as_mut() {
                 match panel_ix.cmp(active_panel_index) {
                     std::cmp::Ordering::Less => {
@@ -605,7 +1119,12 @@ impl Dock {
                     std::cmp::Ordering::Greater => {}
                 }
             }
-            self.panel_entries.remove(panel_ix);
+            let entry = self.panel_entries.remove(panel_ix);
+            entry.panel.on_close(window, cx);
+            cx.emit(DockEvent::PanelRemoved {
+                persistent_name: T::persistent_name(),
+                panel_id: Entity::entity_id(panel),
+            });
             cx.notify();
         }
     }
@@ -616,6 +1135,18 @@ impl Dock {
 
     pub fn activate_panel(&mut self, panel_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
         if Some(panel_ix) != self.active_panel_index {
+            if let Some((_, ratio)) = self
+                .split_panels
+                .filter(|(split_index, _)| *split_index == panel_ix)
+            {
+                // Activating the split panel swaps it with the active one, keeping both visible.
+                self.split_panels = self.active_panel_index.map(|index| (index, 1. - ratio));
+                self.active_panel_index = Some(panel_ix);
+                self.emit_panel_activated(cx);
+                cx.notify();
+                return;
+            }
+
             if let Some(active_panel) = self.active_panel_entry() {
                 active_panel.panel.set_active(false, window, cx);
             }
@@ -625,10 +1156,55 @@ impl Dock {
                 active_panel.panel.set_active(true, window, cx);
             }
 
+            self.emit_panel_activated(cx);
             cx.notify();
         }
     }
 
+    /// Activates the next enabled panel, wrapping around to the first one, and focuses it.
+    ///
+    /// A closed dock is opened on its current active panel instead of advancing.
+    pub fn toggle_panel_cycle(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.panel_entries.is_empty() {
+            return;
+        }
+
+        if self.is_open {
+            let len = self.panel_entries.len();
+            let current = self.active_panel_index.unwrap_or(len - 1);
+            let next = (1..=len)
+                .map(|offset| (current + offset) % len)
+                .find(|&ix| self.panel_entries[ix].panel.enabled(cx));
+            if let Some(next) = next {
+                self.activate_panel(next, window, cx);
+            }
+        } else {
+            if self.active_panel_index.is_none() {
+                let Some(panel_ix) = self
+                    .first_enabled_panel_idx(cx)
+                    .log_with_level(log::Level::Info)
+                else {
+                    return;
+                };
+                self.activate_panel(panel_ix, window, cx);
+            }
+            self.set_open(true, window, cx);
+        }
+
+        if let Some(panel) = self.active_panel() {
+            window.focus(&panel.panel_focus_handle(cx));
+        }
+    }
+
+    fn emit_panel_activated(&self, cx: &mut Context<Self>) {
+        if let Some(entry) = self.active_panel_entry() {
+            cx.emit(DockEvent::PanelActivated {
+                persistent_name: entry.panel.persistent_name(),
+                panel_id: entry.panel.panel_id(),
+            });
+        }
+    }
+
     pub fn visible_panel(&self) -> Option<&Arc<dyn PanelHandle>> {
         let entry = self.visible_entry()?;
         Some(&entry.panel)
@@ -678,9 +1254,14 @@ impl Dock {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(entry) = self.active_panel_entry() {
-            let size = size.map(|size| size.max(RESIZE_HANDLE_SIZE).round());
+        if let Some(entry) = self
+            .active_panel_index
+            .and_then(|index| self.panel_entries.get_mut(index))
+        {
+            let min_size = entry.panel.min_size(cx);
+            let size = size.map(|size| size.max(min_size).round());
 
+            entry.size = size;
             entry.panel.set_size(size, window, cx);
             cx.notify();
         }
@@ -704,8 +1285,12 @@ impl Dock {
     pub fn clamp_panel_size(&mut self, max_size: Pixels, window: &mut Window, cx: &mut App) {
         let max_size = px((max_size.0 - RESIZE_HANDLE_SIZE.0).abs());
         for panel in self.panel_entries.iter().map(|entry| &entry.panel) {
-            if panel.size(window, cx) > max_size {
-                panel.set_size(Some(max_size.max(RESIZE_HANDLE_SIZE)), window, cx);
+            let size = panel.size(window, cx);
+            let min_size = panel.min_size(cx);
+            if size > max_size {
+                panel.set_size(Some(max_size.max(min_size)), window, cx);
+            } else if size < min_size {
+                panel.set_size(Some(min_size), window, cx);
             }
         }
     }
@@ -714,8 +1299,18 @@ impl Dock {
 impl Render for Dock {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let dispatch_context = Self::dispatch_context();
-        if let Some(entry) = self.visible_entry() {
+        let transition_duration = self.transition.as_ref().map(|(duration, _)| *duration);
+        // A closing dock keeps showing its panel until the transition ends.
+        let entry = self.visible_entry().or_else(|| {
+            transition_duration
+                .is_some()
+                .then(|| self.active_panel_entry())
+                .flatten()
+        });
+        if let Some(entry) = entry {
             let size = entry.panel.size(window, cx);
+            // Resizing mid-transition would fight the animation.
+            let resizable = self.resizable(cx) && transition_duration.is_none();
 
             let position = self.position;
             let create_resize_handle = || {
@@ -777,14 +1372,122 @@ impl Render for Dock {
                 }
             };
 
-            div()
+            let panel_content = |entry: &PanelEntry| {
+                entry
+                    .panel
+                    .to_any()
+                    .cached(StyleRefinement::default().v_flex().size_full())
+            };
+            let content = if let Some((split_entry, ratio)) =
+                self.split_panel_entry().zip(self.split_ratio())
+            {
+                let axis = self.position().axis();
+                let split_handle = div()
+                    .id("split-resize-handle")
+                    .on_drag(DraggedDockSplit, |split, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| split.clone())
+                    })
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|_, _: &MouseDownEvent, _, cx| {
+                            cx.stop_propagation();
+                        }),
+                    )
+                    .on_mouse_up(
+                        MouseButton::Left,
+                        cx.listener(|dock, e: &MouseUpEvent, window, cx| {
+                            if e.click_count == 2 {
+                                dock.set_split_ratio(0.5, cx);
+                                dock.workspace
+                                    .update(cx, |workspace, cx| {
+                                        workspace.serialize_workspace(window, cx);
+                                    })
+                                    .ok();
+                                cx.stop_propagation();
+                            }
+                        }),
+                    )
+                    .occlude()
+                    .absolute()
+                    .map(|this| match axis {
+                        Axis::Horizontal => this
+                            .bottom(-RESIZE_HANDLE_SIZE / 2.)
+                            .left(px(0.))
+                            .w_full()
+                            .h(RESIZE_HANDLE_SIZE)
+                            .cursor_row_resize(),
+                        Axis::Vertical => this
+                            .right(-RESIZE_HANDLE_SIZE / 2.)
+                            .top(px(0.))
+                            .h_full()
+                            .w(RESIZE_HANDLE_SIZE)
+                            .cursor_col_resize(),
+                    });
+
+                div()
+                    .size_full()
+                    .flex()
+                    .map(|this| match axis {
+                        Axis::Horizontal => this.flex_col(),
+                        Axis::Vertical => this.flex_row(),
+                    })
+                    .on_drag_move(cx.listener(
+                        move |dock, e: &DragMoveEvent<DraggedDockSplit>, window, cx| {
+                            let ratio = match axis {
+                                Axis::Horizontal => {
+                                    (e.event.position.y - e.bounds.top()) / e.bounds.size.height
+                                }
+                                Axis::Vertical => {
+                                    (e.event.position.x - e.bounds.left()) / e.bounds.size.width
+                                }
+                            };
+                            if dock.split_ratio() != Some(ratio) {
+                                dock.set_split_ratio(ratio, cx);
+                                dock.workspace
+                                    .update(cx, |workspace, cx| {
+                                        workspace.serialize_workspace(window, cx);
+                                    })
+                                    .ok();
+                            }
+                        },
+                    ))
+                    .child(
+                        div()
+                            .relative()
+                            .flex_none()
+                            .map(|this| match axis {
+                                Axis::Horizontal => this.w_full().h(relative(ratio)),
+                                Axis::Vertical => this.h_full().w(relative(ratio)),
+                            })
+                            .child(panel_content(entry))
+                            .when(resizable, |this| this.child(deferred(split_handle))),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .border_color(cx.theme().colors().border)
+                            .map(|this| match axis {
+                                Axis::Horizontal => this.w_full().border_t_1(),
+                                Axis::Vertical => this.h_full().border_l_1(),
+                            })
+                            .child(panel_content(split_entry)),
+                    )
+                    .into_any_element()
+            } else {
+                panel_content(entry).into_any_element()
+            };
+
+            let axis = self.position().axis();
+            let dock = div()
                 .key_context(dispatch_context)
                 .track_focus(&self.focus_handle(cx))
                 .flex()
                 .bg(cx.theme().colors().panel_background)
                 .border_color(cx.theme().colors().border)
                 .overflow_hidden()
-                .map(|this| match self.position().axis() {
+                .map(|this| match axis {
                     Axis::Horizontal => this.w(size).h_full().flex_row(),
                     Axis::Vertical => this.h(size).w_full().flex_col(),
                 })
@@ -799,24 +1502,53 @@ impl Render for Dock {
                             Axis::Horizontal => this.min_w(size).h_full(),
                             Axis::Vertical => this.min_h(size).w_full(),
                         })
-                        .child(
-                            entry
-                                .panel
-                                .to_any()
-                                .cached(StyleRefinement::default().v_flex().size_full()),
-                        ),
+                        .child(content),
                 )
-                .when(self.resizable(cx), |this| {
-                    this.child(create_resize_handle())
-                })
+                .when(resizable, |this| this.child(create_resize_handle()));
+
+            match transition_duration {
+                Some(duration) => {
+                    let is_open = self.is_open;
+                    dock.with_animation(
+                        ("dock-transition", self.transition_count),
+                        Animation::new(duration).with_easing(gpui::ease_in_out),
+                        move |dock, delta| {
+                            let open_fraction = if is_open { delta } else { 1. - delta };
+                            match axis {
+                                Axis::Horizontal => dock.w(size * open_fraction),
+                                Axis::Vertical => dock.h(size * open_fraction),
+                            }
+                        },
+                    )
+                    .into_any_element()
+                }
+                None => dock.into_any_element(),
+            }
         } else {
             div()
                 .key_context(dispatch_context)
                 .track_focus(&self.focus_handle(cx))
+                .into_any_element()
         }
     }
 }
 
+/// Orders panels the user reordered first, by their custom position, then the
+/// rest by activation priority.
+fn panel_sort_key(
+    panel_order: &BTreeMap<String, usize>,
+    persistent_name: &str,
+    activation_priority: u32,
+) -> (usize, u32) {
+    (
+        panel_order
+            .get(persistent_name)
+            .copied()
+            .unwrap_or(usize::MAX),
+        activation_priority,
+    )
+}
+
 impl PanelButtons {
     pub fn new(dock: Entity<Dock>, cx: &mut Context<Self>) -> Self {
         cx.observe(&dock, |_, _, cx| cx.notify()).detach();
@@ -826,11 +1558,17 @@ impl PanelButtons {
 
 impl Render for PanelButtons {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let dock_entity = self.dock.downgrade();
+        let dock_id = self.dock.entity_id();
         let dock = self.dock.read(cx);
         let active_index = dock.active_panel_index;
+        let split_index = dock.split_panels.map(|(index, _)| index);
         let is_open = dock.is_open;
+        let auto_hide = dock.auto_hide;
         let dock_position = dock.position;
 
+        let badge_border_color = cx.theme().colors().status_bar_background;
+
         let (menu_anchor, menu_attach) = match dock.position {
             DockPosition::Left => (Corner::BottomLeft, Corner::TopLeft),
             DockPosition::Bottom | DockPosition::Right => (Corner::BottomRight, Corner::TopRight),
@@ -843,10 +1581,17 @@ impl Render for PanelButtons {
             .filter_map(|(i, entry)| {
                 let icon = entry.panel.icon(window, cx)?;
                 let icon_tooltip = entry.panel.icon_tooltip(window, cx)?;
+                let badge = entry.panel.badge(window, cx);
                 let name = entry.panel.persistent_name();
                 let panel = entry.panel.clone();
+                let panel_id = panel.panel_id();
+                let pinned = entry.pinned;
+                let dock_entity = dock_entity.clone();
 
                 let is_active_button = Some(i) == active_index && is_open;
+                let is_split = Some(i) == split_index && is_open;
+                let can_split =
+                    is_open && active_index.is_some_and(|index| index != i) && !is_split;
                 let (action, tooltip) = if is_active_button {
                     let action = dock.toggle_action();
 
@@ -884,23 +1629,193 @@ impl Render for PanelButtons {
                                         )
                                     }
                                 }
-                                menu
+                                menu.separator()
+                                    .entry(
+                                        if pinned { "Unpin Panel" } else { "Pin Panel" },
+                                        None,
+                                        {
+                                            let dock_entity = dock_entity.clone();
+                                            move |window, cx| {
+                                                dock_entity
+                                                    .update(cx, |dock, cx| {
+                                                        dock.set_panel_pinned(
+                                                            panel_id, !pinned, cx,
+                                                        );
+                                                        dock.workspace
+                                                            .update(cx, |workspace, cx| {
+                                                                workspace.serialize_workspace(
+                                                                    window, cx,
+                                                                );
+                                                            })
+                                                            .ok();
+                                                    })
+                                                    .ok();
+                                            }
+                                        },
+                                    )
+                                    .toggleable_entry(
+                                        "Auto-Hide Dock",
+                                        auto_hide,
+                                        IconPosition::Start,
+                                        None,
+                                        {
+                                            let dock_entity = dock_entity.clone();
+                                            move |window, cx| {
+                                                dock_entity
+                                                    .update(cx, |dock, cx| {
+                                                        dock.set_auto_hide(!auto_hide, cx);
+                                                        dock.workspace
+                                                            .update(cx, |workspace, cx| {
+                                                                workspace.serialize_workspace(
+                                                                    window, cx,
+                                                                );
+                                                            })
+                                                            .ok();
+                                                    })
+                                                    .ok();
+                                            }
+                                        },
+                                    )
+                                    .when(can_split, |menu| {
+                                        let dock_entity = dock_entity.clone();
+                                        menu.entry(
+                                            "Split Dock With Panel",
+                                            None,
+                                            move |window, cx| {
+                                                dock_entity
+                                                    .update(cx, |dock, cx| {
+                                                        dock.split_with_panel(i, window, cx);
+                                                        dock.workspace
+                                                            .update(cx, |workspace, cx| {
+                                                                workspace.serialize_workspace(
+                                                                    window, cx,
+                                                                );
+                                                            })
+                                                            .ok();
+                                                    })
+                                                    .ok();
+                                            },
+                                        )
+                                    })
+                                    .when(is_split, |menu| {
+                                        let dock_entity = dock_entity.clone();
+                                        menu.entry("Close Split", None, move |window, cx| {
+                                            dock_entity
+                                                .update(cx, |dock, cx| {
+                                                    dock.close_split(window, cx);
+                                                    dock.workspace
+                                                        .update(cx, |workspace, cx| {
+                                                            workspace
+                                                                .serialize_workspace(window, cx);
+                                                        })
+                                                        .ok();
+                                                })
+                                                .ok();
+                                        })
+                                    })
                             })
                         })
                         .anchor(menu_anchor)
                         .attach(menu_attach)
                         .trigger(
-                            IconButton::new(name, icon)
-                                .icon_size(IconSize::Small)
-                                .toggle_state(is_active_button)
-                                .on_click({
-                                    let action = action.boxed_clone();
-                                    move |_, window, cx| {
-                                        window.dispatch_action(action.boxed_clone(), cx)
+                            div()
+                                .id(("panel-button", i))
+                                .relative()
+                                .rounded_sm()
+                                .on_drag(
+                                    DraggedPanelButton {
+                                        dock_id,
+                                        panel_id,
+                                        icon,
+                                    },
+                                    |dragged, _, _, cx| cx.new(|_| dragged.clone()),
+                                )
+                                .drag_over::<DraggedPanelButton>(move |style, dragged, _, cx| {
+                                    if dragged.dock_id == dock_id && dragged.panel_id != panel_id {
+                                        style.bg(cx.theme().colors().drop_target_background)
+                                    } else {
+                                        style
                                     }
                                 })
-                                .tooltip(move |window, cx| {
-                                    Tooltip::for_action(tooltip.clone(), &*action, window, cx)
+                                .on_drop(cx.listener(
+                                    move |this, dragged: &DraggedPanelButton, window, cx| {
+                                        if dragged.dock_id != dock_id {
+                                            return;
+                                        }
+                                        this.dock.update(cx, |dock, cx| {
+                                            let Some(dragged_ix) =
+                                                dock.panel_entries.iter().position(|entry| {
+                                                    entry.panel.panel_id() == dragged.panel_id
+                                                })
+                                            else {
+                                                return;
+                                            };
+                                            dock.swap_panels(dragged_ix, i, cx);
+                                            dock.workspace
+                                                .update(cx, |workspace, cx| {
+                                                    workspace.serialize_workspace(window, cx);
+                                                })
+                                                .ok();
+                                        });
+                                    },
+                                ))
+                                // Auto-hidden docks reopen when their active panel's button is hovered.
+                                .when(auto_hide && !is_open && Some(i) == active_index, |this| {
+                                    let dock_entity = dock_entity.clone();
+                                    let panel = entry.panel.clone();
+                                    this.on_hover(move |hovered, window, cx| {
+                                        if *hovered {
+                                            dock_entity
+                                                .update(cx, |dock, cx| {
+                                                    dock.set_open(true, window, cx)
+                                                })
+                                                .ok();
+                                            window.focus(&panel.panel_focus_handle(cx));
+                                        }
+                                    })
+                                })
+                                .child(
+                                    IconButton::new(name, icon)
+                                        .icon_size(IconSize::Small)
+                                        .toggle_state(is_active_button || is_split)
+                                        .when_some(badge.as_ref(), |this, badge| {
+                                            this.indicator(Indicator::dot().color(badge.color))
+                                                .indicator_border_color(Some(badge_border_color))
+                                        })
+                                        .on_click({
+                                            let action = action.boxed_clone();
+                                            move |_, window, cx| {
+                                                window.dispatch_action(action.boxed_clone(), cx)
+                                            }
+                                        })
+                                        .tooltip(move |window, cx| {
+                                            Tooltip::for_action(
+                                                tooltip.clone(),
+                                                &*action,
+                                                window,
+                                                cx,
+                                            )
+                                        }),
+                                )
+                                .when_some(badge.and_then(|badge| badge.count), |this, count| {
+                                    this.child(
+                                        div()
+                                            .absolute()
+                                            .bottom(px(-2.))
+                                            .right(px(-3.))
+                                            .px_0p5()
+                                            .rounded_sm()
+                                            .bg(badge_border_color)
+                                            .child(
+                                                Label::new(if count > 99 {
+                                                    "99+".to_string()
+                                                } else {
+                                                    count.to_string()
+                                                })
+                                                .size(LabelSize::XSmall)
+                                                .color(Color::Muted),
+                                            ),
+                                    )
                                 }),
                         ),
                 )
@@ -935,10 +1850,14 @@ pub mod test {
 
     pub struct TestPanel {
         pub position: DockPosition,
+        pub default_position: Option<DockPosition>,
+        pub has_chosen_position: bool,
         pub zoomed: bool,
         pub active: bool,
         pub focus_handle: FocusHandle,
         pub size: Pixels,
+        pub min_size: Pixels,
+        pub close_count: usize,
     }
     actions!(test, [ToggleTestPanel]);
 
@@ -948,10 +1867,14 @@ pub mod test {
         pub fn new(position: DockPosition, cx: &mut App) -> Self {
             Self {
                 position,
+                default_position: None,
+                has_chosen_position: true,
                 zoomed: false,
                 active: false,
                 focus_handle: cx.focus_handle(),
                 size: px(300.),
+                min_size: RESIZE_HANDLE_SIZE,
+                close_count: 0,
             }
         }
     }
@@ -971,6 +1894,14 @@ pub mod test {
             self.position
         }
 
+        fn default_position(&self, _window: &Window, _: &App) -> super::DockPosition {
+            self.default_position.unwrap_or(self.position)
+        }
+
+        fn has_chosen_position(&self, _window: &Window, _: &App) -> bool {
+            self.has_chosen_position
+        }
+
         fn position_is_valid(&self, _: super::DockPosition) -> bool {
             true
         }
@@ -988,6 +1919,10 @@ pub mod test {
             self.size = size.unwrap_or(px(300.));
         }
 
+        fn min_size(&self, _cx: &App) -> Pixels {
+            self.min_size
+        }
+
         fn icon(&self, _window: &Window, _: &App) -> Option<ui::IconName> {
             None
         }
@@ -1012,6 +1947,10 @@ pub mod test {
             self.active = active;
         }
 
+        fn on_close(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
+            self.close_count += 1;
+        }
+
         fn activation_priority(&self) -> u32 {
             100
         }
@@ -166,6 +166,8 @@ pub struct BreadcrumbText {
     pub text: String,
     pub highlights: Option<Vec<(Range<usize>, HighlightStyle)>>,
     pub font: Option<Font>,
+    /// Called when the segment is clicked, making it render as a button.
+    pub on_click: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -238,6 +240,13 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
         None
     }
 
+    /// Returns item-specific entries appended to the tab's context menu.
+    ///
+    /// The actions are dispatched to the item's focus handle.
+    fn tab_context_menu_actions(&self, _: &App) -> Vec<(SharedString, Box<dyn Action>)> {
+        Vec::new()
+    }
+
     fn to_item_events(_event: &Self::Event, _f: impl FnMut(ItemEvent)) {}
 
     fn deactivated(&mut self, _window: &mut Window, _: &mut Context<Self>) {}
@@ -448,6 +457,7 @@ pub trait ItemHandle: 'static + Send {
     fn tab_icon(&self, window: &Window, cx: &App) -> Option<Icon>;
     fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString>;
     fn tab_tooltip_content(&self, cx: &App) -> Option<TabTooltipContent>;
+    fn tab_context_menu_actions(&self, cx: &App) -> Vec<(SharedString, Box<dyn Action>)>;
     fn telemetry_event_text(&self, cx: &App) -> Option<&'static str>;
     fn dragged_tab_content(
         &self,
@@ -587,6 +597,10 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.read(cx).tab_tooltip_text(cx)
     }
 
+    fn tab_context_menu_actions(&self, cx: &App) -> Vec<(SharedString, Box<dyn Action>)> {
+        self.read(cx).tab_context_menu_actions(cx)
+    }
+
     fn dragged_tab_content(
         &self,
         params: TabContentParams,
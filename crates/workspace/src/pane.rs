@@ -73,6 +73,15 @@ impl DraggedSelection {
     }
 }
 
+/// Plain text being dragged, such as a selection from an editor.
+///
+/// Panes don't open anything for dropped text themselves; it only reaches their
+/// custom drop handle, if one is set.
+#[derive(Debug, Clone)]
+pub struct DraggedText {
+    pub text: SharedString,
+}
+
 #[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SaveIntent {
@@ -2438,11 +2447,16 @@ impl Pane {
         let has_items_to_left = ix > 0;
         let has_items_to_right = ix < total_items - 1;
         let is_pinned = self.is_tab_pinned(ix);
+        let item_actions = item.tab_context_menu_actions(cx);
         let pane = cx.entity().downgrade();
         let menu_context = item.item_focus_handle(cx);
         right_click_menu(ix).trigger(tab).menu(move |window, cx| {
             let pane = pane.clone();
             let menu_context = menu_context.clone();
+            let item_actions = item_actions
+                .iter()
+                .map(|(label, action)| (label.clone(), action.boxed_clone()))
+                .collect::<Vec<_>>();
             ContextMenu::build(window, cx, move |mut menu, window, cx| {
                 if let Some(pane) = pane.upgrade() {
                     menu = menu
@@ -2661,6 +2675,13 @@ impl Pane {
                     } else {
                         menu = menu.map(pin_tab_entries);
                     }
+
+                    if !item_actions.is_empty() {
+                        menu = menu.separator();
+                        for (label, action) in item_actions {
+                            menu = menu.action(label, action);
+                        }
+                    }
                 }
 
                 menu.context(menu_context)
@@ -3029,6 +3050,19 @@ impl Pane {
             .log_err();
     }
 
+    fn handle_dragged_text_drop(
+        &mut self,
+        dragged_text: &DraggedText,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(custom_drop_handle) = self.custom_drop_handle.clone() {
+            if let ControlFlow::Break(()) = custom_drop_handle(self, dragged_text, window, cx) {
+                return;
+            }
+        }
+    }
+
     fn handle_external_paths_drop(
         &mut self,
         paths: &ExternalPaths,
@@ -3361,6 +3395,9 @@ impl Render for Pane {
                             .when(is_local, |div| {
                                 div.group_drag_over::<ExternalPaths>("", |style| style.visible())
                             })
+                            .when(self.custom_drop_handle.is_some(), |div| {
+                                div.group_drag_over::<DraggedText>("", |style| style.visible())
+                            })
                             .when_some(self.can_drop_predicate.clone(), |this, p| {
                                 this.can_drop(move |a, window, cx| p(a, window, cx))
                             })
@@ -3380,6 +3417,11 @@ impl Render for Pane {
                             .on_drop(cx.listener(move |this, paths, window, cx| {
                                 this.handle_external_paths_drop(paths, window, cx)
                             }))
+                            .on_drop(cx.listener(
+                                move |this, dragged_text: &DraggedText, window, cx| {
+                                    this.handle_dragged_text_drop(dragged_text, window, cx)
+                                },
+                            ))
                             .map(|div| {
                                 let size = DefiniteLength::Fraction(0.5);
                                 match self.drag_split_direction {
@@ -3679,6 +3721,21 @@ impl Render for DraggedTab {
     }
 }
 
+impl Render for DraggedText {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let first_line = self.text.lines().next().unwrap_or_default().to_string();
+        div()
+            .max_w_64()
+            .px_2()
+            .py_1()
+            .rounded_sm()
+            .bg(cx.theme().colors().element_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(first_line).buffer_font(cx).truncate())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZero;
@@ -541,6 +541,39 @@ define_connection! {
         ALTER TABLE breakpoints ADD COLUMN condition TEXT;
         ALTER TABLE breakpoints ADD COLUMN hit_condition TEXT;
     ),
+    // Add pinned panels persistence
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN left_dock_pinned_panels TEXT;
+        ALTER TABLE workspaces ADD COLUMN right_dock_pinned_panels TEXT;
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_pinned_panels TEXT;
+    ),
+    // Add per-workspace dock panel sizes
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN left_dock_panel_sizes TEXT;
+        ALTER TABLE workspaces ADD COLUMN right_dock_panel_sizes TEXT;
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_panel_sizes TEXT;
+    ),
+    // Add split dock panels
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN left_dock_split_panel TEXT;
+        ALTER TABLE workspaces ADD COLUMN right_dock_split_panel TEXT;
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_split_panel TEXT;
+        ALTER TABLE workspaces ADD COLUMN left_dock_split_ratio REAL;
+        ALTER TABLE workspaces ADD COLUMN right_dock_split_ratio REAL;
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_split_ratio REAL;
+    ),
+    // Custom order of the panels in each dock, as a JSON array of persistent names.
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN left_dock_panel_order TEXT;
+        ALTER TABLE workspaces ADD COLUMN right_dock_panel_order TEXT;
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_panel_order TEXT;
+    ),
+    // Whether each dock closes when it loses focus.
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN left_dock_auto_hide INTEGER;
+        ALTER TABLE workspaces ADD COLUMN right_dock_auto_hide INTEGER;
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_auto_hide INTEGER;
+    ),
     ];
 }
 
@@ -592,12 +625,30 @@ impl WorkspaceDb {
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
+                    left_dock_pinned_panels,
+                    left_dock_panel_sizes,
+                    left_dock_split_panel,
+                    left_dock_split_ratio,
+                    left_dock_panel_order,
+                    left_dock_auto_hide,
                     right_dock_visible,
                     right_dock_active_panel,
                     right_dock_zoom,
+                    right_dock_pinned_panels,
+                    right_dock_panel_sizes,
+                    right_dock_split_panel,
+                    right_dock_split_ratio,
+                    right_dock_panel_order,
+                    right_dock_auto_hide,
                     bottom_dock_visible,
                     bottom_dock_active_panel,
                     bottom_dock_zoom,
+                    bottom_dock_pinned_panels,
+                    bottom_dock_panel_sizes,
+                    bottom_dock_split_panel,
+                    bottom_dock_split_ratio,
+                    bottom_dock_panel_order,
+                    bottom_dock_auto_hide,
                     window_id
                 FROM workspaces
                 WHERE local_paths = ?
@@ -658,12 +709,30 @@ impl WorkspaceDb {
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
+                    left_dock_pinned_panels,
+                    left_dock_panel_sizes,
+                    left_dock_split_panel,
+                    left_dock_split_ratio,
+                    left_dock_panel_order,
+                    left_dock_auto_hide,
                     right_dock_visible,
                     right_dock_active_panel,
                     right_dock_zoom,
+                    right_dock_pinned_panels,
+                    right_dock_panel_sizes,
+                    right_dock_split_panel,
+                    right_dock_split_ratio,
+                    right_dock_panel_order,
+                    right_dock_auto_hide,
                     bottom_dock_visible,
                     bottom_dock_active_panel,
                     bottom_dock_zoom,
+                    bottom_dock_pinned_panels,
+                    bottom_dock_panel_sizes,
+                    bottom_dock_split_panel,
+                    bottom_dock_split_ratio,
+                    bottom_dock_panel_order,
+                    bottom_dock_auto_hide,
                     window_id
                 FROM workspaces
                 WHERE ssh_project_id = ?
@@ -795,19 +864,37 @@ impl WorkspaceDb {
                                 left_dock_visible,
                                 left_dock_active_panel,
                                 left_dock_zoom,
+                                left_dock_pinned_panels,
+                                left_dock_panel_sizes,
+                                left_dock_split_panel,
+                                left_dock_split_ratio,
+                                left_dock_panel_order,
+                                left_dock_auto_hide,
                                 right_dock_visible,
                                 right_dock_active_panel,
                                 right_dock_zoom,
+                                right_dock_pinned_panels,
+                                right_dock_panel_sizes,
+                                right_dock_split_panel,
+                                right_dock_split_ratio,
+                                right_dock_panel_order,
+                                right_dock_auto_hide,
                                 bottom_dock_visible,
                                 bottom_dock_active_panel,
                                 bottom_dock_zoom,
+                                bottom_dock_pinned_panels,
+                                bottom_dock_panel_sizes,
+                                bottom_dock_split_panel,
+                                bottom_dock_split_ratio,
+                                bottom_dock_panel_order,
+                                bottom_dock_auto_hide,
                                 session_id,
                                 window_id,
                                 timestamp,
                                 local_paths_array,
                                 local_paths_order_array
                             )
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, CURRENT_TIMESTAMP, ?15, ?16)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, CURRENT_TIMESTAMP, ?33, ?34)
                             ON CONFLICT DO
                             UPDATE SET
                                 local_paths = ?2,
@@ -815,17 +902,35 @@ impl WorkspaceDb {
                                 left_dock_visible = ?4,
                                 left_dock_active_panel = ?5,
                                 left_dock_zoom = ?6,
-                                right_dock_visible = ?7,
-                                right_dock_active_panel = ?8,
-                                right_dock_zoom = ?9,
-                                bottom_dock_visible = ?10,
-                                bottom_dock_active_panel = ?11,
-                                bottom_dock_zoom = ?12,
-                                session_id = ?13,
-                                window_id = ?14,
+                                left_dock_pinned_panels = ?7,
+                                left_dock_panel_sizes = ?8,
+                                left_dock_split_panel = ?9,
+                                left_dock_split_ratio = ?10,
+                                left_dock_panel_order = ?11,
+                                left_dock_auto_hide = ?12,
+                                right_dock_visible = ?13,
+                                right_dock_active_panel = ?14,
+                                right_dock_zoom = ?15,
+                                right_dock_pinned_panels = ?16,
+                                right_dock_panel_sizes = ?17,
+                                right_dock_split_panel = ?18,
+                                right_dock_split_ratio = ?19,
+                                right_dock_panel_order = ?20,
+                                right_dock_auto_hide = ?21,
+                                bottom_dock_visible = ?22,
+                                bottom_dock_active_panel = ?23,
+                                bottom_dock_zoom = ?24,
+                                bottom_dock_pinned_panels = ?25,
+                                bottom_dock_panel_sizes = ?26,
+                                bottom_dock_split_panel = ?27,
+                                bottom_dock_split_ratio = ?28,
+                                bottom_dock_panel_order = ?29,
+                                bottom_dock_auto_hide = ?30,
+                                session_id = ?31,
+                                window_id = ?32,
                                 timestamp = CURRENT_TIMESTAMP,
-                                local_paths_array = ?15,
-                                local_paths_order_array = ?16
+                                local_paths_array = ?33,
+                                local_paths_order_array = ?34
                         );
                         let mut prepared_query = conn.exec_bound(query)?;
                         let args = (workspace.id, &local_paths, &local_paths_order, workspace.docks, workspace.session_id, workspace.window_id, local_paths.paths().iter().map(|path| path.to_string_lossy().to_string()).join(","), local_paths_order.order().iter().map(|order| order.to_string()).join(","));
@@ -847,31 +952,67 @@ impl WorkspaceDb {
                                 left_dock_visible,
                                 left_dock_active_panel,
                                 left_dock_zoom,
+                                left_dock_pinned_panels,
+                                left_dock_panel_sizes,
+                                left_dock_split_panel,
+                                left_dock_split_ratio,
+                                left_dock_panel_order,
+                                left_dock_auto_hide,
                                 right_dock_visible,
                                 right_dock_active_panel,
                                 right_dock_zoom,
+                                right_dock_pinned_panels,
+                                right_dock_panel_sizes,
+                                right_dock_split_panel,
+                                right_dock_split_ratio,
+                                right_dock_panel_order,
+                                right_dock_auto_hide,
                                 bottom_dock_visible,
                                 bottom_dock_active_panel,
                                 bottom_dock_zoom,
+                                bottom_dock_pinned_panels,
+                                bottom_dock_panel_sizes,
+                                bottom_dock_split_panel,
+                                bottom_dock_split_ratio,
+                                bottom_dock_panel_order,
+                                bottom_dock_auto_hide,
                                 session_id,
                                 window_id,
                                 timestamp
                             )
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, CURRENT_TIMESTAMP)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, CURRENT_TIMESTAMP)
                             ON CONFLICT DO
                             UPDATE SET
                                 ssh_project_id = ?2,
                                 left_dock_visible = ?3,
                                 left_dock_active_panel = ?4,
                                 left_dock_zoom = ?5,
-                                right_dock_visible = ?6,
-                                right_dock_active_panel = ?7,
-                                right_dock_zoom = ?8,
-                                bottom_dock_visible = ?9,
-                                bottom_dock_active_panel = ?10,
-                                bottom_dock_zoom = ?11,
-                                session_id = ?12,
-                                window_id = ?13,
+                                left_dock_pinned_panels = ?6,
+                                left_dock_panel_sizes = ?7,
+                                left_dock_split_panel = ?8,
+                                left_dock_split_ratio = ?9,
+                                left_dock_panel_order = ?10,
+                                left_dock_auto_hide = ?11,
+                                right_dock_visible = ?12,
+                                right_dock_active_panel = ?13,
+                                right_dock_zoom = ?14,
+                                right_dock_pinned_panels = ?15,
+                                right_dock_panel_sizes = ?16,
+                                right_dock_split_panel = ?17,
+                                right_dock_split_ratio = ?18,
+                                right_dock_panel_order = ?19,
+                                right_dock_auto_hide = ?20,
+                                bottom_dock_visible = ?21,
+                                bottom_dock_active_panel = ?22,
+                                bottom_dock_zoom = ?23,
+                                bottom_dock_pinned_panels = ?24,
+                                bottom_dock_panel_sizes = ?25,
+                                bottom_dock_split_panel = ?26,
+                                bottom_dock_split_ratio = ?27,
+                                bottom_dock_panel_order = ?28,
+                                bottom_dock_auto_hide = ?29,
+                                session_id = ?30,
+                                window_id = ?31,
                                 timestamp = CURRENT_TIMESTAMP
                         ))?((
                             workspace.id,
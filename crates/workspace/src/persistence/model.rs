@@ -304,6 +304,13 @@ pub struct DockData {
     pub(crate) visible: bool,
     pub(crate) active_panel: Option<String>,
     pub(crate) zoom: bool,
+    pub(crate) pinned_panels: Vec<String>,
+    pub(crate) panel_sizes: BTreeMap<String, f32>,
+    pub(crate) split_panel: Option<String>,
+    pub(crate) split_ratio: Option<f32>,
+    /// The persistent names of the panels the user reordered, in their custom order.
+    pub(crate) panel_order: Vec<String>,
+    pub(crate) auto_hide: bool,
 }
 
 impl Column for DockData {
@@ -311,11 +318,38 @@ impl Column for DockData {
         let (visible, next_index) = Option::<bool>::column(statement, start_index)?;
         let (active_panel, next_index) = Option::<String>::column(statement, next_index)?;
         let (zoom, next_index) = Option::<bool>::column(statement, next_index)?;
+        let (pinned_panels, next_index) = Option::<String>::column(statement, next_index)?;
+        let pinned_panels = pinned_panels
+            .map(|pinned_panels| serde_json::from_str::<Vec<String>>(&pinned_panels))
+            .transpose()
+            .context("deserializing pinned panels")?
+            .unwrap_or_default();
+        let (panel_sizes, next_index) = Option::<String>::column(statement, next_index)?;
+        let panel_sizes = panel_sizes
+            .map(|panel_sizes| serde_json::from_str::<BTreeMap<String, f32>>(&panel_sizes))
+            .transpose()
+            .context("deserializing panel sizes")?
+            .unwrap_or_default();
+        let (split_panel, next_index) = Option::<String>::column(statement, next_index)?;
+        let (split_ratio, next_index) = Option::<f32>::column(statement, next_index)?;
+        let (panel_order, next_index) = Option::<String>::column(statement, next_index)?;
+        let panel_order = panel_order
+            .map(|panel_order| serde_json::from_str::<Vec<String>>(&panel_order))
+            .transpose()
+            .context("deserializing panel order")?
+            .unwrap_or_default();
+        let (auto_hide, next_index) = Option::<bool>::column(statement, next_index)?;
         Ok((
             DockData {
                 visible: visible.unwrap_or(false),
                 active_panel,
                 zoom: zoom.unwrap_or(false),
+                pinned_panels,
+                panel_sizes,
+                split_panel,
+                split_ratio,
+                panel_order,
+                auto_hide: auto_hide.unwrap_or(false),
             },
             next_index,
         ))
@@ -326,7 +360,14 @@ impl Bind for DockData {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
         let next_index = statement.bind(&self.visible, start_index)?;
         let next_index = statement.bind(&self.active_panel, next_index)?;
-        statement.bind(&self.zoom, next_index)
+        let next_index = statement.bind(&self.zoom, next_index)?;
+        let next_index =
+            statement.bind(&serde_json::to_string(&self.pinned_panels)?, next_index)?;
+        let next_index = statement.bind(&serde_json::to_string(&self.panel_sizes)?, next_index)?;
+        let next_index = statement.bind(&self.split_panel, next_index)?;
+        let next_index = statement.bind(&self.split_ratio, next_index)?;
+        let next_index = statement.bind(&serde_json::to_string(&self.panel_order)?, next_index)?;
+        statement.bind(&self.auto_hide, next_index)
     }
 }
 
@@ -113,6 +113,9 @@ use crate::persistence::{
 
 pub const SERIALIZATION_THROTTLE_TIME: Duration = Duration::from_millis(200);
 
+const DOCK_RESIZE_STEP: Pixels = px(16.);
+const DOCK_LARGE_RESIZE_STEP: Pixels = px(64.);
+
 static ZED_WINDOW_SIZE: LazyLock<Option<Size<Pixels>>> = LazyLock::new(|| {
     env::var("ZED_WINDOW_SIZE")
         .ok()
@@ -238,6 +241,31 @@ pub struct CloseInactiveTabsAndPanes {
 #[derive(Clone, Deserialize, PartialEq, JsonSchema)]
 pub struct SendKeystrokes(pub String);
 
+/// Activates the next panel in a dock, opening the dock if it's closed.
+#[derive(Clone, PartialEq, Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CycleDockPanels {
+    pub dock: DockPosition,
+}
+
+/// Grows the focused dock's active panel by a fixed step.
+#[derive(Clone, PartialEq, Debug, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IncreaseDockSize {
+    /// Use a larger step.
+    #[serde(default)]
+    pub large: bool,
+}
+
+/// Shrinks the focused dock's active panel by a fixed step.
+#[derive(Clone, PartialEq, Debug, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DecreaseDockSize {
+    /// Use a larger step.
+    #[serde(default)]
+    pub large: bool,
+}
+
 #[derive(Clone, Deserialize, PartialEq, Default, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Reload {
@@ -260,6 +288,9 @@ impl_actions!(
         ActivatePane,
         CloseAllItemsAndPanes,
         CloseInactiveTabsAndPanes,
+        CycleDockPanels,
+        DecreaseDockSize,
+        IncreaseDockSize,
         MoveItemToPane,
         MoveItemToPaneInDirection,
         OpenTerminal,
@@ -1399,7 +1430,18 @@ impl Workspace {
         cx.on_focus_in(&focus_handle, window, Self::handle_panel_focused)
             .detach();
 
-        let dock_position = panel.position(window, cx);
+        // Panels go to the position the user chose for them. Otherwise, they go back to the
+        // dock they were serialized in, and start at their default position if there's none.
+        let dock_position = if panel.read(cx).has_chosen_position(window, cx) {
+            panel.position(window, cx)
+        } else {
+            self.all_docks()
+                .into_iter()
+                .map(|dock| dock.read(cx))
+                .find(|dock| dock.has_serialized_panel(T::persistent_name()))
+                .map(|dock| dock.position())
+                .unwrap_or_else(|| panel.default_position(window, cx))
+        };
         let dock = self.dock_at_position(dock_position);
 
         dock.update(cx, |dock, cx| {
@@ -1901,6 +1943,72 @@ impl Workspace {
         }
     }
 
+    fn cycle_dock_panels(
+        &mut self,
+        action: &CycleDockPanels,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.dock_at_position(action.dock)
+            .clone()
+            .update(cx, |dock, cx| dock.toggle_panel_cycle(window, cx));
+        self.dismiss_zoomed_items_to_reveal(Some(action.dock), window, cx);
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
+    fn increase_dock_size(
+        &mut self,
+        action: &IncreaseDockSize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let step = if action.large {
+            DOCK_LARGE_RESIZE_STEP
+        } else {
+            DOCK_RESIZE_STEP
+        };
+        self.resize_focused_dock(step, window, cx);
+    }
+
+    fn decrease_dock_size(
+        &mut self,
+        action: &DecreaseDockSize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let step = if action.large {
+            DOCK_LARGE_RESIZE_STEP
+        } else {
+            DOCK_RESIZE_STEP
+        };
+        self.resize_focused_dock(-step, window, cx);
+    }
+
+    /// Resizes the active panel of the dock containing focus by `amount`, respecting the
+    /// panel's minimum size and the workspace bounds like dragging the resize handle does.
+    fn resize_focused_dock(&mut self, amount: Pixels, window: &mut Window, cx: &mut Context<Self>) {
+        let docks = self.all_docks();
+        let Some(dock) = docks
+            .into_iter()
+            .find(|dock| dock.focus_handle(cx).contains_focused(window, cx))
+        else {
+            cx.propagate();
+            return;
+        };
+        let Some(panel_size) = dock.read(cx).active_panel_size(window, cx) else {
+            return;
+        };
+        let new_size = (panel_size + amount).max(px(0.));
+        match dock.read(cx).position() {
+            DockPosition::Left => resize_left_dock(new_size, self, window, cx),
+            DockPosition::Bottom => resize_bottom_dock(new_size, self, window, cx),
+            DockPosition::Right => resize_right_dock(new_size, self, window, cx),
+        }
+        self.serialize_workspace(window, cx);
+        cx.notify();
+    }
+
     pub fn prepare_to_close(
         &mut self,
         close_intent: CloseIntent,
@@ -2533,6 +2641,9 @@ impl Workspace {
         dock.update(cx, |dock, cx| {
             let other_is_zoomed = self.zoomed.is_some() && self.zoomed_position != Some(dock_side);
             let was_visible = dock.is_open() && !other_is_zoomed;
+            if was_visible && dock.is_active_panel_pinned() {
+                return;
+            }
             dock.set_open(!was_visible, window, cx);
 
             if dock.active_panel().is_none() {
@@ -4659,6 +4770,14 @@ impl Workspace {
                 .active_panel()
                 .map(|panel| panel.is_zoomed(window, cx))
                 .unwrap_or(false);
+            let left_pinned_panels = left_dock.pinned_panels();
+            let left_panel_sizes = left_dock.panel_sizes();
+            let left_split_panel = left_dock
+                .split_panel()
+                .map(|panel| panel.persistent_name().to_string());
+            let left_split_ratio = left_dock.split_ratio();
+            let left_panel_order = left_dock.panel_order();
+            let left_auto_hide = left_dock.auto_hide();
 
             let right_dock = this.right_dock.read(cx);
             let right_visible = right_dock.is_open();
@@ -4669,6 +4788,14 @@ impl Workspace {
                 .active_panel()
                 .map(|panel| panel.is_zoomed(window, cx))
                 .unwrap_or(false);
+            let right_pinned_panels = right_dock.pinned_panels();
+            let right_panel_sizes = right_dock.panel_sizes();
+            let right_split_panel = right_dock
+                .split_panel()
+                .map(|panel| panel.persistent_name().to_string());
+            let right_split_ratio = right_dock.split_ratio();
+            let right_panel_order = right_dock.panel_order();
+            let right_auto_hide = right_dock.auto_hide();
 
             let bottom_dock = this.bottom_dock.read(cx);
             let bottom_visible = bottom_dock.is_open();
@@ -4679,22 +4806,48 @@ impl Workspace {
                 .active_panel()
                 .map(|panel| panel.is_zoomed(window, cx))
                 .unwrap_or(false);
+            let bottom_pinned_panels = bottom_dock.pinned_panels();
+            let bottom_panel_sizes = bottom_dock.panel_sizes();
+            let bottom_split_panel = bottom_dock
+                .split_panel()
+                .map(|panel| panel.persistent_name().to_string());
+            let bottom_split_ratio = bottom_dock.split_ratio();
+            let bottom_panel_order = bottom_dock.panel_order();
+            let bottom_auto_hide = bottom_dock.auto_hide();
 
             DockStructure {
                 left: DockData {
                     visible: left_visible,
                     active_panel: left_active_panel,
                     zoom: left_dock_zoom,
+                    pinned_panels: left_pinned_panels,
+                    panel_sizes: left_panel_sizes,
+                    split_panel: left_split_panel,
+                    split_ratio: left_split_ratio,
+                    panel_order: left_panel_order,
+                    auto_hide: left_auto_hide,
                 },
                 right: DockData {
                     visible: right_visible,
                     active_panel: right_active_panel,
                     zoom: right_dock_zoom,
+                    pinned_panels: right_pinned_panels,
+                    panel_sizes: right_panel_sizes,
+                    split_panel: right_split_panel,
+                    split_ratio: right_split_ratio,
+                    panel_order: right_panel_order,
+                    auto_hide: right_auto_hide,
                 },
                 bottom: DockData {
                     visible: bottom_visible,
                     active_panel: bottom_active_panel,
                     zoom: bottom_dock_zoom,
+                    pinned_panels: bottom_pinned_panels,
+                    panel_sizes: bottom_panel_sizes,
+                    split_panel: bottom_split_panel,
+                    split_ratio: bottom_split_ratio,
+                    panel_order: bottom_panel_order,
+                    auto_hide: bottom_auto_hide,
                 },
             }
         }
@@ -4926,6 +5079,9 @@ impl Workspace {
             .on_action(cx.listener(Self::activate_pane_at_index))
             .on_action(cx.listener(Self::move_item_to_pane_at_index))
             .on_action(cx.listener(Self::move_focused_panel_to_next_position))
+            .on_action(cx.listener(Self::cycle_dock_panels))
+            .on_action(cx.listener(Self::increase_dock_size))
+            .on_action(cx.listener(Self::decrease_dock_size))
             .on_action(cx.listener(|workspace, _: &Unfollow, window, cx| {
                 let pane = workspace.active_pane().clone();
                 workspace.unfollow_in_pane(&pane, window, cx);
@@ -6966,11 +7122,11 @@ pub fn move_active_item(
 
 #[cfg(test)]
 mod tests {
-    use std::{cell::RefCell, rc::Rc};
+    use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
 
     use super::*;
     use crate::{
-        dock::{PanelEvent, test::TestPanel},
+        dock::{DockEvent, PanelEvent, test::TestPanel},
         item::{
             ItemEvent,
             test::{TestItem, TestProjectItem},
@@ -8074,6 +8230,678 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_pinned_panel_keeps_dock_open(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Bottom, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Bottom, window, cx);
+            workspace.bottom_dock().update(cx, |dock, cx| {
+                dock.set_panel_pinned(panel.entity_id(), true, cx)
+            });
+            panel
+        });
+
+        // Toggling a dock with a pinned active panel doesn't close it.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Bottom, window, cx);
+            let bottom_dock = workspace.bottom_dock().read(cx);
+            assert!(bottom_dock.is_open());
+            assert_eq!(bottom_dock.pinned_panels(), vec!["TestPanel".to_string()]);
+        });
+
+        // The panel itself can still close the dock.
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::Close));
+        workspace.update(cx, |workspace, cx| {
+            assert!(!workspace.bottom_dock().read(cx).is_open());
+        });
+
+        // Once unpinned, the dock toggles as usual.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Bottom, window, cx);
+            assert!(workspace.bottom_dock().read(cx).is_open());
+            workspace.bottom_dock().update(cx, |dock, cx| {
+                dock.set_panel_pinned(panel.entity_id(), false, cx)
+            });
+            workspace.toggle_dock(DockPosition::Bottom, window, cx);
+            assert!(!workspace.bottom_dock().read(cx).is_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_panel_on_close(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            panel
+        });
+        assert_eq!(panel.read_with(cx, |panel, _| panel.close_count), 0);
+
+        // Hiding the dock closes its active panel.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        assert_eq!(panel.read_with(cx, |panel, _| panel.close_count), 1);
+
+        // So does the panel asking to be closed.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::Close));
+        assert_eq!(panel.read_with(cx, |panel, _| panel.close_count), 2);
+
+        // Removing the panel from its dock closes it exactly once.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            workspace
+                .left_dock()
+                .update(cx, |dock, cx| dock.remove_panel(&panel, window, cx));
+        });
+        assert_eq!(panel.read_with(cx, |panel, _| panel.close_count), 3);
+    }
+
+    #[gpui::test]
+    async fn test_panel_default_position(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs.clone(), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // Without serialized dock state, the panel starts at its default position.
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| {
+                let mut panel = TestPanel::new(DockPosition::Left, cx);
+                panel.default_position = Some(DockPosition::Bottom);
+                panel.has_chosen_position = false;
+                panel
+            });
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(
+                workspace
+                    .bottom_dock()
+                    .read(cx)
+                    .panel::<TestPanel>()
+                    .is_some()
+            );
+            assert!(
+                workspace
+                    .left_dock()
+                    .read(cx)
+                    .panel::<TestPanel>()
+                    .is_none()
+            );
+        });
+
+        // Unrelated settings changes leave it there.
+        cx.update_global::<SettingsStore, _>(|_, _| {});
+        workspace.update(cx, |workspace, cx| {
+            assert!(
+                workspace
+                    .bottom_dock()
+                    .read(cx)
+                    .panel::<TestPanel>()
+                    .is_some()
+            );
+        });
+
+        // Moving the panel makes it follow its position.
+        panel.update_in(cx, |panel, window, cx| {
+            panel.set_position(DockPosition::Right, window, cx)
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert!(
+                workspace
+                    .right_dock()
+                    .read(cx)
+                    .panel::<TestPanel>()
+                    .is_some()
+            );
+            assert!(
+                workspace
+                    .bottom_dock()
+                    .read(cx)
+                    .panel::<TestPanel>()
+                    .is_none()
+            );
+        });
+
+        // In a later session, the panel is restored to the dock that serialized it,
+        // even when that's its default position rather than its configured one.
+        for serialized_position in [DockPosition::Bottom, DockPosition::Right] {
+            let project = Project::test(fs.clone(), [], cx).await;
+            let (workspace, cx) =
+                cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace
+                    .dock_at_position(serialized_position)
+                    .update(cx, |dock, _| {
+                        dock.serialized_dock = Some(DockData {
+                            active_panel: Some("TestPanel".to_string()),
+                            ..Default::default()
+                        });
+                    });
+                let panel = cx.new(|cx| {
+                    let mut panel = TestPanel::new(DockPosition::Left, cx);
+                    panel.default_position = Some(DockPosition::Bottom);
+                    panel.has_chosen_position = false;
+                    panel
+                });
+                workspace.add_panel(panel, window, cx);
+            });
+            workspace.update(cx, |workspace, cx| {
+                for dock in workspace.all_docks() {
+                    let dock = dock.read(cx);
+                    assert_eq!(
+                        dock.panel::<TestPanel>().is_some(),
+                        dock.position() == serialized_position,
+                        "panel serialized in the {serialized_position:?} dock was added to the {:?} dock",
+                        dock.position()
+                    );
+                }
+            });
+        }
+
+        // A dock's panel order keeps the names of panels that have left it, so it doesn't
+        // restore the panel there, and a chosen position wins over the serialized dock.
+        for (has_chosen_position, expected_position) in
+            [(false, DockPosition::Bottom), (true, DockPosition::Left)]
+        {
+            let project = Project::test(fs.clone(), [], cx).await;
+            let (workspace, cx) =
+                cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.right_dock().update(cx, |dock, _| {
+                    dock.serialized_dock = Some(DockData {
+                        panel_order: vec!["TestPanel".to_string()],
+                        ..Default::default()
+                    });
+                });
+                workspace.bottom_dock().update(cx, |dock, _| {
+                    dock.serialized_dock = Some(DockData {
+                        active_panel: has_chosen_position.then(|| "TestPanel".to_string()),
+                        ..Default::default()
+                    });
+                });
+                let panel = cx.new(|cx| {
+                    let mut panel = TestPanel::new(DockPosition::Left, cx);
+                    panel.default_position = Some(DockPosition::Bottom);
+                    panel.has_chosen_position = has_chosen_position;
+                    panel
+                });
+                workspace.add_panel(panel, window, cx);
+            });
+            workspace.update(cx, |workspace, cx| {
+                for dock in workspace.all_docks() {
+                    let dock = dock.read(cx);
+                    assert_eq!(
+                        dock.panel::<TestPanel>().is_some(),
+                        dock.position() == expected_position,
+                        "panel expected in the {expected_position:?} dock was added to the {:?} dock",
+                        dock.position()
+                    );
+                }
+            });
+        }
+    }
+
+    #[gpui::test]
+    async fn test_split_dock_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (panel_1, panel_2) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            // Panels of equal priority are inserted in front of each other.
+            workspace.add_panel(panel_2.clone(), window, cx);
+            workspace.add_panel(panel_1.clone(), window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.activate_panel(0, window, cx);
+                dock.set_open(true, window, cx);
+            });
+            (panel_1, panel_2)
+        });
+
+        // Splitting shows the second panel alongside the active one.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.split_with_panel(1, window, cx);
+                assert_eq!(
+                    dock.active_panel().map(|panel| panel.panel_id()),
+                    Some(panel_1.entity_id())
+                );
+                assert_eq!(
+                    dock.split_panel().map(|panel| panel.panel_id()),
+                    Some(panel_2.entity_id())
+                );
+                assert_eq!(dock.split_ratio(), Some(0.5));
+            });
+        });
+        assert!(panel_1.read_with(cx, |panel, _| panel.active));
+        assert!(panel_2.read_with(cx, |panel, _| panel.active));
+
+        // Activating the split panel swaps the two panels.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.set_split_ratio(0.7, cx);
+                dock.activate_panel(1, window, cx);
+                assert_eq!(
+                    dock.active_panel().map(|panel| panel.panel_id()),
+                    Some(panel_2.entity_id())
+                );
+                assert_eq!(
+                    dock.split_panel().map(|panel| panel.panel_id()),
+                    Some(panel_1.entity_id())
+                );
+                assert!((dock.split_ratio().unwrap() - 0.3).abs() < f32::EPSILON);
+            });
+        });
+
+        // Closing the dock hides both panels.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+        });
+        assert!(!panel_1.read_with(cx, |panel, _| panel.active));
+        assert!(!panel_2.read_with(cx, |panel, _| panel.active));
+
+        // Removing the split panel returns the dock to a single panel.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.remove_panel(&panel_1, window, cx);
+                assert!(dock.split_panel().is_none());
+                assert_eq!(
+                    dock.active_panel().map(|panel| panel.panel_id()),
+                    Some(panel_2.entity_id())
+                );
+            });
+        });
+    }
+
+    #[gpui::test]
+    async fn test_cycle_dock_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (panel_1, panel_2) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_2.clone(), window, cx);
+            workspace.add_panel(panel_1.clone(), window, cx);
+            workspace
+                .left_dock()
+                .update(cx, |dock, cx| dock.activate_panel(1, window, cx));
+            (panel_1, panel_2)
+        });
+
+        let assert_active = |panel: &Entity<TestPanel>, cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                let dock = workspace.left_dock().read(cx);
+                assert!(dock.is_open());
+                assert_eq!(
+                    dock.active_panel().map(|active| active.panel_id()),
+                    Some(panel.entity_id())
+                );
+                assert!(panel.focus_handle(cx).contains_focused(window, cx));
+            });
+        };
+
+        // Opening a closed dock keeps its active panel.
+        cx.dispatch_action(CycleDockPanels {
+            dock: DockPosition::Left,
+        });
+        assert_active(&panel_2, cx);
+
+        // Cycling wraps around to the first panel.
+        cx.dispatch_action(CycleDockPanels {
+            dock: DockPosition::Left,
+        });
+        assert_active(&panel_1, cx);
+
+        cx.dispatch_action(CycleDockPanels {
+            dock: DockPosition::Left,
+        });
+        assert_active(&panel_2, cx);
+    }
+
+    #[gpui::test]
+    async fn test_dock_panel_sizes_restore(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let (left_panel, right_panel) = workspace.update_in(cx, |workspace, window, cx| {
+            let left_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            let right_panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(left_panel.clone(), window, cx);
+            workspace.add_panel(right_panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            (left_panel, right_panel)
+        });
+
+        // Only panels resized in this workspace have a stored size.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.resize_active_panel(Some(px(420.)), window, cx)
+            });
+            assert_eq!(
+                workspace.left_dock().read(cx).panel_sizes(),
+                BTreeMap::from_iter([("TestPanel".to_string(), 420.)])
+            );
+            assert!(workspace.right_dock().read(cx).panel_sizes().is_empty());
+        });
+
+        // Restoring applies the stored sizes and leaves other panels at their default.
+        workspace.update_in(cx, |workspace, window, cx| {
+            left_panel.update(cx, |panel, cx| panel.set_size(None, window, cx));
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.serialized_dock = Some(DockData {
+                    visible: true,
+                    active_panel: Some("TestPanel".to_string()),
+                    panel_sizes: BTreeMap::from_iter([("TestPanel".to_string(), 250.)]),
+                    ..Default::default()
+                });
+                dock.restore_state(window, cx);
+            });
+            assert_eq!(left_panel.read(cx).size, px(250.));
+            assert_eq!(right_panel.read(cx).size, px(300.));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_swap_dock_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // Panels with the same activation priority are inserted in front of each other.
+        let (panel_1, panel_2) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), window, cx);
+            workspace.add_panel(panel_2.clone(), window, cx);
+            (panel_1, panel_2)
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.activate_panel(0, window, cx);
+                assert!(dock.panel_order().is_empty());
+
+                dock.swap_panels(0, 1, cx);
+                assert_eq!(dock.active_panel_index(), Some(1));
+                assert_eq!(
+                    dock.active_panel().map(|panel| panel.panel_id()),
+                    Some(panel_2.entity_id())
+                );
+                assert_eq!(dock.panel_order(), vec!["TestPanel".to_string()]);
+
+                // Swapping a panel with itself or past the end does nothing.
+                dock.swap_panels(1, 1, cx);
+                dock.swap_panels(0, 2, cx);
+                assert_eq!(dock.active_panel_index(), Some(1));
+
+                dock.activate_panel(0, window, cx);
+                assert_eq!(
+                    dock.active_panel().map(|panel| panel.panel_id()),
+                    Some(panel_1.entity_id())
+                );
+            });
+        });
+    }
+
+    #[gpui::test]
+    async fn test_dock_events(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let _subscription = workspace.update(cx, |workspace, cx| {
+            let events = events.clone();
+            cx.subscribe(workspace.left_dock(), move |_, _, event: &DockEvent, _| {
+                events.borrow_mut().push(event.clone());
+            })
+        });
+
+        let (panel_1, panel_2) = workspace.update_in(cx, |workspace, window, cx| {
+            let panel_1 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            let panel_2 = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(panel_1.clone(), window, cx);
+            workspace.add_panel(panel_2.clone(), window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.activate_panel(1, window, cx);
+                dock.swap_panels(0, 1, cx);
+                dock.remove_panel(&panel_2, window, cx);
+            });
+            (panel_1, panel_2)
+        });
+
+        let panel_1 = panel_1.entity_id();
+        let panel_2 = panel_2.entity_id();
+        assert_eq!(
+            events.take(),
+            [
+                DockEvent::PanelAdded {
+                    persistent_name: "TestPanel",
+                    panel_id: panel_1,
+                },
+                DockEvent::PanelAdded {
+                    persistent_name: "TestPanel",
+                    panel_id: panel_2,
+                },
+                DockEvent::PanelActivated {
+                    persistent_name: "TestPanel",
+                    panel_id: panel_1,
+                },
+                DockEvent::PanelMoved {
+                    persistent_name: "TestPanel",
+                    panel_id: panel_1,
+                    index: 0,
+                },
+                DockEvent::PanelMoved {
+                    persistent_name: "TestPanel",
+                    panel_id: panel_2,
+                    index: 1,
+                },
+                DockEvent::PanelRemoved {
+                    persistent_name: "TestPanel",
+                    panel_id: panel_2,
+                },
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_panel_min_size(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            panel.update(cx, |panel, _| panel.min_size = px(100.));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_dock(DockPosition::Left, window, cx);
+            panel
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let left_dock = workspace.left_dock().clone();
+            left_dock.update(cx, |dock, cx| {
+                dock.resize_active_panel(Some(px(20.)), window, cx)
+            });
+            assert_eq!(panel.read(cx).size, px(100.));
+
+            // Resetting the size still goes back to the panel's default.
+            left_dock.update(cx, |dock, cx| dock.resize_active_panel(None, window, cx));
+            assert_eq!(panel.read(cx).size, px(300.));
+
+            // Clamping to a small window keeps the minimum size.
+            left_dock.update(cx, |dock, cx| dock.clamp_panel_size(px(50.), window, cx));
+            assert_eq!(panel.read(cx).size, px(100.));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_resize_dock_with_keyboard(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            panel.update(cx, |panel, _| panel.min_size = px(100.));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+            panel
+        });
+        cx.run_until_parked();
+        assert_eq!(panel.read_with(cx, |panel, _| panel.size), px(300.));
+
+        cx.dispatch_action(IncreaseDockSize::default());
+        assert_eq!(panel.read_with(cx, |panel, _| panel.size), px(316.));
+
+        cx.dispatch_action(IncreaseDockSize { large: true });
+        assert_eq!(panel.read_with(cx, |panel, _| panel.size), px(380.));
+
+        cx.dispatch_action(DecreaseDockSize::default());
+        assert_eq!(panel.read_with(cx, |panel, _| panel.size), px(364.));
+
+        // Shrinking stops at the panel's minimum size.
+        for _ in 0..5 {
+            cx.dispatch_action(DecreaseDockSize { large: true });
+        }
+        assert_eq!(panel.read_with(cx, |panel, _| panel.size), px(100.));
+
+        // The actions do nothing when no dock is focused.
+        workspace.update_in(cx, |workspace, window, cx| {
+            window.focus(&workspace.active_pane().focus_handle(cx));
+        });
+        cx.dispatch_action(IncreaseDockSize::default());
+        assert_eq!(panel.read_with(cx, |panel, _| panel.size), px(100.));
+    }
+
+    #[gpui::test]
+    async fn test_dock_auto_hide(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace
+                .right_dock()
+                .update(cx, |dock, cx| dock.set_auto_hide(true, cx));
+            panel
+        });
+
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        pane.update_in(cx, |pane, window, cx| {
+            let item = cx.new(TestItem::new);
+            pane.add_item(Box::new(item), true, true, None, window, cx);
+        });
+
+        // Focusing the center pane closes an auto-hidden dock.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(panel.read(cx).focus_handle(cx).contains_focused(window, cx));
+        });
+        pane.update_in(cx, |pane, window, cx| pane.focus_active_item(window, cx));
+        workspace.update_in(cx, |workspace, _, cx| {
+            assert!(!workspace.right_dock().read(cx).is_open());
+        });
+
+        // Focusing anything else, like a modal, leaves it open.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_modal(window, cx, TestModal::new);
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(workspace.has_active_modal(window, cx));
+            assert!(workspace.right_dock().read(cx).is_open());
+            workspace.toggle_modal(window, cx, TestModal::new);
+        });
+        pane.update_in(cx, |pane, window, cx| pane.focus_active_item(window, cx));
+        workspace.update_in(cx, |workspace, _, cx| {
+            assert!(!workspace.right_dock().read(cx).is_open());
+        });
+
+        // Zoomed panels keep their dock open.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+        panel.update_in(cx, |panel, window, cx| panel.set_zoomed(true, window, cx));
+        pane.update_in(cx, |pane, window, cx| pane.focus_active_item(window, cx));
+        workspace.update_in(cx, |workspace, _, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+        });
+        panel.update_in(cx, |panel, window, cx| panel.set_zoomed(false, window, cx));
+
+        // Docks that don't auto-hide stay open.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace
+                .right_dock()
+                .update(cx, |dock, cx| dock.set_auto_hide(false, cx));
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+        pane.update_in(cx, |pane, window, cx| pane.focus_active_item(window, cx));
+        workspace.update_in(cx, |workspace, _, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+        });
+    }
+
     #[gpui::test]
     async fn test_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);
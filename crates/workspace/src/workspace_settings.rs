@@ -26,6 +26,8 @@ pub struct WorkspaceSettings {
     pub max_tabs: Option<NonZeroUsize>,
     pub when_closing_with_no_tabs: CloseWindowWhenNoItems,
     pub on_last_window_closed: OnLastWindowClosed,
+    pub dock_animation_duration_ms: u64,
+    pub reduce_motion: bool,
 }
 
 #[derive(Copy, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -183,6 +185,15 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: auto (nothing on macOS, "app quit" otherwise)
     pub on_last_window_closed: Option<OnLastWindowClosed>,
+    /// How long opening or closing a dock is animated for, in milliseconds.
+    /// Set to 0 to open and close docks instantly.
+    ///
+    /// Default: 150
+    pub dock_animation_duration_ms: Option<u64>,
+    /// Whether to turn off animations in the workspace, such as docks opening and closing.
+    ///
+    /// Default: false
+    pub reduce_motion: Option<bool>,
 }
 
 #[derive(Deserialize)]
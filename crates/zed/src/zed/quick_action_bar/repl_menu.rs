@@ -10,10 +10,11 @@ use repl::{
     worktree_id_for_editor,
 };
 use ui::{
-    ButtonLike, ContextMenu, IconWithIndicator, Indicator, IntoElement, PopoverMenu,
-    PopoverMenuHandle, Tooltip, prelude::*,
+    ButtonLike, ContextMenu, ContextMenuEntry, IconWithIndicator, Indicator, IntoElement,
+    PopoverMenu, PopoverMenuHandle, Tooltip, prelude::*,
 };
 use util::ResultExt;
+use workspace::Workspace;
 
 use super::QuickActionBar;
 
@@ -30,6 +31,8 @@ struct ReplMenuState {
     status: KernelStatus,
     kernel_name: SharedString,
     kernel_language: SharedString,
+    /// How long the current execution has been running, when the kernel is busy.
+    current_delta: Option<Duration>,
 }
 
 impl QuickActionBar {
@@ -87,13 +90,12 @@ impl QuickActionBar {
                 let editor = editor.clone();
                 let session = session.clone();
                 ContextMenu::build(window, cx, move |menu, _, cx| {
-                    let menu_state = session_state(session, cx);
+                    let menu_state = session_state(session.clone(), cx);
                     let status = menu_state.status;
                     let editor = editor.clone();
 
                     menu.map(|menu| {
                         if status.is_connected() {
-                            let status = status.clone();
                             menu.custom_row(move |_window, _cx| {
                                 h_flex()
                                     .child(
@@ -107,14 +109,28 @@ impl QuickActionBar {
                                     )
                                     .into_any_element()
                             })
-                            .custom_row(move |_window, _cx| {
-                                h_flex()
-                                    .child(
-                                        Label::new(status.clone().to_string())
-                                            .size(LabelSize::Small)
-                                            .color(Color::Muted),
-                                    )
-                                    .into_any_element()
+                            .custom_row({
+                                let session = session.clone();
+                                move |_window, cx| {
+                                    // Read the state on every render, which happens each
+                                    // second while the kernel is busy.
+                                    let menu_state = session_state(session.clone(), cx);
+                                    let status = match menu_state.current_delta {
+                                        Some(delta) => format!(
+                                            "{} for {}",
+                                            menu_state.status.to_string(),
+                                            format_elapsed(delta)
+                                        ),
+                                        None => menu_state.status.to_string(),
+                                    };
+                                    h_flex()
+                                        .child(
+                                            Label::new(status)
+                                                .size(LabelSize::Small)
+                                                .color(Color::Muted),
+                                        )
+                                        .into_any_element()
+                                }
                             })
                         } else {
                             let status = status.clone();
@@ -130,6 +146,14 @@ impl QuickActionBar {
                         }
                     })
                     .separator()
+                    .when(repl::kernel_launch_errored(editor.clone(), cx), |menu| {
+                        menu.entry("View Startup Log", None, {
+                            let editor = editor.clone();
+                            move |window, cx| {
+                                repl::open_startup_log(editor.clone(), window, cx);
+                            }
+                        })
+                    })
                     .custom_entry(
                         move |_window, _cx| {
                             Label::new(if has_nonempty_selection {
@@ -146,6 +170,16 @@ impl QuickActionBar {
                             }
                         },
                     )
+                    .item(
+                        ContextMenuEntry::new("Run All Cells")
+                            .disabled(!repl::has_active_session(editor.clone(), cx))
+                            .handler({
+                                let editor = editor.clone();
+                                move |window, cx| {
+                                    repl::run_all_cells(editor.clone(), window, cx);
+                                }
+                            }),
+                    )
                     .custom_entry(
                         move |_window, _cx| {
                             Label::new("Interrupt")
@@ -174,6 +208,16 @@ impl QuickActionBar {
                             }
                         },
                     )
+                    .item(
+                        ContextMenuEntry::new("Export Session…")
+                            .disabled(!repl::session_has_outputs(editor.clone(), cx))
+                            .handler({
+                                let editor = editor.clone();
+                                move |window, cx| {
+                                    repl::export_session(editor.clone(), window, cx);
+                                }
+                            }),
+                    )
                     .separator()
                     .custom_entry(
                         move |_window, _cx| {
@@ -203,10 +247,95 @@ impl QuickActionBar {
                             }
                         },
                     )
+                    .item(
+                        ContextMenuEntry::new("Restart Kernel and Run All")
+                            .disabled(matches!(status, KernelStatus::Restarting))
+                            .handler({
+                                let editor = editor.clone();
+                                move |window, cx| {
+                                    repl::restart_and_run_all(editor.clone(), window, cx);
+                                }
+                            }),
+                    )
                     .separator()
-                    .action("View Sessions", Box::new(repl::Sessions))
-                    // TODO: Add shut down all kernels action
-                    // .action("Shut Down all Kernels", Box::new(gpui::NoAction))
+                    .toggleable_entry(
+                        "Pin Kernel to This Editor",
+                        repl::is_kernel_pinned(editor.clone(), cx),
+                        IconPosition::Start,
+                        Some(Box::new(repl::ToggleKernelPin)),
+                        {
+                            let editor = editor.clone();
+                            move |_, cx| {
+                                repl::toggle_kernel_pin(editor.clone(), cx);
+                            }
+                        },
+                    )
+                    .map(|menu| {
+                        let default_kernel = repl::default_kernel_name(editor.clone(), cx);
+                        menu.item(
+                            ContextMenuEntry::new(match &default_kernel {
+                                Some(kernel_name) => {
+                                    format!("Clear Default Kernel ({kernel_name})")
+                                }
+                                None => "Clear Default Kernel".to_string(),
+                            })
+                            .disabled(default_kernel.is_none())
+                            .handler({
+                                let editor = editor.clone();
+                                move |_, cx| repl::clear_default_kernel(editor.clone(), cx)
+                            }),
+                        )
+                    })
+                    .entry("View Sessions", Some(Box::new(repl::Sessions)), {
+                        let editor = editor.clone();
+                        move |window, cx| {
+                            // Editors outside of any worktree see every session.
+                            let worktree_id = worktree_id_for_editor(editor.clone(), cx);
+                            if let Some(workspace) = Workspace::for_window(window, cx) {
+                                workspace.update(cx, |workspace, cx| {
+                                    repl::open_sessions(workspace, worktree_id, window, cx)
+                                });
+                            }
+                        }
+                    })
+                    .map(|menu| {
+                        if !repl::has_running_kernels(cx) && !repl::is_shutting_down_kernels(cx) {
+                            return menu.disabled_action(
+                                "Shut Down All Kernels",
+                                Box::new(repl::ShutdownAll),
+                            );
+                        }
+                        menu.custom_entry(
+                            |_window, cx| {
+                                let shutting_down = repl::is_shutting_down_kernels(cx);
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Label::new("Shut Down All Kernels")
+                                            .size(LabelSize::Small)
+                                            .color(Color::Error),
+                                    )
+                                    .when(shutting_down, |this| {
+                                        this.child(
+                                            Icon::new(IconName::ArrowCircle)
+                                                .size(IconSize::Small)
+                                                .color(Color::Muted)
+                                                .with_animation(
+                                                    "shut-down-all-kernels",
+                                                    Animation::new(Duration::from_secs(2)).repeat(),
+                                                    |icon, delta| {
+                                                        icon.transform(Transformation::rotate(
+                                                            percentage(delta),
+                                                        ))
+                                                    },
+                                                ),
+                                        )
+                                    })
+                                    .into_any_element()
+                            },
+                            |window, cx| repl::shutdown_all(window, cx),
+                        )
+                    })
                 })
                 .into()
             })
@@ -391,7 +520,7 @@ fn session_state(session: Entity<Session>, cx: &mut App) -> ReplMenuState {
             kernel_language: kernel_language.clone(),
             // TODO: Technically not shutdown, but indeterminate
             status: KernelStatus::Shutdown,
-            // current_delta: Duration::default(),
+            current_delta: None,
         }
     };
 
@@ -412,14 +541,22 @@ fn session_state(session: Entity<Session>, cx: &mut App) -> ReplMenuState {
                 status: session.kernel.status(),
                 ..fill_fields()
             },
-            ExecutionState::Busy => ReplMenuState {
-                tooltip: format!("Interrupt {} ({})", kernel_name, kernel_language).into(),
-                icon_is_animating: true,
-                popover_disabled: false,
-                indicator: None,
-                status: session.kernel.status(),
-                ..fill_fields()
-            },
+            ExecutionState::Busy => {
+                let current_delta = session.execution_elapsed();
+                let mut tooltip = format!("Interrupt {} ({})", kernel_name, kernel_language);
+                if let Some(delta) = current_delta {
+                    tooltip.push_str(&format!(", running for {}", format_elapsed(delta)));
+                }
+                ReplMenuState {
+                    tooltip: tooltip.into(),
+                    icon_is_animating: true,
+                    popover_disabled: false,
+                    indicator: None,
+                    status: session.kernel.status(),
+                    current_delta,
+                    ..fill_fields()
+                }
+            }
         },
         Kernel::StartingKernel(_) => ReplMenuState {
             tooltip: format!("{} is starting", kernel_name).into(),
@@ -457,3 +594,13 @@ fn session_state(session: Entity<Session>, cx: &mut App) -> ReplMenuState {
         },
     }
 }
+
+/// Formats the duration of an execution, e.g. `45s`, `2m 05s` or `1h 02m`.
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}